@@ -2,37 +2,316 @@ use std::io::{BufRead, Error as IoError, ErrorKind, Seek};
 
 use png::*;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Gray,
+    GrayAlpha,
+    Rgba,
+}
+
+impl ImageFormat {
+    pub fn bytes_per_pixel(self) -> u32 {
+        match self {
+            ImageFormat::Gray => 1,
+            ImageFormat::GrayAlpha => 2,
+            ImageFormat::Rgba => 4,
+        }
+    }
+}
+
 pub struct Image {
     pub width: u32,
     pub height: u32,
+    pub format: ImageFormat,
     pub data: Vec<u8>,
 }
 
 impl Image {
-    pub fn read<R: BufRead + Seek>(reader: R) -> Result<Self, DecodingError> {
+    fn unsupported(message: String) -> DecodingError {
+        DecodingError::IoError(IoError::new(ErrorKind::Unsupported, message))
+    }
+    /// The format rows of `color_type` are converted to.
+    fn output_format(color_type: ColorType, expand: bool) -> Result<ImageFormat, DecodingError> {
+        match color_type {
+            ColorType::Rgba | ColorType::Rgb => Ok(ImageFormat::Rgba),
+            ColorType::GrayscaleAlpha if expand => Ok(ImageFormat::Rgba),
+            ColorType::GrayscaleAlpha => Ok(ImageFormat::GrayAlpha),
+            ColorType::Grayscale if !expand => Ok(ImageFormat::Gray),
+            _ => Err(Self::unsupported(format!("unsupported color type {color_type:?}"))),
+        }
+    }
+    fn decoder<R: BufRead + Seek>(reader: R, expand: bool) -> Result<Reader<R>, DecodingError> {
         let mut decoder = Decoder::new(reader);
-        decoder.set_transformations(Transformations::ALPHA);
-        let mut image_reader = decoder.read_info()?;
+        decoder.set_transformations(if expand {
+            Transformations::ALPHA
+        } else {
+            Transformations::EXPAND
+        });
+        decoder.read_info()
+    }
+    fn decode<R: BufRead + Seek>(reader: R, expand: bool) -> Result<Self, DecodingError> {
+        let mut image_reader = Self::decoder(reader, expand)?;
         let mut data = vec![0; image_reader.output_buffer_size().unwrap()];
         let info = image_reader.next_frame(&mut data)?;
         data.truncate(info.buffer_size());
         assert_eq!(info.bit_depth, BitDepth::Eight);
+        let format = Self::output_format(info.color_type, expand)?;
         match info.color_type {
-            ColorType::Rgba => {}
-            ColorType::GrayscaleAlpha => {
-                data = data.chunks_exact(2).flat_map(|x| [x[0], x[0], x[0], x[1]]).collect();
+            ColorType::Rgb => {
+                data = data.chunks_exact(3).flat_map(|x| [x[0], x[1], x[2], 255]).collect();
             }
-            _ => {
-                return Err(DecodingError::IoError(IoError::new(
-                    ErrorKind::Unsupported,
-                    format!("unsupported color type {:?}", info.color_type),
-                )));
+            ColorType::GrayscaleAlpha if expand => {
+                data = data.chunks_exact(2).flat_map(|x| [x[0], x[0], x[0], x[1]]).collect();
             }
+            _ => {}
         }
         Ok(Image {
             width: info.width,
             height: info.height,
+            format,
+            data,
+        })
+    }
+    /// Reads a PNG image, converting it to RGBA.
+    pub fn read<R: BufRead + Seek>(reader: R) -> Result<Self, DecodingError> {
+        Self::decode(reader, true)
+    }
+    /// Reads a PNG image, keeping grayscale images as one or two channels. Useful for masks.
+    pub fn read_unexpanded<R: BufRead + Seek>(reader: R) -> Result<Self, DecodingError> {
+        Self::decode(reader, false)
+    }
+    /// Converts a grayscale image to RGBA, or copies an image that is already RGBA.
+    pub fn to_rgba(&self) -> Image {
+        let data = match self.format {
+            ImageFormat::Gray => self.data.iter().flat_map(|x| [*x, *x, *x, 255]).collect(),
+            ImageFormat::GrayAlpha => self
+                .data
+                .chunks_exact(2)
+                .flat_map(|x| [x[0], x[0], x[0], x[1]])
+                .collect(),
+            ImageFormat::Rgba => self.data.clone(),
+        };
+        Image {
+            width: self.width,
+            height: self.height,
+            format: ImageFormat::Rgba,
             data,
+        }
+    }
+    /// Starts reading a PNG image one row at a time, converting it to RGBA. Only one row is decoded
+    /// in memory at once, so large images can be written straight into their destination.
+    pub fn stream<R: BufRead + Seek>(reader: R) -> Result<ImageStream<R>, DecodingError> {
+        ImageStream::new(reader, true)
+    }
+    /// Like [`Image::stream`], but keeps grayscale images as one or two channels.
+    pub fn stream_unexpanded<R: BufRead + Seek>(reader: R) -> Result<ImageStream<R>, DecodingError> {
+        ImageStream::new(reader, false)
+    }
+}
+
+/// A PNG image being decoded row by row, created by [`Image::stream`]. Interlaced images aren't
+/// supported, since their rows are decoded out of order; use [`Image::read`] for those.
+pub struct ImageStream<R: BufRead + Seek> {
+    reader: Reader<R>,
+    color_type: ColorType,
+    format: ImageFormat,
+    row: Vec<u8>,
+}
+
+impl<R: BufRead + Seek> ImageStream<R> {
+    fn new(reader: R, expand: bool) -> Result<Self, DecodingError> {
+        let reader = Image::decoder(reader, expand)?;
+        if reader.info().interlaced {
+            return Err(Image::unsupported("interlaced images can't be streamed".to_string()));
+        }
+        let (color_type, bit_depth) = reader.output_color_type();
+        if bit_depth != BitDepth::Eight {
+            return Err(Image::unsupported(format!("unsupported bit depth {bit_depth:?}")));
+        }
+        let format = Image::output_format(color_type, expand)?;
+        let row_size = reader
+            .output_line_size(reader.info().width)
+            .ok_or(DecodingError::LimitsExceeded)?;
+        Ok(ImageStream {
+            reader,
+            color_type,
+            format,
+            row: vec![0; row_size],
         })
     }
+    pub fn width(&self) -> u32 {
+        self.reader.info().width
+    }
+    pub fn height(&self) -> u32 {
+        self.reader.info().height
+    }
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+    /// Size in bytes of one converted row.
+    pub fn row_size(&self) -> usize {
+        self.width() as usize * self.format.bytes_per_pixel() as usize
+    }
+    /// Decodes the next row into the start of `output`, which must be at least [`ImageStream::row_size`]
+    /// bytes. Returns false once every row has been read.
+    pub fn read_row(&mut self, output: &mut [u8]) -> Result<bool, DecodingError> {
+        if self.reader.read_row(&mut self.row)?.is_none() {
+            return Ok(false);
+        }
+        let output = &mut output[..self.row_size()];
+        match self.color_type {
+            ColorType::Rgb => {
+                for (pixel, x) in output.chunks_exact_mut(4).zip(self.row.chunks_exact(3)) {
+                    pixel.copy_from_slice(&[x[0], x[1], x[2], 255]);
+                }
+            }
+            ColorType::GrayscaleAlpha if self.format == ImageFormat::Rgba => {
+                for (pixel, x) in output.chunks_exact_mut(4).zip(self.row.chunks_exact(2)) {
+                    pixel.copy_from_slice(&[x[0], x[0], x[0], x[1]]);
+                }
+            }
+            _ => output.copy_from_slice(&self.row),
+        }
+        Ok(true)
+    }
+    /// Decodes the remaining rows into `output`, starting each row `stride` bytes after the previous
+    /// one. The stride can be larger than [`ImageStream::row_size`], such as for a texture staging buffer
+    /// with aligned rows.
+    pub fn read_into(&mut self, output: &mut [u8], stride: usize) -> Result<(), DecodingError> {
+        assert!(stride >= self.row_size(), "stride is smaller than a row");
+        for row in output.chunks_mut(stride) {
+            if !self.read_row(row)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+        io::Cursor,
+    };
+
+    use super::*;
+
+    thread_local! {
+        static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+        static PEAK: Cell<isize> = const { Cell::new(0) };
+    }
+
+    /// Tracks the bytes allocated by each thread, so tests running in parallel don't affect each other.
+    struct CountingAllocator;
+
+    impl CountingAllocator {
+        fn track(change: isize) {
+            let _ = ALLOCATED.try_with(|allocated| {
+                allocated.set(allocated.get() + change);
+                let _ = PEAK.try_with(|peak| peak.set(peak.get().max(allocated.get())));
+            });
+        }
+        /// The most bytes the current thread had allocated at once while running `f`, beyond what it had
+        /// allocated before.
+        fn peak_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+            let start = ALLOCATED.with(Cell::get);
+            PEAK.with(|peak| peak.set(start));
+            let result = f();
+            (result, (PEAK.with(Cell::get) - start) as usize)
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            Self::track(layout.size() as isize);
+            unsafe { System.alloc(layout) }
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            Self::track(-(layout.size() as isize));
+            unsafe { System.dealloc(ptr, layout) }
+        }
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            Self::track(new_size as isize - layout.size() as isize);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn encode_png(width: u32, height: u32, color_type: ColorType, data: &[u8]) -> Vec<u8> {
+        let mut png = Vec::new();
+        let mut encoder = Encoder::new(&mut png, width, height);
+        encoder.set_color(color_type);
+        encoder.set_depth(BitDepth::Eight);
+        encoder.write_header().unwrap().write_image_data(data).unwrap();
+        png
+    }
+
+    #[test]
+    fn read_unexpanded_keeps_grayscale() {
+        let data = [0, 64, 128, 192, 255, 32];
+        let png = encode_png(3, 2, ColorType::Grayscale, &data);
+        let image = Image::read_unexpanded(Cursor::new(png)).unwrap();
+        assert_eq!(image.format, ImageFormat::Gray);
+        assert_eq!(image.data.len(), 3 * 2);
+        assert_eq!(image.data, data);
+    }
+
+    #[test]
+    fn to_rgba_expands_grayscale() {
+        let png = encode_png(2, 1, ColorType::Grayscale, &[10, 20]);
+        let image = Image::read_unexpanded(Cursor::new(png.clone())).unwrap().to_rgba();
+        assert_eq!(image.format, ImageFormat::Rgba);
+        assert_eq!(image.data, Image::read(Cursor::new(png)).unwrap().data);
+    }
+
+    #[test]
+    fn stream_rows_match_read() {
+        let rgb: Vec<u8> = (0..4 * 3 * 3).map(|x| (x * 7) as u8).collect();
+        let gray_alpha: Vec<u8> = (0..4 * 3 * 2).map(|x| (x * 11) as u8).collect();
+        for (color_type, data) in [(ColorType::Rgb, rgb), (ColorType::GrayscaleAlpha, gray_alpha)] {
+            let png = encode_png(4, 3, color_type, &data);
+            let image = Image::read(Cursor::new(png.clone())).unwrap();
+            let mut stream = Image::stream(Cursor::new(png)).unwrap();
+            assert_eq!((stream.width(), stream.height()), (image.width, image.height));
+            assert_eq!(stream.format(), image.format);
+            let mut row = vec![0; stream.row_size()];
+            for expected in image.data.chunks_exact(stream.row_size()) {
+                assert!(stream.read_row(&mut row).unwrap());
+                assert_eq!(row, expected);
+            }
+            assert!(!stream.read_row(&mut row).unwrap());
+        }
+    }
+
+    #[test]
+    fn stream_peak_memory_is_bounded() {
+        let (width, height) = (1024, 1024);
+        let data: Vec<u8> = (0..width * height * 3).map(|x| (x % 251) as u8).collect();
+        let png = encode_png(width, height, ColorType::Rgb, &data);
+        let image_size = (width * height * 4) as usize;
+
+        let (image, read_peak) = CountingAllocator::peak_during(|| Image::read(Cursor::new(png.as_slice())).unwrap());
+        assert_eq!(image.data.len(), image_size);
+        assert!(read_peak >= image_size);
+
+        // Rows are written to the same buffer, like a caller streaming into a texture would.
+        let (rows, stream_peak) = CountingAllocator::peak_during(|| {
+            let mut stream = Image::stream(Cursor::new(png.as_slice())).unwrap();
+            let mut row = vec![0; stream.row_size()];
+            let mut rows = 0;
+            while stream.read_row(&mut row).unwrap() {
+                rows += 1;
+            }
+            rows
+        });
+        assert_eq!(rows, height);
+        assert!(
+            stream_peak < image_size / 8,
+            "streaming used {stream_peak} bytes for a {image_size} byte image"
+        );
+    }
 }
@@ -2,10 +2,11 @@ pub mod image;
 pub mod serde_util;
 
 use std::{
+    collections::BTreeMap,
     error::Error,
     fmt::Display,
     fs::File,
-    io::{BufReader, Error as IoError, ErrorKind, Read, Seek},
+    io::{BufReader, Cursor, Error as IoError, ErrorKind, Read, Seek},
     path::PathBuf,
 };
 
@@ -61,6 +62,8 @@ pub trait AssetSource: Display {
     where
         Self: 'a;
     fn load(&mut self, path: &AssetPath) -> Result<BufReader<Self::Reader<'_>>>;
+    /// Lists the entries of a directory as paths relative to the source, with subdirectories ending in
+    /// `/`. Some sources also list the contents of subdirectories.
     fn read_directory(&self, path: &AssetPath) -> Result<Vec<String>>;
 }
 
@@ -90,14 +93,22 @@ impl AssetSource for DirectorySource {
         let mut entries: Vec<_> = std::fs::read_dir(dir_path)
             .map_err(|e| AssetError::with_path(self.0.display(), path, e))?
             .filter_map(|res| {
-                res.ok().filter(|e| e.file_type().unwrap().is_file()).map(|e| {
-                    e.path()
-                        .strip_prefix(&self.0)
-                        .expect("invalid path")
-                        .to_str()
-                        .expect("path not UTF-8")
-                        .to_string()
-                })
+                let entry = res.ok()?;
+                let file_type = entry.file_type().ok()?;
+                if !file_type.is_file() && !file_type.is_dir() {
+                    return None;
+                }
+                let mut path = entry
+                    .path()
+                    .strip_prefix(&self.0)
+                    .expect("invalid path")
+                    .to_str()
+                    .expect("path not UTF-8")
+                    .to_string();
+                if file_type.is_dir() {
+                    path.push('/');
+                }
+                Some(path)
             })
             .collect();
         entries.sort();
@@ -145,6 +156,52 @@ impl AssetSource for ArchiveSource {
     }
 }
 
+/// Assets held in memory, such as generated or embedded files. Directories are implied by the paths of
+/// the assets in them, and listing a directory also lists the contents of its subdirectories.
+#[derive(Debug, Default)]
+pub struct MemorySource(BTreeMap<String, Vec<u8>>);
+
+impl MemorySource {
+    pub fn new() -> Self {
+        MemorySource::default()
+    }
+    pub fn insert(&mut self, path: impl Into<String>, data: impl Into<Vec<u8>>) {
+        self.0.insert(path.into(), data.into());
+    }
+}
+impl Display for MemorySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("memory")
+    }
+}
+impl AssetSource for MemorySource {
+    type Reader<'a> = Cursor<&'a [u8]>;
+    fn load(&mut self, path: &AssetPath) -> Result<BufReader<Self::Reader<'_>>> {
+        match self.0.get(path) {
+            Some(data) => Ok(BufReader::new(Cursor::new(data.as_slice()))),
+            None => Err(AssetError::with_path(&*self, path, IoError::from(ErrorKind::NotFound))),
+        }
+    }
+    fn read_directory(&self, path: &AssetPath) -> Result<Vec<String>> {
+        let path = path.trim_matches('/');
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{path}/")
+        };
+        let entries: Vec<String> = self
+            .0
+            .keys()
+            .filter(|name| name.starts_with(&prefix))
+            .cloned()
+            .collect();
+        if entries.is_empty() && !path.is_empty() {
+            return Err(AssetError::with_path(self, path, IoError::from(ErrorKind::NotFound)));
+        }
+        Ok(entries)
+    }
+}
+
 #[derive(Debug)]
 pub struct SubdirectorySource<'a, S> {
     base: &'a mut S,
@@ -199,15 +256,40 @@ pub fn load_yaml<S: AssetSource, T: DeserializeOwned>(asset_source: &mut S, path
     serde_yml::from_reader(reader)
         .map_err(|e| AssetError::with_path(asset_source, path, IoError::new(ErrorKind::InvalidData, e)))
 }
+fn map_image_error<S: AssetSource>(asset_source: &S, path: &AssetPath, error: png::DecodingError) -> AssetError {
+    let error = match error {
+        png::DecodingError::IoError(error) => error,
+        png::DecodingError::Format(_) => IoError::new(ErrorKind::InvalidData, error),
+        png::DecodingError::Parameter(_) => IoError::new(ErrorKind::InvalidInput, error),
+        png::DecodingError::LimitsExceeded => IoError::new(ErrorKind::FileTooLarge, error),
+    };
+    AssetError::with_path(asset_source, path, error)
+}
 pub fn load_image<S: AssetSource>(asset_source: &mut S, path: &AssetPath) -> Result<Image> {
     let reader = asset_source.load(path)?;
-    Image::read(reader).map_err(|e| {
-        let error = match e {
-            png::DecodingError::IoError(error) => error,
-            png::DecodingError::Format(_) => IoError::new(ErrorKind::InvalidData, e),
-            png::DecodingError::Parameter(_) => IoError::new(ErrorKind::InvalidInput, e),
-            png::DecodingError::LimitsExceeded => IoError::new(ErrorKind::FileTooLarge, e),
-        };
-        AssetError::with_path(asset_source, path, error)
-    })
+    Image::read(reader).map_err(|e| map_image_error(asset_source, path, e))
+}
+/// Loads an image without converting grayscale to RGBA. See [`Image::read_unexpanded`].
+pub fn load_image_unexpanded<S: AssetSource>(asset_source: &mut S, path: &AssetPath) -> Result<Image> {
+    let reader = asset_source.load(path)?;
+    Image::read_unexpanded(reader).map_err(|e| map_image_error(asset_source, path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_source_lists_directories() {
+        let mut source = MemorySource::new();
+        source.insert("a.txt", "a");
+        source.insert("dir/b.txt", "b");
+        source.insert("dir/sub/c.txt", "c");
+        source.insert("directory.txt", "d");
+        assert_eq!(load_string(&mut source, "dir/b.txt").unwrap(), "b");
+        assert_eq!(source.read_directory("dir").unwrap(), ["dir/b.txt", "dir/sub/c.txt"]);
+        assert_eq!(source.read_directory("").unwrap().len(), 4);
+        assert!(source.read_directory("missing").is_err());
+        assert!(source.load("missing.txt").is_err());
+    }
 }
@@ -2,6 +2,10 @@ use std::{fmt::Display, marker::PhantomData, str::FromStr};
 
 use serde::{Deserialize, Deserializer, de};
 
+/// Deserializes `T` from either a string, parsed with its `FromStr` impl, or a map, deserialized with its
+/// `Deserialize` impl. Use it with `#[serde(deserialize_with = "string_or_struct")]` on fields that
+/// have a short string form, such as colors written as hex strings. Other forms, like sequences or
+/// numbers, are rejected.
 pub fn string_or_struct<'de, T, D>(deserializer: D) -> Result<T, D::Error>
 where
     T: Deserialize<'de> + FromStr<Err: Display>,
@@ -41,3 +45,58 @@ where
 
     deserializer.deserialize_any(StringOrStruct(PhantomData))
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use silica_color::Rgba;
+
+    use super::*;
+
+    /// Written as `"16"` for a square, or as a map.
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Size {
+        width: u32,
+        height: u32,
+    }
+
+    impl FromStr for Size {
+        type Err = std::num::ParseIntError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let size = s.parse()?;
+            Ok(Size {
+                width: size,
+                height: size,
+            })
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Config {
+        #[serde(deserialize_with = "string_or_struct")]
+        size: Size,
+        #[serde(deserialize_with = "string_or_struct")]
+        color: Rgba,
+    }
+
+    #[test]
+    fn deserialize_string_forms() {
+        let config: Config = serde_yml::from_str("size: '16'\ncolor: '#ff0000'").unwrap();
+        assert_eq!(config.size, Size { width: 16, height: 16 });
+        assert_eq!(config.color, Rgba::RED);
+    }
+
+    #[test]
+    fn deserialize_struct_forms() {
+        let config: Config =
+            serde_yml::from_str("size: { width: 16, height: 9 }\ncolor: { r: 0.0, g: 1.0, b: 0.0 }").unwrap();
+        assert_eq!(config.size, Size { width: 16, height: 9 });
+        assert_eq!(config.color, Rgba::GREEN);
+    }
+
+    #[test]
+    fn reject_other_forms() {
+        assert!(serde_yml::from_str::<Config>("size: [16, 9]\ncolor: '#ff0000'").is_err());
+        assert!(serde_yml::from_str::<Config>("size: '16'\ncolor: 'red'").is_err());
+    }
+}
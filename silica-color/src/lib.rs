@@ -26,9 +26,9 @@
 //! the color crates I've seen are *heavily* focused on color space management and conversion, and
 //! often fall flat if you need to do more than the simplest of operations on the colors themselves.
 //! Here are some specific needs I have:
-//! - Stored as repr(C) f32s to be easy to use in wgpu shaders.
+//! - Stored as repr(C) f32s to be easy to use in wgpu shaders. (`bytemuck` feature, enabled by default.)
 //! - Serialize and deserialize in a straightforward way. Alpha should default to 1 if not specified, since opaque
-//!   colors are very common.
+//!   colors are very common. (`serde` feature, enabled by default.)
 //! - Convert to and from u32 linear hex codes.
 //! - Constants for black and white.
 //!
@@ -37,20 +37,35 @@
 
 use std::hash::{Hash, Hasher};
 
-use bytemuck::{Pod, Zeroable};
-use serde::{Deserialize, Serialize};
-
+#[cfg(feature = "serde")]
 fn default_alpha() -> f32 {
     1.0
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+fn srgb_to_linear(x: f32) -> f32 {
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
+    }
+}
+fn linear_to_srgb(x: f32) -> f32 {
+    if x <= 0.0031308 {
+        x * 12.92
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Rgba {
     pub r: f32,
     pub g: f32,
     pub b: f32,
-    #[serde(default = "default_alpha")]
+    #[cfg_attr(feature = "serde", serde(default = "default_alpha"))]
     pub a: f32,
 }
 
@@ -63,14 +78,21 @@ impl Rgba {
     pub const YELLOW: Rgba = Rgba::new_opaque(1.0, 1.0, 0.0);
     pub const MAGENTA: Rgba = Rgba::new_opaque(1.0, 0.0, 1.0);
     pub const CYAN: Rgba = Rgba::new_opaque(0.0, 1.0, 1.0);
+    pub const DEFAULT_EPSILON: f32 = 1e-4;
     pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
         Rgba { r, g, b, a }
     }
     pub const fn new_opaque(r: f32, g: f32, b: f32) -> Self {
         Rgba { r, g, b, a: 1.0 }
     }
-    pub fn from_u8(r: u8, g: u8, b: u8, a: u8) -> Self {
-        fn to_f32(x: u8) -> f32 {
+    pub const fn gray(v: f32) -> Self {
+        Rgba::new_opaque(v, v, v)
+    }
+    pub const fn gray_alpha(v: f32, a: f32) -> Self {
+        Rgba::new(v, v, v, a)
+    }
+    pub const fn from_u8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        const fn to_f32(x: u8) -> f32 {
             (x as f32) / 255.0
         }
         Rgba {
@@ -80,12 +102,108 @@ impl Rgba {
             a: to_f32(a),
         }
     }
+    /// Creates a color from a linear hex code in ARGB order, the same as `From<u32>`.
+    pub const fn from_hex(value: u32) -> Self {
+        let [a, r, g, b] = value.to_be_bytes();
+        Rgba::from_u8(r, g, b, a)
+    }
+    /// Creates an opaque color from a linear hex code in RGB order. The highest byte is ignored.
+    pub const fn from_hex_rgb(value: u32) -> Self {
+        Rgba::from_hex(value | 0xFF000000)
+    }
+    /// Creates a color from sRGB-encoded channels, such as a hex code from a design tool. Alpha is
+    /// not gamma-encoded, so it is only scaled to 0-1.
+    pub fn from_srgb_u8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        fn to_f32(x: u8) -> f32 {
+            srgb_to_linear((x as f32) / 255.0)
+        }
+        Rgba {
+            r: to_f32(r),
+            g: to_f32(g),
+            b: to_f32(b),
+            a: (a as f32) / 255.0,
+        }
+    }
+    /// Converts the color to sRGB-encoded channels, the inverse of [`Rgba::from_srgb_u8`].
+    pub fn to_srgb_u8(self) -> (u8, u8, u8, u8) {
+        fn to_u8(x: f32) -> u8 {
+            (x.clamp(0.0, 1.0) * 255.0).round() as u8
+        }
+        (
+            to_u8(linear_to_srgb(self.r.clamp(0.0, 1.0))),
+            to_u8(linear_to_srgb(self.g.clamp(0.0, 1.0))),
+            to_u8(linear_to_srgb(self.b.clamp(0.0, 1.0))),
+            to_u8(self.a),
+        )
+    }
+    /// Creates a color from channels in the range 0-100, as used by many design tools.
+    pub fn from_percent(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Rgba {
+            r: r / 100.0,
+            g: g / 100.0,
+            b: b / 100.0,
+            a: a / 100.0,
+        }
+    }
+    pub fn to_percent(&self) -> (f32, f32, f32, f32) {
+        (self.r * 100.0, self.g * 100.0, self.b * 100.0, self.a * 100.0)
+    }
+    pub const fn from_array(array: [f32; 4]) -> Self {
+        Rgba::new(array[0], array[1], array[2], array[3])
+    }
+    pub const fn to_array(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+    /// Creates a color from hue (in degrees), saturation, and value. This is HSV in linear space, so it
+    /// won't match color pickers that use sRGB HSV.
+    pub fn from_hsv(h: f32, s: f32, v: f32, a: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let c = v * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = v - c;
+        Rgba::new(r + m, g + m, b + m, a)
+    }
+    /// Converts the color to linear HSV, the inverse of [`Rgba::from_hsv`]. Grays have a hue and
+    /// saturation of 0.
+    pub fn to_hsv(self) -> (f32, f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == self.r {
+            60.0 * ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            60.0 * ((self.b - self.r) / delta + 2.0)
+        } else {
+            60.0 * ((self.r - self.g) / delta + 4.0)
+        };
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        (h, s, max, self.a)
+    }
     pub fn to_u32(&self) -> u32 {
         fn to_u8(x: f32) -> u8 {
-            (x * 255.0) as u8
+            (x.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
         }
         u32::from_be_bytes([to_u8(self.a), to_u8(self.r), to_u8(self.g), to_u8(self.b)])
     }
+    pub fn with_red(self, r: f32) -> Self {
+        Rgba { r, ..self }
+    }
+    pub fn with_green(self, g: f32) -> Self {
+        Rgba { g, ..self }
+    }
+    pub fn with_blue(self, b: f32) -> Self {
+        Rgba { b, ..self }
+    }
     pub fn with_alpha(self, a: f32) -> Self {
         Rgba { a, ..self }
     }
@@ -95,6 +213,157 @@ impl Rgba {
             ..self
         }
     }
+    /// Multiplies the color channels by alpha.
+    pub fn premultiply(self) -> Self {
+        Rgba {
+            r: self.r * self.a,
+            g: self.g * self.a,
+            b: self.b * self.a,
+            a: self.a,
+        }
+    }
+    /// Divides the color channels by alpha, the inverse of [`Rgba::premultiply`]. Colors with zero
+    /// alpha are returned unchanged.
+    pub fn unpremultiply(self) -> Self {
+        if self.a == 0.0 {
+            return self;
+        }
+        Rgba {
+            r: self.r / self.a,
+            g: self.g / self.a,
+            b: self.b / self.a,
+            a: self.a,
+        }
+    }
+    /// Linearly interpolates all four channels, with `t` clamped to 0-1. Since colors are linear, this
+    /// matches how blending and `mix` behave in shaders.
+    pub fn lerp(self, other: Rgba, t: f32) -> Self {
+        self.lerp_unclamped(other, t.clamp(0.0, 1.0))
+    }
+    /// Same as [`Rgba::lerp`], but `t` is not clamped, so it can extrapolate past either color.
+    pub fn lerp_unclamped(self, other: Rgba, t: f32) -> Self {
+        Rgba {
+            r: self.r * (1.0 - t) + other.r * t,
+            g: self.g * (1.0 - t) + other.g * t,
+            b: self.b * (1.0 - t) + other.b * t,
+            a: self.a * (1.0 - t) + other.a * t,
+        }
+    }
+    /// Whether all four channels are within `epsilon` of each other. Unlike `PartialEq` and `Hash`, this
+    /// tolerates the rounding error from arithmetic.
+    pub fn approx_eq(self, other: Rgba, epsilon: f32) -> bool {
+        (self.r - other.r).abs() <= epsilon
+            && (self.g - other.g).abs() <= epsilon
+            && (self.b - other.b).abs() <= epsilon
+            && (self.a - other.a).abs() <= epsilon
+    }
+    /// Per-channel minimum, including alpha.
+    pub fn min(self, other: Rgba) -> Self {
+        Rgba {
+            r: self.r.min(other.r),
+            g: self.g.min(other.g),
+            b: self.b.min(other.b),
+            a: self.a.min(other.a),
+        }
+    }
+    /// Per-channel maximum, including alpha.
+    pub fn max(self, other: Rgba) -> Self {
+        Rgba {
+            r: self.r.max(other.r),
+            g: self.g.max(other.g),
+            b: self.b.max(other.b),
+            a: self.a.max(other.a),
+        }
+    }
+    /// Clamps all four channels to 0-1.
+    pub fn clamp(self) -> Self {
+        Rgba {
+            a: self.a.clamp(0.0, 1.0),
+            ..self.clamp_rgb()
+        }
+    }
+    /// Same as [`Rgba::clamp`].
+    pub fn saturate(self) -> Self {
+        self.clamp()
+    }
+    /// Clamps all four channels to `min`-`max`.
+    pub fn clamp_range(self, min: f32, max: f32) -> Self {
+        Rgba {
+            r: self.r.clamp(min, max),
+            g: self.g.clamp(min, max),
+            b: self.b.clamp(min, max),
+            a: self.a.clamp(min, max),
+        }
+    }
+    /// Clamps the color channels to 0-1, leaving alpha alone.
+    pub fn clamp_rgb(self) -> Self {
+        Rgba {
+            r: self.r.clamp(0.0, 1.0),
+            g: self.g.clamp(0.0, 1.0),
+            b: self.b.clamp(0.0, 1.0),
+            a: self.a,
+        }
+    }
+    /// Composites this color over `background` with straight (not premultiplied) alpha.
+    pub fn over(self, background: Rgba) -> Self {
+        let a = self.a + background.a * (1.0 - self.a);
+        if a == 0.0 {
+            return Rgba::new(0.0, 0.0, 0.0, 0.0);
+        }
+        let bg_weight = background.a * (1.0 - self.a);
+        Rgba {
+            r: (self.r * self.a + background.r * bg_weight) / a,
+            g: (self.g * self.a + background.g * bg_weight) / a,
+            b: (self.b * self.a + background.b * bg_weight) / a,
+            a,
+        }
+    }
+    /// Returns `steps` evenly spaced colors from this color to `other`, including both ends.
+    pub fn gradient(self, other: Rgba, steps: usize) -> impl Iterator<Item = Rgba> {
+        let divisor = steps.saturating_sub(1).max(1) as f32;
+        (0..steps).map(move |i| self.lerp(other, i as f32 / divisor))
+    }
+    /// Relative luminance using the Rec. 709 coefficients.
+    pub fn luminance(self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+    /// WCAG contrast ratio between two colors, from 1 (no contrast) to 21 (black and white). Alpha is
+    /// ignored.
+    pub fn contrast_ratio(self, other: Rgba) -> f32 {
+        let a = self.luminance();
+        let b = other.luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+    /// Replaces the color channels with the luminance, preserving alpha.
+    pub fn grayscale(self) -> Self {
+        let l = self.luminance();
+        Rgba {
+            r: l,
+            g: l,
+            b: l,
+            a: self.a,
+        }
+    }
+    /// Scales the color channels by `2^stops`, preserving alpha. The result isn't clamped, use
+    /// [`Rgba::clamp_rgb`] if needed.
+    pub fn adjust_exposure(self, stops: f32) -> Self {
+        let scale = stops.exp2();
+        Rgba {
+            r: self.r * scale,
+            g: self.g * scale,
+            b: self.b * scale,
+            a: self.a,
+        }
+    }
+    /// Raises each color channel to the power of `gamma`, preserving alpha.
+    pub fn apply_gamma(self, gamma: f32) -> Self {
+        Rgba {
+            r: self.r.powf(gamma),
+            g: self.g.powf(gamma),
+            b: self.b.powf(gamma),
+            a: self.a,
+        }
+    }
 }
 impl Default for Rgba {
     fn default() -> Self {
@@ -140,22 +409,85 @@ impl std::ops::MulAssign for Rgba {
     }
 }
 
+/// Component-wise, including alpha.
+impl std::ops::Add for Rgba {
+    type Output = Self;
+    fn add(self, rhs: Rgba) -> Self {
+        Rgba {
+            r: self.r + rhs.r,
+            g: self.g + rhs.g,
+            b: self.b + rhs.b,
+            a: self.a + rhs.a,
+        }
+    }
+}
+impl std::ops::AddAssign for Rgba {
+    fn add_assign(&mut self, rhs: Rgba) {
+        self.r += rhs.r;
+        self.g += rhs.g;
+        self.b += rhs.b;
+        self.a += rhs.a;
+    }
+}
+/// Component-wise, including alpha.
+impl std::ops::Sub for Rgba {
+    type Output = Self;
+    fn sub(self, rhs: Rgba) -> Self {
+        Rgba {
+            r: self.r - rhs.r,
+            g: self.g - rhs.g,
+            b: self.b - rhs.b,
+            a: self.a - rhs.a,
+        }
+    }
+}
+impl std::ops::SubAssign for Rgba {
+    fn sub_assign(&mut self, rhs: Rgba) {
+        self.r -= rhs.r;
+        self.g -= rhs.g;
+        self.b -= rhs.b;
+        self.a -= rhs.a;
+    }
+}
+
+impl From<[f32; 4]> for Rgba {
+    fn from(value: [f32; 4]) -> Self {
+        Rgba::from_array(value)
+    }
+}
+impl From<Rgba> for [f32; 4] {
+    fn from(value: Rgba) -> Self {
+        value.to_array()
+    }
+}
 impl From<u32> for Rgba {
     fn from(value: u32) -> Self {
-        let bytes = value.to_be_bytes();
-        Rgba::from_u8(bytes[1], bytes[2], bytes[3], bytes[0])
+        Rgba::from_hex(value)
     }
 }
 impl std::str::FromStr for Rgba {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.strip_prefix('#').unwrap_or(s);
+        // Shorthand hex codes repeat each digit, so "f08" is the same as "ff0088". The 4-digit form
+        // puts alpha last like CSS, so "f008" is the same as "88ff0000".
+        let expanded;
+        let s = if s.len() == 3 || s.len() == 4 {
+            let mut chars = s.chars().collect::<Vec<_>>();
+            chars.rotate_right(s.len() - 3);
+            expanded = chars.into_iter().flat_map(|c| [c, c]).collect::<String>();
+            expanded.as_str()
+        } else {
+            s
+        };
         let has_alpha = if s.len() == 8 {
             true
         } else if s.len() == 6 {
             false
         } else {
-            return Err("wrong length".to_string());
+            return Err(format!(
+                "wrong length for hex color, expected 3, 4, 6, or 8 digits: {s}"
+            ));
         };
         let mut value = u32::from_str_radix(s, 16).map_err(|e| e.to_string())?;
         if !has_alpha {
@@ -190,3 +522,437 @@ impl Hash for Rgba {
         f32_hash(state, self.a);
     }
 }
+
+/// Compares `f32`s the same way [`f32_hash`] hashes them.
+#[inline]
+fn f32_key_eq(a: f32, b: f32) -> bool {
+    (a == 0.0 && b == 0.0) || (a.is_nan() && b.is_nan()) || a.to_bits() == b.to_bits()
+}
+
+/// Wrapper for using `Rgba` as a map key. Equality matches the `Hash` impl: all NANs are equal, the sign
+/// of zero is ignored, and everything else is compared bit for bit.
+#[derive(Debug, Clone, Copy)]
+pub struct HashRgba(pub Rgba);
+
+impl PartialEq for HashRgba {
+    fn eq(&self, other: &Self) -> bool {
+        f32_key_eq(self.0.r, other.0.r)
+            && f32_key_eq(self.0.g, other.0.g)
+            && f32_key_eq(self.0.b, other.0.b)
+            && f32_key_eq(self.0.a, other.0.a)
+    }
+}
+impl Eq for HashRgba {}
+impl Hash for HashRgba {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+impl From<Rgba> for HashRgba {
+    fn from(value: Rgba) -> Self {
+        HashRgba(value)
+    }
+}
+impl From<HashRgba> for Rgba {
+    fn from(value: HashRgba) -> Self {
+        value.0
+    }
+}
+
+/// Serializes `Rgba` as `[r, g, b]` or `[r, g, b, a]`, for use with `#[serde(with = "silica_color::serde_array")]`.
+/// Alpha is left out when it's 1, and defaults to 1 when deserializing.
+#[cfg(feature = "serde")]
+pub mod serde_array {
+    use serde::{
+        Deserializer, Serializer,
+        de::{Error, SeqAccess, Visitor},
+        ser::SerializeSeq,
+    };
+
+    use crate::Rgba;
+
+    pub fn serialize<S: Serializer>(color: &Rgba, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = if color.a == 1.0 { 3 } else { 4 };
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for channel in &color.to_array()[..len] {
+            seq.serialize_element(channel)?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rgba, D::Error> {
+        struct ArrayVisitor;
+        impl<'de> Visitor<'de> for ArrayVisitor {
+            type Value = Rgba;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an array of 3 or 4 numbers")
+            }
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Rgba, A::Error> {
+                let mut channels = [1.0; 4];
+                for (i, channel) in channels.iter_mut().enumerate() {
+                    match seq.next_element()? {
+                        Some(value) => *channel = value,
+                        None if i == 3 => break,
+                        None => return Err(A::Error::invalid_length(i, &self)),
+                    }
+                }
+                if seq.next_element::<f32>()?.is_some() {
+                    return Err(A::Error::invalid_length(5, &self));
+                }
+                Ok(Rgba::from_array(channels))
+            }
+        }
+        deserializer.deserialize_seq(ArrayVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_below_range() {
+        let color = Rgba::new(-0.5, -1.0, 0.0, -0.25);
+        assert_eq!(color.clamp(), Rgba::new(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(color.clamp_rgb(), Rgba::new(0.0, 0.0, 0.0, -0.25));
+    }
+
+    #[test]
+    fn clamp_above_range() {
+        let color = Rgba::new(1.5, 2.0, 1.0, 3.0);
+        assert_eq!(color.clamp(), Rgba::new(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(color.clamp_rgb(), Rgba::new(1.0, 1.0, 1.0, 3.0));
+    }
+
+    #[test]
+    fn clamp_within_range() {
+        let color = Rgba::new(0.25, 0.5, 0.75, 0.5);
+        assert_eq!(color.clamp(), color);
+        assert_eq!(color.clamp_rgb(), color);
+        assert_eq!(color.saturate(), color);
+    }
+
+    #[test]
+    fn saturate_clamps_each_channel() {
+        assert_eq!(Rgba::new(2.0, -1.0, 0.5, 1.0).saturate(), Rgba::new(1.0, 0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    fn clamp_custom_range() {
+        let color = Rgba::new(-1.0, 0.5, 4.0, 2.0);
+        assert_eq!(color.clamp_range(0.0, 2.0), Rgba::new(0.0, 0.5, 2.0, 2.0));
+        assert_eq!(color.clamp_range(0.0, 1.0), color.clamp());
+    }
+
+    #[test]
+    fn from_percent_scales_to_unit_range() {
+        assert_eq!(Rgba::from_percent(100.0, 100.0, 100.0, 100.0), Rgba::WHITE);
+        assert_eq!(Rgba::from_percent(50.0, 0.0, 50.0, 50.0), Rgba::new(0.5, 0.0, 0.5, 0.5));
+        assert_eq!(Rgba::new(0.5, 1.0, 0.0, 1.0).to_percent(), (50.0, 100.0, 0.0, 100.0));
+    }
+
+    #[test]
+    fn srgb_u8_round_trip() {
+        for v in 0..=255u8 {
+            let (r, g, b, a) = Rgba::from_srgb_u8(v, v, v, v).to_srgb_u8();
+            for channel in [r, g, b, a] {
+                assert!(channel.abs_diff(v) <= 1, "{v} came back as {channel}");
+            }
+        }
+    }
+
+    #[test]
+    fn srgb_midpoint_is_linear_half() {
+        let color = Rgba::from_srgb_u8(188, 188, 188, 255);
+        assert!(color.approx_eq(Rgba::gray(0.5), 0.01), "{color:?}");
+    }
+
+    #[test]
+    fn lerp_midpoint_and_endpoints() {
+        assert_eq!(Rgba::BLACK.lerp(Rgba::WHITE, 0.5), Rgba::new_opaque(0.5, 0.5, 0.5));
+        assert_eq!(Rgba::RED.lerp(Rgba::BLUE, 0.0), Rgba::RED);
+        assert_eq!(Rgba::RED.lerp(Rgba::BLUE, 1.0), Rgba::BLUE);
+    }
+
+    #[test]
+    fn lerp_clamps_and_unclamped_extrapolates() {
+        assert_eq!(Rgba::BLACK.lerp(Rgba::WHITE, 2.0), Rgba::WHITE);
+        assert_eq!(Rgba::BLACK.lerp(Rgba::WHITE, -1.0), Rgba::BLACK);
+        assert_eq!(Rgba::BLACK.lerp_unclamped(Rgba::gray(0.5), 2.0), Rgba::WHITE);
+    }
+
+    #[test]
+    fn premultiply_round_trip() {
+        let color = Rgba::new(0.8, 0.4, 0.2, 0.5);
+        assert_eq!(color.premultiply(), Rgba::new(0.4, 0.2, 0.1, 0.5));
+        assert!(
+            color
+                .premultiply()
+                .unpremultiply()
+                .approx_eq(color, Rgba::DEFAULT_EPSILON)
+        );
+    }
+
+    #[test]
+    fn unpremultiply_zero_alpha_stays_finite() {
+        let color = Rgba::new(0.8, 0.4, 0.2, 0.0).premultiply().unpremultiply();
+        assert!(color.to_array().iter().all(|c| c.is_finite()), "{color:?}");
+    }
+
+    #[test]
+    fn luminance_and_grayscale() {
+        assert!((Rgba::RED.luminance() - 0.2126).abs() < Rgba::DEFAULT_EPSILON);
+        assert!((Rgba::WHITE.luminance() - 1.0).abs() < Rgba::DEFAULT_EPSILON);
+        assert!(Rgba::WHITE.grayscale().approx_eq(Rgba::WHITE, Rgba::DEFAULT_EPSILON));
+        assert_eq!(Rgba::RED.with_alpha(0.5).grayscale().a, 0.5);
+    }
+
+    #[test]
+    fn array_order_and_round_trip() {
+        let color = Rgba::new(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(color.to_array(), [0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(<[f32; 4]>::from(color), [0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(Rgba::from_array(color.to_array()), color);
+        assert_eq!(Rgba::from([0.1, 0.2, 0.3, 0.4]), color);
+    }
+
+    #[test]
+    fn add_and_sub_operators() {
+        // Alpha is included, so subtracting an opaque color leaves zero alpha.
+        assert_eq!(Rgba::RED + Rgba::GREEN, Rgba::YELLOW.with_alpha(2.0));
+        assert_eq!(Rgba::WHITE - Rgba::RED, Rgba::CYAN.with_alpha(0.0));
+        assert_eq!((Rgba::RED + Rgba::GREEN).with_alpha(1.0), Rgba::YELLOW);
+        assert_eq!((Rgba::WHITE - Rgba::RED).with_alpha(1.0), Rgba::CYAN);
+
+        let mut color = Rgba::RED;
+        color += Rgba::new(0.0, 1.0, 0.0, 0.0);
+        assert_eq!(color, Rgba::YELLOW);
+        color -= Rgba::new(1.0, 0.0, 0.0, 0.0);
+        assert_eq!(color, Rgba::GREEN);
+    }
+
+    #[test]
+    fn hsv_round_trip_primaries_and_secondaries() {
+        let colors = [
+            (Rgba::RED, 0.0),
+            (Rgba::YELLOW, 60.0),
+            (Rgba::GREEN, 120.0),
+            (Rgba::CYAN, 180.0),
+            (Rgba::BLUE, 240.0),
+            (Rgba::MAGENTA, 300.0),
+        ];
+        for (color, hue) in colors {
+            let (h, s, v, a) = color.to_hsv();
+            assert_eq!((h, s, v, a), (hue, 1.0, 1.0, 1.0), "{color:?}");
+            assert!(
+                Rgba::from_hsv(h, s, v, a).approx_eq(color, Rgba::DEFAULT_EPSILON),
+                "{color:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn hsv_gray_has_zero_saturation() {
+        assert_eq!(Rgba::gray(0.5).to_hsv(), (0.0, 0.0, 0.5, 1.0));
+        assert_eq!(Rgba::BLACK.to_hsv(), (0.0, 0.0, 0.0, 1.0));
+        assert_eq!(Rgba::from_hsv(123.0, 0.0, 0.5, 1.0), Rgba::gray(0.5));
+    }
+
+    #[test]
+    fn u8_round_trip_through_u32() {
+        for v in 0..=255u8 {
+            let color = Rgba::from_u8(v, v, v, v);
+            assert_eq!(color.to_u32().to_be_bytes(), [v; 4]);
+        }
+    }
+
+    #[test]
+    fn to_u32_clamps_out_of_range() {
+        assert_eq!(Rgba::new(2.0, -1.0, 0.5, 1.0).to_u32(), 0xFFFF0080);
+    }
+
+    #[test]
+    fn parse_shorthand_hex() {
+        assert_eq!("#fff".parse::<Rgba>(), Ok(Rgba::WHITE));
+        let color = "#f008".parse::<Rgba>().unwrap();
+        assert_eq!(color, Rgba::from_u8(0xff, 0, 0, 0x88));
+        assert_eq!(color.a, 0x88 as f32 / 255.0);
+        assert!("#ff".parse::<Rgba>().is_err());
+        assert!("#fffff".parse::<Rgba>().is_err());
+    }
+
+    #[test]
+    fn const_from_hex() {
+        const ACCENT: Rgba = Rgba::from_hex_rgb(0xFF8800);
+        assert_eq!(Rgba::from_hex(0xFFFF0000), Rgba::RED);
+        assert_eq!(ACCENT, Rgba::from_u8(0xFF, 0x88, 0x00, 0xFF));
+    }
+
+    #[test]
+    fn approx_eq_respects_epsilon() {
+        let color = Rgba::new(0.5, 0.5, 0.5, 1.0);
+        assert!(color.approx_eq(Rgba::new(0.50005, 0.5, 0.49995, 1.0), Rgba::DEFAULT_EPSILON));
+        assert!(!color.approx_eq(Rgba::new(0.5, 0.5, 0.5, 0.999), Rgba::DEFAULT_EPSILON));
+        assert!(color.approx_eq(Rgba::new(0.5, 0.5, 0.5, 0.999), 0.01));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_casts_channels_in_order() {
+        let color = Rgba::new(0.1, 0.2, 0.3, 0.4);
+        assert_eq!(bytemuck::cast::<Rgba, [f32; 4]>(color), [0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(bytemuck::bytes_of(&color).len(), 16);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_alpha_defaults_to_opaque() {
+        let color: Rgba = serde_yml::from_str("{r: 1.0, g: 0.5, b: 0.0}").unwrap();
+        assert_eq!(color, Rgba::new_opaque(1.0, 0.5, 0.0));
+        let yaml = serde_yml::to_string(&Rgba::new(1.0, 0.5, 0.0, 0.25)).unwrap();
+        assert_eq!(
+            serde_yml::from_str::<Rgba>(&yaml).unwrap(),
+            Rgba::new(1.0, 0.5, 0.0, 0.25)
+        );
+    }
+
+    #[cfg(not(any(feature = "bytemuck", feature = "serde")))]
+    #[test]
+    fn builds_without_optional_features() {
+        assert_eq!(Rgba::from_hex(0xFFFF0000), Rgba::RED);
+    }
+
+    #[test]
+    fn contrast_ratio_bounds() {
+        assert!((Rgba::BLACK.contrast_ratio(Rgba::WHITE) - 21.0).abs() < Rgba::DEFAULT_EPSILON);
+        assert!((Rgba::WHITE.contrast_ratio(Rgba::BLACK) - 21.0).abs() < Rgba::DEFAULT_EPSILON);
+        assert_eq!(Rgba::RED.contrast_ratio(Rgba::RED), 1.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct ArrayColor {
+        #[serde(with = "serde_array")]
+        color: Rgba,
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_array_opaque_round_trip() {
+        let value = ArrayColor {
+            color: Rgba::new_opaque(0.5, 0.25, 1.0),
+        };
+        let yaml = serde_yml::to_string(&value).unwrap();
+        assert_eq!(
+            serde_yml::from_str::<serde_yml::Value>(&yaml).unwrap()["color"]
+                .as_sequence()
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(serde_yml::from_str::<ArrayColor>(&yaml).unwrap(), value);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_array_alpha_round_trip() {
+        let value = ArrayColor {
+            color: Rgba::new(0.5, 0.25, 1.0, 0.5),
+        };
+        let yaml = serde_yml::to_string(&value).unwrap();
+        assert_eq!(
+            serde_yml::from_str::<serde_yml::Value>(&yaml).unwrap()["color"]
+                .as_sequence()
+                .unwrap()
+                .len(),
+            4
+        );
+        assert_eq!(serde_yml::from_str::<ArrayColor>(&yaml).unwrap(), value);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_array_rejects_wrong_length() {
+        assert!(serde_yml::from_str::<ArrayColor>("color: [0.5, 0.5]").is_err());
+        assert!(serde_yml::from_str::<ArrayColor>("color: [0.5, 0.5, 0.5, 0.5, 0.5]").is_err());
+    }
+
+    #[test]
+    fn gray_and_channel_builders() {
+        assert_eq!(Rgba::gray(0.5), Rgba::new_opaque(0.5, 0.5, 0.5));
+        assert_eq!(Rgba::gray_alpha(0.5, 0.25), Rgba::new(0.5, 0.5, 0.5, 0.25));
+        assert_eq!(Rgba::WHITE.with_red(0.0), Rgba::CYAN);
+        assert_eq!(Rgba::WHITE.with_green(0.0), Rgba::MAGENTA);
+        assert_eq!(Rgba::WHITE.with_blue(0.0), Rgba::YELLOW);
+    }
+
+    #[test]
+    fn opaque_over_returns_source() {
+        assert_eq!(Rgba::RED.over(Rgba::BLUE), Rgba::RED);
+        assert_eq!(Rgba::RED.over(Rgba::new(0.0, 0.0, 0.0, 0.0)), Rgba::RED);
+    }
+
+    #[test]
+    fn half_white_over_black_is_gray() {
+        assert_eq!(Rgba::WHITE.with_alpha(0.5).over(Rgba::BLACK), Rgba::gray(0.5));
+        let clear = Rgba::new(0.0, 0.0, 0.0, 0.0);
+        assert_eq!(clear.over(clear), clear);
+    }
+
+    #[test]
+    fn gradient_steps() {
+        let colors: Vec<_> = Rgba::BLACK.gradient(Rgba::WHITE, 3).collect();
+        assert_eq!(colors, [Rgba::BLACK, Rgba::gray(0.5), Rgba::WHITE]);
+        assert_eq!(Rgba::RED.gradient(Rgba::BLUE, 1).collect::<Vec<_>>(), [Rgba::RED]);
+        assert_eq!(Rgba::RED.gradient(Rgba::BLUE, 0).count(), 0);
+    }
+
+    #[test]
+    fn const_from_hex_matches_from_u32() {
+        const ACCENT: Rgba = Rgba::from_hex(0xFF3366FF);
+        assert_eq!(ACCENT, Rgba::from(0xFF3366FF));
+        assert_eq!(ACCENT.to_u32(), 0xFF3366FF);
+        assert_eq!(Rgba::from_hex(0x80102030), Rgba::from(0x80102030));
+    }
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn hash_rgba_matches_hash() {
+        let nan_a = Rgba::new(f32::NAN, 0.0, 1.0, 1.0);
+        let nan_b = Rgba::new(-f32::NAN, -0.0, 1.0, 1.0);
+        assert_ne!(nan_a, nan_b);
+        assert_eq!(hash_of(&nan_a), hash_of(&nan_b));
+        assert_eq!(HashRgba(nan_a), HashRgba(nan_b));
+        assert_eq!(hash_of(&HashRgba(nan_a)), hash_of(&HashRgba(nan_b)));
+        assert_ne!(HashRgba(Rgba::RED), HashRgba(Rgba::BLUE));
+    }
+
+    #[test]
+    fn hash_rgba_as_map_key() {
+        let mut palette = std::collections::HashSet::new();
+        palette.insert(HashRgba(Rgba::new(0.0, 0.5, f32::NAN, 1.0)));
+        assert!(palette.contains(&HashRgba(Rgba::new(-0.0, 0.5, f32::NAN, 1.0))));
+    }
+
+    #[test]
+    fn channel_min_max() {
+        assert_eq!(Rgba::RED.max(Rgba::GREEN), Rgba::YELLOW);
+        assert_eq!(Rgba::YELLOW.min(Rgba::RED), Rgba::RED);
+        let color = Rgba::new(1.5, -0.5, 0.5, 2.0);
+        assert_eq!(color.max(Rgba::BLACK).min(Rgba::WHITE), Rgba::new(1.0, 0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    fn exposure_and_gamma() {
+        let color = Rgba::new(0.25, 0.5, 0.125, 0.5);
+        assert_eq!(color.adjust_exposure(1.0), Rgba::new(0.5, 1.0, 0.25, 0.5));
+        assert_eq!(color.adjust_exposure(-1.0), Rgba::new(0.125, 0.25, 0.0625, 0.5));
+        assert_eq!(color.apply_gamma(1.0), color);
+        assert_eq!(color.apply_gamma(2.0), Rgba::new(0.0625, 0.25, 0.015625, 0.5));
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    cell::Cell,
     fs::{File, OpenOptions},
     io::Write,
     panic::PanicHookInfo,
@@ -25,34 +26,80 @@ macro_rules! app_info {
 }
 
 type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Send + Sync>;
+type CrashHandler = Box<dyn Fn(&str) + Send + Sync>;
 
 static APP_INFO: OnceLock<AppInfo> = OnceLock::new();
 static DEFAULT_PANIC_HOOK: OnceLock<PanicHook> = OnceLock::new();
 static HAS_PANICKED: AtomicBool = AtomicBool::new(false);
+static CRASH_HANDLER: OnceLock<CrashHandler> = OnceLock::new();
+static IN_CRASH_HANDLER: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static IS_CRASH_HANDLER_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Calls the crash handler on its own thread, so a panic inside it is caught by the join instead of
+/// aborting the process. Panics raised by the handler don't call it again.
+fn run_crash_handler(report: &str) {
+    let Some(handler) = CRASH_HANDLER.get() else {
+        return;
+    };
+    if IN_CRASH_HANDLER.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    std::thread::scope(|scope| {
+        let result = std::thread::Builder::new()
+            .name("crash handler".to_string())
+            .spawn_scoped(scope, || {
+                IS_CRASH_HANDLER_THREAD.set(true);
+                handler(report)
+            })
+            .map(|thread| thread.join());
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(_)) => eprintln!("crash handler panicked"),
+            Err(error) => eprintln!("failed to run crash handler: {error}"),
+        }
+    });
+    IN_CRASH_HANDLER.store(false, Ordering::Relaxed);
+}
 
 fn panic_hook(panic_info: &PanicHookInfo) {
     const CRASH_LOG_FILE: &str = "CRASH.txt";
     if let Some(default_hook) = DEFAULT_PANIC_HOOK.get() {
         default_hook(panic_info);
     }
+    // The crash handler panicking isn't a new crash, so it doesn't get its own report.
+    if IS_CRASH_HANDLER_THREAD.get() {
+        return;
+    }
     if let Some(app_info) = APP_INFO.get() {
+        let first_panic = !HAS_PANICKED.swap(true, Ordering::Relaxed);
+        let report = if first_panic {
+            format!(
+                "{} v{}\nRunning on {} {}\n\n{panic_info}\n",
+                app_info.package_name,
+                app_info.package_version,
+                std::env::consts::OS,
+                std::env::consts::ARCH
+            )
+        } else {
+            format!("{panic_info}\n")
+        };
         let result = (|| {
-            if HAS_PANICKED.swap(true, Ordering::Relaxed) {
-                let mut output = OpenOptions::new().append(true).open(CRASH_LOG_FILE)?;
-                writeln!(output)?;
-                writeln!(output, "{panic_info}")
+            if first_panic {
+                File::create(CRASH_LOG_FILE)?.write_all(report.as_bytes())
             } else {
-                let mut output = File::create(CRASH_LOG_FILE)?;
-                writeln!(output, "{} v{}", app_info.package_name, app_info.package_version)?;
-                writeln!(output, "Running on {} {}", std::env::consts::OS, std::env::consts::ARCH)?;
+                let mut output = OpenOptions::new().append(true).open(CRASH_LOG_FILE)?;
                 writeln!(output)?;
-                writeln!(output, "{panic_info}")
+                output.write_all(report.as_bytes())
             }
         })();
         match result {
             Ok(()) => eprintln!("panic message written to {CRASH_LOG_FILE}"),
             Err(error) => eprintln!("failed to write {CRASH_LOG_FILE}: {error}"),
         }
+        run_crash_handler(&report);
     }
 }
 
@@ -90,6 +137,15 @@ pub fn setup_panic_hook(app_info: AppInfo) {
     std::panic::set_hook(Box::new(panic_hook));
 }
 
+/// Sets a callback that receives the text of each crash report after it is written to CRASH.txt, for
+/// example to show it to the user or upload it. Only the first handler set is used.
+///
+/// The handler is only called once [`setup_panic_hook`] or [`setup_env`] has been called, since the report
+/// needs the app info they store. If the handler itself panics, that panic is printed but not reported.
+pub fn set_crash_handler(handler: Box<dyn Fn(&str) + Send + Sync>) {
+    let _ = CRASH_HANDLER.set(handler);
+}
+
 #[cfg(debug_assertions)]
 pub fn setup_env(app_info: AppInfo) {
     setup_logger(&app_info);
@@ -106,3 +162,46 @@ pub fn setup_env(app_info: AppInfo) {
     setup_cwd();
     setup_panic_hook(app_info);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn crash_handler_receives_report() {
+        // The hook writes CRASH.txt to the current directory, so keep it out of the source tree.
+        let dir = std::env::temp_dir().join(format!("silica-env-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::env::set_current_dir(&dir).unwrap();
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let handler_reports = reports.clone();
+        set_crash_handler(Box::new(move |report| {
+            handler_reports.lock().unwrap().push(report.to_string());
+            if report.contains("second crash") {
+                panic!("handler crash");
+            }
+        }));
+        // Without app info, crashes aren't reported.
+        let _ = DEFAULT_PANIC_HOOK.set(std::panic::take_hook());
+        std::panic::set_hook(Box::new(panic_hook));
+        let _ = std::panic::catch_unwind(|| panic!("unreported crash"));
+        assert!(reports.lock().unwrap().is_empty());
+
+        setup_panic_hook(app_info!());
+        let _ = std::panic::catch_unwind(|| panic!("first crash"));
+        let _ = std::panic::catch_unwind(|| panic!("second crash"));
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 2);
+        assert!(reports[0].starts_with("silica-env v"), "{}", reports[0]);
+        assert!(reports[0].contains("first crash"), "{}", reports[0]);
+        assert!(reports[1].contains("second crash"), "{}", reports[1]);
+
+        let crash_log = std::fs::read_to_string(dir.join("CRASH.txt")).unwrap();
+        assert!(crash_log.contains("first crash") && crash_log.contains("second crash"));
+        assert!(!crash_log.contains("handler crash"), "{crash_log}");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,453 @@
+use std::{cell::RefCell, rc::Rc};
+
+use silica_asset::{AssetError, AssetSource};
+use silica_gui::{render::GuiRenderer, *};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileEntry {
+    Directory(String),
+    File(String),
+}
+
+impl FileEntry {
+    pub fn name(&self) -> &str {
+        match self {
+            FileEntry::Directory(name) | FileEntry::File(name) => name,
+        }
+    }
+}
+
+/// Lists the files and subdirectories of a directory in an [`AssetSource`], keeping only files with
+/// one of the allowed extensions.
+pub struct FileBrowser<S> {
+    source: S,
+    directory: String,
+    extensions: Vec<String>,
+    entries: Vec<FileEntry>,
+}
+
+impl<S: AssetSource> FileBrowser<S> {
+    /// Lists `directory`, where an empty string is the root of the source. `extensions` are matched
+    /// without the dot and ignoring case; if it's empty, every file is listed.
+    pub fn new(source: S, directory: &str, extensions: &[&str]) -> Result<Self, AssetError> {
+        let mut browser = FileBrowser {
+            source,
+            directory: String::new(),
+            extensions: extensions.iter().map(|extension| extension.to_lowercase()).collect(),
+            entries: Vec::new(),
+        };
+        browser.set_directory(directory)?;
+        Ok(browser)
+    }
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+    pub fn directory(&self) -> &str {
+        &self.directory
+    }
+    pub fn entries(&self) -> &[FileEntry] {
+        &self.entries
+    }
+    fn path(&self, name: &str) -> String {
+        if self.directory.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", self.directory, name)
+        }
+    }
+    fn matches_extension(&self, name: &str) -> bool {
+        self.extensions.is_empty()
+            || name
+                .rsplit_once('.')
+                .is_some_and(|(_, extension)| self.extensions.contains(&extension.to_lowercase()))
+    }
+    pub fn set_directory(&mut self, directory: &str) -> Result<(), AssetError> {
+        let directory = directory.trim_matches('/');
+        let prefix = if directory.is_empty() {
+            String::new()
+        } else {
+            format!("{directory}/")
+        };
+        let mut directories = Vec::new();
+        let mut files = Vec::new();
+        for path in self.source.read_directory(directory)? {
+            let Some(relative) = path.strip_prefix(&prefix) else {
+                continue;
+            };
+            // Sources that list nested files reveal subdirectories through their paths.
+            match relative.split_once('/') {
+                Some((name, _)) if !name.is_empty() => directories.push(name.to_string()),
+                Some(_) => {}
+                None if !relative.is_empty() && self.matches_extension(relative) => files.push(relative.to_string()),
+                None => {}
+            }
+        }
+        directories.sort();
+        directories.dedup();
+        files.sort();
+        self.directory = directory.to_string();
+        self.entries = directories
+            .into_iter()
+            .map(FileEntry::Directory)
+            .chain(files.into_iter().map(FileEntry::File))
+            .collect();
+        Ok(())
+    }
+    /// Moves to the parent directory. Does nothing at the root.
+    pub fn go_up(&mut self) -> Result<(), AssetError> {
+        if self.directory.is_empty() {
+            return Ok(());
+        }
+        let parent = self.directory.rsplit_once('/').map(|(parent, _)| parent.to_string());
+        self.set_directory(&parent.unwrap_or_default())
+    }
+    /// Enters the directory at `index`, or returns the path of the file at `index`.
+    pub fn open(&mut self, index: usize) -> Result<Option<String>, AssetError> {
+        match self.entries.get(index).cloned() {
+            Some(FileEntry::Directory(name)) => {
+                self.set_directory(&self.path(&name))?;
+                Ok(None)
+            }
+            Some(FileEntry::File(name)) => Ok(Some(self.path(&name))),
+            None => Ok(None),
+        }
+    }
+}
+
+struct FileDialogState<S> {
+    browser: RefCell<FileBrowser<S>>,
+    selection: Rc<ListSelection>,
+    panel: RefCell<Option<NodeId>>,
+    path_label: WidgetId<Label>,
+    list: NodeId,
+    on_selected: EventFn,
+}
+
+impl<S: AssetSource + 'static> FileDialogState<S> {
+    fn path_text(browser: &FileBrowser<S>) -> String {
+        format!("/{}", browser.directory())
+    }
+    fn update_list(&self, gui: &mut Gui) {
+        let browser = self.browser.borrow();
+        self.path_label.set_text(gui, &Self::path_text(&browser));
+        gui.delete_children(self.list);
+        let labels = browser.entries().iter().map(|entry| match entry {
+            FileEntry::Directory(name) => format!("{name}/"),
+            FileEntry::File(name) => name.clone(),
+        });
+        let labels = labels.collect::<Vec<_>>();
+        SelectableListBuilder::new(self.selection.clone())
+            .modify_style(|style| style.grow = true)
+            .parent(self.list)
+            .items(gui, labels.iter().map(String::as_str))
+            .build(gui);
+        gui.request_redraw();
+    }
+    fn change_directory<F>(gui: &mut Gui, state: &Rc<Self>, f: F)
+    where
+        F: FnOnce(&mut FileBrowser<S>) -> Result<(), AssetError>,
+    {
+        let result = f(&mut state.browser.borrow_mut());
+        match result {
+            Ok(()) => state.update_list(gui),
+            Err(error) => log::error!("{error}"),
+        }
+    }
+    fn close(gui: &mut Gui, state: Rc<Self>) {
+        if let Some(panel) = state.panel.borrow_mut().take() {
+            if gui.modal() == Some(panel) {
+                gui.pop_modal();
+            }
+            gui.delete(panel);
+        }
+    }
+}
+
+/// The dialog's panel, which closes the dialog on Escape and opens entries as they're selected.
+struct FileDialogPanel<S> {
+    state: Rc<FileDialogState<S>>,
+}
+
+impl<S: AssetSource + 'static> Widget for FileDialogPanel<S> {
+    fn focusable(&self) -> bool {
+        true
+    }
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, _area: &Area) -> InputAction {
+        if input.nav_key == Some(NavKey::Escape) {
+            executor.queue(
+                EventFn::new_param(FileDialogState::<S>::close),
+                Some(Box::new(self.state.clone())),
+            );
+            return InputAction::Block;
+        }
+        // This relies on input reaching children before their parents: the clicked list item updates the
+        // selection before the panel is visited, so the new entry is opened in the same event.
+        let Some(&index) = self.state.selection.selected().first() else {
+            return InputAction::Pass;
+        };
+        self.state.selection.set_selected([]);
+        let browser = self.state.browser.borrow();
+        match browser.entries().get(index) {
+            Some(FileEntry::Directory(_)) => {
+                let open = EventFn::new_param(|gui: &mut Gui, (state, index): (Rc<FileDialogState<S>>, usize)| {
+                    FileDialogState::change_directory(gui, &state, |browser| browser.open(index).map(|_| ()));
+                });
+                executor.queue(open, Some(Box::new((self.state.clone(), index))));
+            }
+            Some(FileEntry::File(name)) => {
+                executor.queue(
+                    EventFn::new_param(FileDialogState::<S>::close),
+                    Some(Box::new(self.state.clone())),
+                );
+                executor.queue(self.state.on_selected.clone(), Some(Box::new(browser.path(name))));
+            }
+            None => {}
+        }
+        executor.request_redraw();
+        InputAction::Block
+    }
+    fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+}
+
+/// An in-app file picker over an [`AssetSource`], shown as a modal. Directories are
+/// entered by clicking them, and clicking a file closes the dialog and passes the file's path to the
+/// event.
+#[must_use]
+pub struct FileDialogBuilder {
+    title: String,
+    size: Size,
+}
+
+impl FileDialogBuilder {
+    pub fn new(title: &str) -> Self {
+        FileDialogBuilder {
+            title: title.to_string(),
+            size: Size::new(480, 360),
+        }
+    }
+    pub fn size(mut self, size: Size) -> Self {
+        self.size = size;
+        self
+    }
+    /// Opens the dialog as a modal. The dialog deletes itself when closed.
+    pub fn open<S, C, F>(self, gui: &mut Gui, browser: FileBrowser<S>, on_selected: F) -> NodeId
+    where
+        S: AssetSource + 'static,
+        C: 'static,
+        F: Fn(&mut C, String) + 'static,
+    {
+        let state = self.build(gui, browser, on_selected);
+        state.panel.borrow().expect("dialog was just opened")
+    }
+    fn build<S, C, F>(self, gui: &mut Gui, browser: FileBrowser<S>, on_selected: F) -> Rc<FileDialogState<S>>
+    where
+        S: AssetSource + 'static,
+        C: 'static,
+        F: Fn(&mut C, String) + 'static,
+    {
+        let title = LabelBuilder::new(&self.title).build(gui);
+        let path_label = LabelBuilder::new(&FileDialogState::path_text(&browser)).build(gui);
+        let list = gui.create_node(Style {
+            direction: Direction::Column,
+            ..Default::default()
+        });
+        let entries = ScrollAreaBuilder::new(
+            gui,
+            Style {
+                grow: true,
+                ..Default::default()
+            },
+        )
+        .vertical_overflow(gui, OverflowPolicy::Auto)
+        .child(list)
+        .build(gui);
+        gui.modify_style(entries, |style| style.grow = true);
+        let state = Rc::new(FileDialogState {
+            browser: RefCell::new(browser),
+            selection: ListSelection::new(|_: &mut Gui, _: Vec<usize>| {}),
+            panel: RefCell::new(None),
+            path_label,
+            list,
+            on_selected: EventFn::new_param(on_selected),
+        });
+        state.update_list(gui);
+        let up = {
+            let state = state.clone();
+            Button::create(gui, "Up", move |gui: &mut Gui| {
+                FileDialogState::change_directory(gui, &state, FileBrowser::go_up);
+            })
+        };
+        let cancel = {
+            let state = state.clone();
+            Button::create(gui, "Cancel", move |gui: &mut Gui| {
+                FileDialogState::close(gui, state.clone());
+            })
+        };
+        let buttons = NodeBuilder::new()
+            .style(Style {
+                main_align: Align::End,
+                gap: 4,
+                ..Default::default()
+            })
+            .child(up)
+            .child(cancel)
+            .build(gui);
+        let panel = NodeBuilder::new()
+            .style(Style {
+                direction: Direction::Column,
+                min_size: self.size,
+                max_size: self.size,
+                gap: 4,
+                background_color: Some(Color::Background),
+                border_color: Some(Color::Border),
+                border: SideOffsets::new_all_same(1),
+                padding: SideOffsets::new_all_same(8),
+                ..Default::default()
+            })
+            .child(title)
+            .child(path_label)
+            .child(entries)
+            .child(buttons)
+            .build_widget(gui, FileDialogPanel { state: state.clone() });
+        let panel = NodeId::from(panel);
+        *state.panel.borrow_mut() = Some(panel);
+        gui.push_modal(panel);
+        gui.set_focus(Some(panel));
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use silica_asset::{DirectorySource, MemorySource};
+    use silica_gui::theme::StandardTheme;
+    use silica_wgpu::{TextureConfig, wgpu};
+
+    use super::*;
+    use crate::tests::test_context;
+
+    fn test_source() -> MemorySource {
+        let mut source = MemorySource::new();
+        source.insert("readme.txt", "");
+        source.insert("title.png", "");
+        source.insert("maps/one.png", "");
+        source.insert("maps/notes.txt", "");
+        source.insert("maps/deep/two.PNG", "");
+        source
+    }
+
+    #[test]
+    fn browse_directories() {
+        let mut browser = FileBrowser::new(test_source(), "", &["png"]).unwrap();
+        assert_eq!(
+            browser.entries(),
+            [FileEntry::Directory("maps".into()), FileEntry::File("title.png".into())]
+        );
+
+        assert_eq!(browser.open(0).unwrap(), None);
+        assert_eq!(browser.directory(), "maps");
+        assert_eq!(
+            browser.entries(),
+            [FileEntry::Directory("deep".into()), FileEntry::File("one.png".into())]
+        );
+        assert_eq!(browser.open(1).unwrap(), Some("maps/one.png".into()));
+
+        browser.open(0).unwrap();
+        assert_eq!(browser.directory(), "maps/deep");
+        assert_eq!(browser.entries(), [FileEntry::File("two.PNG".into())]);
+
+        browser.go_up().unwrap();
+        assert_eq!(browser.directory(), "maps");
+        browser.go_up().unwrap();
+        browser.go_up().unwrap();
+        assert_eq!(browser.directory(), "");
+    }
+
+    #[test]
+    fn browse_without_filter() {
+        let browser = FileBrowser::new(test_source(), "maps", &[]).unwrap();
+        assert_eq!(
+            browser.entries(),
+            [
+                FileEntry::Directory("deep".into()),
+                FileEntry::File("notes.txt".into()),
+                FileEntry::File("one.png".into())
+            ]
+        );
+        assert!(FileBrowser::new(test_source(), "missing", &[]).is_err());
+    }
+
+    struct NoKey;
+
+    impl KeyboardEvent for NoKey {
+        fn to_hotkey(&self) -> Option<Hotkey> {
+            None
+        }
+        fn to_nav_key(&self) -> Option<NavKey> {
+            None
+        }
+    }
+
+    struct PrimaryButton(bool);
+
+    impl MouseButtonEvent for PrimaryButton {
+        fn is_primary_button(&self) -> bool {
+            true
+        }
+        fn is_secondary_button(&self) -> bool {
+            false
+        }
+        fn is_pressed(&self) -> bool {
+            self.0
+        }
+        fn modifiers(&self) -> Modifiers {
+            Modifiers::default()
+        }
+    }
+
+    /// Clicks the first entry of the dialog's list.
+    fn click_first_entry<S: AssetSource>(gui: &mut Gui, state: &FileDialogState<S>) {
+        gui.layout();
+        let point = gui.screen_rect(state.list).origin + Vector::new(4, 4);
+        let events = [
+            InputEvent::MouseMotion(point),
+            InputEvent::MouseButton(PrimaryButton(true)),
+            InputEvent::MouseButton(PrimaryButton(false)),
+        ];
+        for event in events {
+            let (executor, _) = gui.handle_input::<NoKey, _>(event, Instant::now());
+            executor.execute(gui);
+        }
+    }
+
+    #[test]
+    fn clicking_entries_opens_them() {
+        let context = test_context();
+        let texture_config = TextureConfig::new(&context, wgpu::FilterMode::Linear);
+        let mut theme_source = DirectorySource::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../theme/dark_theme").into());
+        let theme = StandardTheme::load(&context, &texture_config, &mut theme_source).unwrap();
+        let mut gui = Gui::new(Rc::new(theme));
+        gui.set_area(Rect::new(Point::origin(), Size::new(640, 480)));
+
+        let mut source = MemorySource::new();
+        source.insert("maps/one.png", "");
+        let browser = FileBrowser::new(source, "", &[]).unwrap();
+        let selected = Rc::new(RefCell::new(None));
+        let on_selected = {
+            let selected = selected.clone();
+            move |_: &mut Gui, path: String| *selected.borrow_mut() = Some(path)
+        };
+        let state = FileDialogBuilder::new("Open").build(&mut gui, browser, on_selected);
+
+        // The list item and the panel see the click in the same event, so one click is enough.
+        click_first_entry(&mut gui, &state);
+        assert_eq!(state.browser.borrow().directory(), "maps");
+        assert_eq!(*selected.borrow(), None);
+
+        click_first_entry(&mut gui, &state);
+        assert_eq!(selected.borrow().as_deref(), Some("maps/one.png"));
+        assert_eq!(gui.modal(), None);
+    }
+}
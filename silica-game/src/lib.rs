@@ -1,3 +1,4 @@
+pub mod file_dialog;
 pub mod locale;
 pub mod particles;
 pub mod texture;
@@ -32,6 +33,29 @@ pub struct ScreenSpace;
 
 pub type GameAssets = silica_asset::DirectorySource;
 
+/// Pixel-space and world-space geometry types and constructors. World-space types are prefixed with `World`.
+///
+/// ```
+/// use silica_game::geometry::*;
+///
+/// let button = Rect::new(point2(8, 8), size2(64, 24));
+/// assert_eq!(button.center(), Point::new(40, 20));
+///
+/// let sprite = WorldRect::new(point2(0.5, 0.5), size2(2.0, 1.0));
+/// assert_eq!(sprite.max() + vec2(1.0, 0.0), WorldPoint::new(3.5, 1.5));
+/// ```
+pub mod geometry {
+    pub use silica_gui::geometry::*;
+
+    pub use crate::{
+        LocalSpace, ScreenSpace, WorldSpace,
+        world2d::{
+            CameraTransform, Point as WorldPoint, Rect as WorldRect, Size as WorldSize, Transform,
+            Vector as WorldVector,
+        },
+    };
+}
+
 pub trait Game: Sized {
     fn window_attributes() -> WindowAttributes;
     fn load(context: &Context, assets: GameAssets) -> Result<Self, AssetError>;
@@ -42,6 +66,9 @@ pub trait Game: Sized {
     fn input(&mut self, event: InputEvent);
     fn update(&mut self, event_loop: &EventLoop, dt: f32);
     fn clear_color(&self) -> Rgba;
+    /// Records GPU work that must happen before the frame's render pass, such as buffer or texture copies.
+    /// Writes through `context.queue` still work, and are also visible to the render pass.
+    fn prepare(&mut self, _context: &Context, _encoder: &mut wgpu::CommandEncoder) {}
     fn render(&mut self, context: &Context, pass: &mut wgpu::RenderPass);
 }
 
@@ -67,19 +94,27 @@ impl<T: Game> App for GameApp<T> {
         &mut self,
         event_loop: &EventLoop,
         context: &Context,
-        view: &wgpu::TextureView,
+        frame: &wgpu::Texture,
         encoder: &mut wgpu::CommandEncoder,
     ) {
         let now = Instant::now();
         let dt = (now - self.last_update).as_secs_f32();
         self.last_update = now;
         self.game.update(event_loop, dt);
+        self.draw(context, frame, encoder);
+    }
+}
+impl<T: Game> GameApp<T> {
+    /// Records the game's preparation work and then its render pass.
+    fn draw(&mut self, context: &Context, frame: &wgpu::Texture, encoder: &mut wgpu::CommandEncoder) {
+        self.game.prepare(context, encoder);
 
+        let view = frame.create_view(&wgpu::TextureViewDescriptor::default());
         let clear_color = self.game.clear_color();
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: None,
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
+                view: &view,
                 resolve_target: None,
                 ops: wgpu::Operations {
                     load: wgpu::LoadOp::Clear(wgpu::Color {
@@ -182,3 +217,198 @@ pub fn save_data<T: Serialize>(path: &str, data: &T) -> Result<(), IoError> {
     let buf = postcard::to_stdvec(data).map_err(IoError::other)?;
     std::fs::write(path, buf)
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use silica_wgpu::wgpu::util::DeviceExt;
+
+    use super::*;
+
+    pub(crate) fn test_context() -> Context {
+        let mut context = Context::init(AdapterFeatures::default());
+        context.surface_format = Some(wgpu::TextureFormat::Rgba8Unorm);
+        context
+    }
+
+    /// Reads back the pixels of an `Rgba8Unorm` texture, row by row.
+    pub(crate) fn read_pixels(context: &Context, texture: &wgpu::Texture) -> Vec<[u8; 4]> {
+        let row_size = texture.width() * 4;
+        let padded_row_size = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (padded_row_size * texture.height()) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: None,
+                },
+            },
+            texture.size(),
+        );
+        context.queue.submit([encoder.finish()]);
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        context.device.poll(wgpu::PollType::Wait).unwrap();
+        let data = buffer.slice(..).get_mapped_range();
+        data.chunks(padded_row_size as usize)
+            .flat_map(|row| row[..row_size as usize].chunks(4))
+            .map(|pixel| pixel.try_into().unwrap())
+            .collect()
+    }
+
+    /// Draws a red triangle from a vertex buffer that's empty until `prepare` copies the vertices into it.
+    struct CopyGame {
+        pipeline: wgpu::RenderPipeline,
+        vertices: wgpu::Buffer,
+        source: wgpu::Buffer,
+    }
+
+    impl CopyGame {
+        const SHADER: &str = "
+            @vertex
+            fn vs_main(@location(0) position: vec2f) -> @builtin(position) vec4f {
+                return vec4f(position, 0.0, 1.0);
+            }
+            @fragment
+            fn fs_main() -> @location(0) vec4f {
+                return vec4f(1.0, 0.0, 0.0, 1.0);
+            }
+        ";
+        fn new(context: &Context) -> Self {
+            let shader = context.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(Self::SHADER.into()),
+            });
+            let pipeline = context.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: None,
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: 8,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                    }],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                    compilation_options: Default::default(),
+                }),
+                primitive: Default::default(),
+                depth_stencil: None,
+                multisample: Default::default(),
+                multiview: None,
+                cache: None,
+            });
+            // Covers the whole target.
+            let triangle: [[f32; 2]; 3] = [[-1.0, -1.0], [3.0, -1.0], [-1.0, 3.0]];
+            let source = context.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&triangle),
+                usage: wgpu::BufferUsages::COPY_SRC,
+            });
+            let vertices = context.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: source.size(),
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            CopyGame {
+                pipeline,
+                vertices,
+                source,
+            }
+        }
+    }
+
+    impl Game for CopyGame {
+        fn window_attributes() -> WindowAttributes {
+            WindowAttributes::default()
+        }
+        fn load(_context: &Context, _assets: GameAssets) -> Result<Self, AssetError> {
+            unimplemented!()
+        }
+        fn resize_window(&mut self, _context: &Context, _size: SurfaceSize) {}
+        fn input(&mut self, _event: InputEvent) {}
+        fn update(&mut self, _event_loop: &EventLoop, _dt: f32) {}
+        fn clear_color(&self) -> Rgba {
+            Rgba::BLACK
+        }
+        fn prepare(&mut self, _context: &Context, encoder: &mut wgpu::CommandEncoder) {
+            encoder.copy_buffer_to_buffer(&self.source, 0, &self.vertices, 0, self.source.size());
+        }
+        fn render(&mut self, _context: &Context, pass: &mut wgpu::RenderPass) {
+            pass.set_pipeline(&self.pipeline);
+            pass.set_vertex_buffer(0, self.vertices.slice(..));
+            pass.draw(0..3, 0..1);
+        }
+    }
+
+    #[test]
+    fn geometry_types_from_one_import() {
+        use crate::geometry::*;
+
+        let rect = Rect::new(point2(1, 2), size2(3, 4));
+        let padded = rect.outer_rect(SideOffsets::new_all_same(1));
+        assert_eq!(padded, Rect::new(Point::new(0, 1), Size::new(5, 6)));
+        assert_eq!(Box2D::from_origin_and_size(rect.origin, rect.size).to_rect(), rect);
+        assert_eq!(Point::new(1, 2) + Vector::new(1, 1), point2(2, 3));
+        assert_eq!(vec2::<i32, Pixel>(1, 1), Vector::new(1, 1));
+
+        let world = WorldRect::new(WorldPoint::new(0.0, 0.0), WorldSize::new(2.0, 2.0));
+        assert_eq!(world.center() + WorldVector::new(1.0, 0.0), point2(2.0, 1.0));
+        let transform = Transform::translation(1.0, 0.0).then(&CameraTransform::scale(2.0, 2.0));
+        assert_eq!(
+            transform.transform_point(point2::<f32, LocalSpace>(1.0, 1.0)),
+            point2::<f32, ScreenSpace>(4.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn prepare_copy_is_visible_in_render() {
+        let context = test_context();
+        let frame = context.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let mut app = GameApp {
+            game: CopyGame::new(&context),
+            last_update: Instant::now(),
+        };
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        app.draw(&context, &frame, &mut encoder);
+        context.queue.submit([encoder.finish()]);
+        assert!(
+            read_pixels(&context, &frame)
+                .iter()
+                .all(|pixel| *pixel == [255, 0, 0, 255])
+        );
+    }
+}
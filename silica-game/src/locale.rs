@@ -4,7 +4,7 @@ pub use fluent_bundle::FluentArgs;
 use fluent_bundle::{FluentBundle, FluentMessage, FluentResource};
 use silica_asset::{AssetError, AssetSource};
 use silica_gui::FontSystem;
-use unic_langid::LanguageIdentifier;
+use unic_langid::{CharacterDirection, LanguageIdentifier};
 
 pub struct Message<'a>(&'a str, Option<FluentMessage<'a>>);
 
@@ -58,6 +58,13 @@ impl Localization {
         Ok(Localization(bundle))
     }
 
+    /// Whether the loaded locale is written right-to-left, for setting [`silica_gui::Style::rtl`].
+    pub fn is_rtl(&self) -> bool {
+        self.0
+            .locales
+            .first()
+            .is_some_and(|locale| locale.character_direction() == CharacterDirection::RTL)
+    }
     pub fn message<'a>(&'a self, id: &'a str) -> Message<'a> {
         Message(id, self.0.get_message(id))
     }
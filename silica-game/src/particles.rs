@@ -1,4 +1,7 @@
+use std::io::Error as IoError;
+
 use bytemuck::Pod;
+use serde::{Serialize, de::DeserializeOwned};
 use silica_wgpu::{Buffer, Context, Texture};
 
 use crate::world2d::Quad;
@@ -54,6 +57,11 @@ where
         self.particles.retain_mut(|particle| self.solver.update(particle, dt));
         self.changed = true;
     }
+    /// Replaces all particles, such as with ones from [`ParticleSystem::snapshot`].
+    pub fn restore(&mut self, particles: Vec<S::Particle>) {
+        self.particles = particles;
+        self.changed = true;
+    }
     pub fn texture(&self) -> &Texture {
         &self.texture
     }
@@ -67,6 +75,22 @@ where
         self.particles.resize(self.particles.len() + count, particle);
         self.changed = true;
     }
+    pub fn snapshot(&self) -> Vec<S::Particle> {
+        self.particles.clone()
+    }
+}
+impl<S> ParticleSystem<S>
+where
+    S: ParticleSolver,
+    S::Particle: Serialize + DeserializeOwned,
+{
+    pub fn save_snapshot(&self, path: &str) -> Result<(), IoError> {
+        crate::save_data(path, &self.particles)
+    }
+    pub fn load_snapshot(&mut self, path: &str) -> Result<(), IoError> {
+        self.restore(crate::load_data(path)?);
+        Ok(())
+    }
 }
 impl<S> ParticleSystem<S>
 where
@@ -91,3 +115,72 @@ where
         self.primitives.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use silica_gui::Rgba;
+    use silica_wgpu::{TextureConfig, Uv, wgpu};
+
+    use super::*;
+    use crate::{tests::test_context, world2d::Rect};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Spark {
+        x: f32,
+        life: f32,
+    }
+
+    struct SparkSolver;
+
+    impl ParticleSolver for SparkSolver {
+        type Particle = Spark;
+        type Primitive = Quad;
+        fn update(&self, particle: &mut Spark, dt: f32) -> bool {
+            particle.x += dt;
+            particle.life -= dt;
+            particle.life > 0.0
+        }
+        fn draw(&self, particle: &Spark) -> Quad {
+            Quad {
+                transform: Quad::rect_transform(Rect::new(euclid::point2(particle.x, 0.0), euclid::size2(1.0, 1.0))),
+                uv: Uv::FULL,
+                color: Rgba::WHITE,
+            }
+        }
+    }
+
+    #[test]
+    fn snapshot_restores_particles() {
+        let context = test_context();
+        let texture_config = TextureConfig::new(&context, wgpu::FilterMode::Nearest);
+        let particles = vec![Spark { x: 0.0, life: 1.0 }, Spark { x: 5.0, life: 3.0 }];
+        let mut system = ParticleSystem::with_particles(
+            particles.clone(),
+            SparkSolver,
+            Texture::white(&context, &texture_config),
+        );
+        system.prepare(&context);
+        let snapshot = system.snapshot();
+        assert_eq!(snapshot, particles);
+
+        system.update(2.0);
+        assert_eq!(system.snapshot(), [Spark { x: 7.0, life: 1.0 }]);
+        system.prepare(&context);
+
+        system.restore(snapshot);
+        assert_eq!(system.snapshot(), particles);
+        assert!(system.changed);
+        system.prepare(&context);
+        assert!(!system.changed);
+
+        let path = std::env::temp_dir().join("silica-particle-snapshot");
+        let path = path.to_str().unwrap();
+        system.update(2.0);
+        system.save_snapshot(path).unwrap();
+        system.restore(particles);
+        system.load_snapshot(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(system.snapshot(), [Spark { x: 7.0, life: 1.0 }]);
+    }
+}
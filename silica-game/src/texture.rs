@@ -1,7 +1,7 @@
 use etagere::BucketedAtlasAllocator;
-pub use silica_asset::image::Image;
+pub use silica_asset::image::{Image, ImageFormat};
 use silica_asset::{AssetError, AssetSource};
-use silica_wgpu::{Context, Texture, TextureConfig, TextureRect, TextureSize, Uv, UvRect, wgpu};
+use silica_wgpu::{Context, Texture, TextureConfig, TexturePoint, TextureRect, TextureSize, Uv, UvRect, wgpu};
 
 pub type ImagePoint = euclid::Point2D<u32, Image>;
 pub type ImageSize = euclid::Size2D<u32, Image>;
@@ -9,6 +9,7 @@ pub type ImageSize = euclid::Size2D<u32, Image>;
 pub trait ImageExt {
     const FORMAT: wgpu::TextureFormat;
     fn size(&self) -> ImageSize;
+    fn texture_format(&self) -> wgpu::TextureFormat;
     fn create_texture(&self, context: &Context, config: &TextureConfig) -> Texture;
     fn load_texture<S: AssetSource>(
         context: &Context,
@@ -30,8 +31,21 @@ impl ImageExt for Image {
     fn size(&self) -> ImageSize {
         ImageSize::new(self.width, self.height)
     }
+    fn texture_format(&self) -> wgpu::TextureFormat {
+        match self.format {
+            ImageFormat::Gray => wgpu::TextureFormat::R8Unorm,
+            ImageFormat::GrayAlpha => wgpu::TextureFormat::Rg8Unorm,
+            ImageFormat::Rgba => Self::FORMAT,
+        }
+    }
     fn create_texture(&self, context: &Context, config: &TextureConfig) -> Texture {
-        Texture::new_with_data(context, config, self.size().cast_unit(), Self::FORMAT, &self.data)
+        Texture::new_with_data(
+            context,
+            config,
+            self.size().cast_unit(),
+            self.texture_format(),
+            &self.data,
+        )
     }
     fn load_texture<S: AssetSource>(
         context: &Context,
@@ -48,17 +62,30 @@ impl ImageExt for Image {
         texture: &Texture,
         rect: Option<TextureRect>,
     ) -> UvRect {
-        const BPP: u32 = 4;
+        let bpp = self.format.bytes_per_pixel();
         let rect = rect.unwrap_or(TextureRect::from_size(self.size().cast_unit()));
-        let offset = (source.x + (source.y * self.width)) * BPP;
-        texture.write_data(context, rect, &self.data, offset as u64, self.width * BPP);
+        assert!(
+            source.x + rect.width() <= self.width && source.y + rect.height() <= self.height,
+            "read of {:?} at {source:?} is outside of {:?} image",
+            rect.size(),
+            self.size()
+        );
+        let offset = (source.x + (source.y * self.width)) * bpp;
+        texture.write_data(context, rect, &self.data, offset as u64, self.width * bpp);
         Uv::normalize(rect, texture.size())
     }
 }
 
+struct AtlasStaging {
+    image: Image,
+    dirty: Option<TextureRect>,
+}
+
 pub struct TextureAtlas {
     texture: Texture,
     allocator: BucketedAtlasAllocator,
+    staging: Option<AtlasStaging>,
+    texture_writes: usize,
 }
 
 impl TextureAtlas {
@@ -66,6 +93,47 @@ impl TextureAtlas {
         TextureAtlas {
             texture: Texture::new(context, config, size, Image::FORMAT),
             allocator: BucketedAtlasAllocator::new(size.to_i32().cast_unit()),
+            staging: None,
+            texture_writes: 0,
+        }
+    }
+    /// Creates an atlas that assembles loaded images on the CPU, and uploads them all at once when
+    /// [`TextureAtlas::flush`] or [`TextureAtlas::finish_batched`] is called.
+    pub fn new_batched(context: &Context, config: &TextureConfig, size: TextureSize) -> Self {
+        let mut atlas = Self::new(context, config, size);
+        atlas.staging = Some(AtlasStaging {
+            image: Image {
+                width: size.width,
+                height: size.height,
+                format: ImageFormat::Rgba,
+                data: vec![0; (size.width as usize) * (size.height as usize) * 4],
+            },
+            dirty: None,
+        });
+        atlas
+    }
+    fn write_image(&mut self, context: &Context, image: &Image, source: ImagePoint, rect: TextureRect) -> UvRect {
+        // The atlas texture is RGBA, so grayscale images are expanded first.
+        let expanded;
+        let image = if image.format == ImageFormat::Rgba {
+            image
+        } else {
+            expanded = image.to_rgba();
+            &expanded
+        };
+        if let Some(staging) = self.staging.as_mut() {
+            const BPP: usize = 4;
+            let row_len = (rect.width() as usize) * BPP;
+            for y in 0..rect.height() {
+                let src = ((source.x + ((source.y + y) * image.width)) as usize) * BPP;
+                let dst = ((rect.min.x + ((rect.min.y + y) * staging.image.width)) as usize) * BPP;
+                staging.image.data[dst..(dst + row_len)].copy_from_slice(&image.data[src..(src + row_len)]);
+            }
+            staging.dirty = Some(staging.dirty.map(|dirty| dirty.union(&rect)).unwrap_or(rect));
+            Uv::normalize(rect, self.texture.size())
+        } else {
+            self.texture_writes += 1;
+            image.write_to_texture(context, source, &self.texture, Some(rect))
         }
     }
     pub fn load(&mut self, context: &Context, image: &Image) -> UvRect {
@@ -73,9 +141,9 @@ impl TextureAtlas {
             .allocator
             .allocate(image.size().to_i32().cast_unit())
             .expect("not enough space in atlas");
-        let rect =
-            TextureRect::from_origin_and_size(alloc.rectangle.min.to_u32().cast_unit(), image.size().cast_unit());
-        image.write_to_texture(context, ImagePoint::zero(), &self.texture, Some(rect))
+        let origin: TexturePoint = alloc.rectangle.min.to_u32().cast_unit();
+        let rect = TextureRect::from_origin_and_size(origin, image.size().cast_unit());
+        self.write_image(context, image, ImagePoint::zero(), rect)
     }
     pub fn load_frames(&mut self, context: &Context, image: &Image, frame_size: TextureSize) -> Vec<UvRect> {
         let mut uvs = Vec::new();
@@ -85,15 +153,114 @@ impl TextureAtlas {
                 .allocator
                 .allocate(frame_size.to_i32().cast_unit())
                 .expect("not enough space in atlas");
-            let rect = TextureRect::from_origin_and_size(alloc.rectangle.min.to_u32().cast_unit(), frame_size);
-            uvs.push(image.write_to_texture(context, ImagePoint::new(x, 0), &self.texture, Some(rect)));
+            let origin: TexturePoint = alloc.rectangle.min.to_u32().cast_unit();
+            let rect = TextureRect::from_origin_and_size(origin, frame_size);
+            uvs.push(self.write_image(context, image, ImagePoint::new(x, 0), rect));
             x += frame_size.width;
         }
         uvs
     }
+    /// Uploads all images loaded since the last flush with a single texture write. Does nothing if the
+    /// atlas wasn't created with [`TextureAtlas::new_batched`].
+    pub fn flush(&mut self, context: &Context) {
+        if let Some(staging) = self.staging.as_mut()
+            && let Some(rect) = staging.dirty.take()
+        {
+            self.texture_writes += 1;
+            staging
+                .image
+                .write_to_texture(context, rect.min.cast_unit(), &self.texture, Some(rect));
+        }
+    }
+    pub fn finish_batched(mut self, context: &Context, name: &str) -> Texture {
+        self.flush(context);
+        self.finish(name)
+    }
+    /// Returns the atlas texture, logging how full it is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the atlas was created with [`TextureAtlas::new_batched`] and has images that haven't been
+    /// uploaded yet. Call [`TextureAtlas::flush`] first, or use [`TextureAtlas::finish_batched`].
     pub fn finish(self, name: &str) -> Texture {
+        assert!(
+            self.staging.as_ref().is_none_or(|staging| staging.dirty.is_none()),
+            "{name} texture atlas has images that weren't flushed"
+        );
         let fill_ratio = self.allocator.allocated_space() as f32 / self.allocator.size().area() as f32;
-        log::debug!("{} texture atlas {}% filled", name, (fill_ratio * 100.0) as i32);
+        log::debug!(
+            "{} texture atlas {}% filled, uploaded with {} texture writes",
+            name,
+            (fill_ratio * 100.0) as i32,
+            self.texture_writes
+        );
         self.texture
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wgpu::FilterMode;
+
+    use super::*;
+    use crate::tests::test_context;
+
+    fn test_images(count: u8) -> Vec<Image> {
+        (0..count)
+            .map(|i| Image {
+                width: 4,
+                height: 4,
+                format: ImageFormat::Rgba,
+                data: vec![i; 4 * 4 * 4],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn batched_atlas_writes_once() {
+        let context = test_context();
+        let config = TextureConfig::new(&context, FilterMode::Nearest);
+        let images = test_images(16);
+
+        let mut atlas = TextureAtlas::new(&context, &config, TextureSize::new(64, 64));
+        for image in &images {
+            atlas.load(&context, image);
+        }
+        assert_eq!(atlas.texture_writes, images.len());
+
+        let mut atlas = TextureAtlas::new_batched(&context, &config, TextureSize::new(64, 64));
+        for image in &images {
+            atlas.load(&context, image);
+        }
+        assert_eq!(atlas.texture_writes, 0);
+        atlas.flush(&context);
+        atlas.flush(&context);
+        assert_eq!(atlas.texture_writes, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "weren't flushed")]
+    fn finish_unflushed_batch_panics() {
+        let context = test_context();
+        let config = TextureConfig::new(&context, FilterMode::Nearest);
+        let mut atlas = TextureAtlas::new_batched(&context, &config, TextureSize::new(64, 64));
+        atlas.load(&context, &test_images(1)[0]);
+        atlas.finish("test");
+    }
+
+    #[test]
+    fn gray_image_creates_r8_texture() {
+        let context = test_context();
+        let config = TextureConfig::new(&context, FilterMode::Nearest);
+        let image = Image {
+            width: 3,
+            height: 2,
+            format: ImageFormat::Gray,
+            data: vec![0, 64, 128, 192, 255, 32],
+        };
+        assert_eq!(image.data.len(), (image.width * image.height) as usize);
+        let texture = image.create_texture(&context, &config);
+        assert_eq!(texture.format(), wgpu::TextureFormat::R8Unorm);
+        assert_eq!(texture.size(), TextureSize::new(3, 2));
+    }
+}
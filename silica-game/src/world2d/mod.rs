@@ -64,10 +64,12 @@ impl Default for Camera2D {
 struct Uniforms {
     view_matrix: CameraTransform,
     screen_resolution: [f32; 2],
+    tint: Rgba,
 }
 
 pub struct Pipeline2D {
     pipeline: wgpu::RenderPipeline,
+    uniforms: Uniforms,
     uniforms_buffer: wgpu::Buffer,
     uniforms_bind_group: wgpu::BindGroup,
 }
@@ -103,6 +105,7 @@ impl Pipeline2D {
         let uniforms = Uniforms {
             view_matrix: CameraTransform::identity(),
             screen_resolution: [0.0; 2],
+            tint: Rgba::WHITE,
         };
         let uniforms_buffer = device.create_buffer_init(&util::BufferInitDescriptor {
             label: Some("world2d uniforms"),
@@ -153,19 +156,29 @@ impl Pipeline2D {
 
         Pipeline2D {
             pipeline,
+            uniforms,
             uniforms_buffer,
             uniforms_bind_group,
         }
     }
 
     pub fn set_camera(&mut self, context: &Context, camera_transform: CameraTransform, size: SurfaceSize) {
-        let uniforms = Uniforms {
-            view_matrix: camera_transform,
-            screen_resolution: size.to_f32().to_array(),
-        };
+        self.uniforms.view_matrix = camera_transform;
+        self.uniforms.screen_resolution = size.to_f32().to_array();
+        self.write_uniforms(context);
+    }
+    /// Sets a color multiplied with the color of every quad drawn with this pipeline. Defaults to white.
+    pub fn set_tint(&mut self, context: &Context, tint: Rgba) {
+        self.uniforms.tint = tint;
+        self.write_uniforms(context);
+    }
+    pub fn tint(&self) -> Rgba {
+        self.uniforms.tint
+    }
+    fn write_uniforms(&self, context: &Context) {
         context
             .queue
-            .write_buffer(&self.uniforms_buffer, 0, bytemuck::bytes_of(&uniforms));
+            .write_buffer(&self.uniforms_buffer, 0, bytemuck::bytes_of(&self.uniforms));
     }
 }
 
@@ -184,3 +197,72 @@ impl BatcherPipeline for Pipeline2D {
         pass.draw(0..4, range);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use silica_wgpu::{Batcher, Texture, Uv};
+
+    use super::*;
+    use crate::tests::{read_pixels, test_context};
+
+    /// Draws a white quad covering a 4x4 target with `tint`, and returns the target's pixels.
+    fn draw_tinted(tint: Rgba) -> Vec<[u8; 4]> {
+        let context = test_context();
+        let texture_config = TextureConfig::new(&context, wgpu::FilterMode::Nearest);
+        let frame = context.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let size = SurfaceSize::new(4, 4);
+        let mut pipeline = Pipeline2D::new(&context, &texture_config);
+        pipeline.set_camera(&context, Camera2D::default().transform(size, None), size);
+        pipeline.set_tint(&context, tint);
+        let mut batcher = Batcher::new(&context);
+        batcher.set_texture(&Texture::white(&context, &texture_config));
+        batcher.queue(Quad {
+            transform: Quad::rect_transform(Rect::new(Point::new(-2.0, -2.0), Size::new(4.0, 4.0))),
+            uv: Uv::FULL,
+            color: Rgba::WHITE,
+        });
+
+        let view = frame.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        batcher.draw(&context, &mut pass, &pipeline);
+        drop(pass);
+        context.queue.submit([encoder.finish()]);
+        read_pixels(&context, &frame)
+    }
+
+    #[test]
+    fn tint_multiplies_quad_color() {
+        assert!(draw_tinted(Rgba::WHITE).iter().all(|pixel| *pixel == [255; 4]));
+        assert!(
+            draw_tinted(Rgba::new(1.0, 0.5, 0.0, 1.0))
+                .iter()
+                .all(|pixel| *pixel == [255, 128, 0, 255])
+        );
+    }
+}
@@ -12,9 +12,13 @@ struct VertexOutput {
     @location(1) color: vec4f,
 }
 
+// The view matrix is stored as separate columns, since a mat3x2f gets a 16 byte column stride in GL uniform blocks.
 struct Params {
-    view_matrix: mat3x2f,
+    view_x: vec2f,
+    view_y: vec2f,
+    view_offset: vec2f,
     screen_resolution: vec2f,
+    tint: vec4f,
 }
 
 @group(0) @binding(0)
@@ -32,7 +36,7 @@ fn vs_main(in_vert: VertexInput) -> VertexOutput {
         in_vert.vertex_idx & 1u,
         (in_vert.vertex_idx >> 1u) & 1u,
     ));
-    let view_transform = mat3x3f(vec3f(params.view_matrix[0], 0.0), vec3f(params.view_matrix[1], 0.0), vec3f(params.view_matrix[2], 1.0));
+    let view_transform = mat3x3f(vec3f(params.view_x, 0.0), vec3f(params.view_y, 0.0), vec3f(params.view_offset, 1.0));
     let model_transform = mat3x3f(vec3f(in_vert.transform0.xy, 0.0), vec3f(in_vert.transform0.z, in_vert.transform1.x, 0.0), vec3f(in_vert.transform1.yz, 1.0));
     let pos = view_transform * model_transform * vec3f(corner_position, 1.0);
     let uv = mix(in_vert.uv.xy, in_vert.uv.zw, corner_position);
@@ -41,7 +45,7 @@ fn vs_main(in_vert: VertexInput) -> VertexOutput {
     out_vert.position = vec4f(2.0 * pos.xy / params.screen_resolution - 1.0, 0.0, 1.0);
     out_vert.position.y *= -1.0;
     out_vert.uv = uv;
-    out_vert.color = in_vert.color;
+    out_vert.color = in_vert.color * params.tint;
     return out_vert;
 }
 
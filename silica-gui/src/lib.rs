@@ -1,3 +1,30 @@
+/// Traces the rest of the enclosing scope as a span with the `tracing` feature, or logs its name at trace
+/// level without it. Exported for the other silica crates, so the feature is checked here rather than in the
+/// caller.
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! trace_scope {
+    ($name:literal) => {
+        let _span = $crate::__private::tracing::trace_span!($name).entered();
+    };
+}
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! trace_scope {
+    ($name:literal) => {
+        $crate::__private::log::trace!($name);
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use log;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+}
+
 pub mod render;
 pub mod theme;
 mod widget;
@@ -7,6 +34,7 @@ use std::{
     cell::RefCell,
     marker::PhantomData,
     rc::Rc,
+    time::{Duration, Instant},
 };
 
 pub use glyphon;
@@ -36,19 +64,41 @@ impl Hotkey {
     }
 }
 
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub mod1: bool,
+    pub mod2: bool,
+}
+
+/// Keys used to move through lists and menus, and to edit fields, without a mouse. Up, Down, and
+/// Backspace repeat while held.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NavKey {
+    Up,
+    Down,
+    Enter,
+    Escape,
+    Backspace,
+}
+
 pub trait KeyboardEvent {
     fn to_hotkey(&self) -> Option<Hotkey>;
+    fn to_nav_key(&self) -> Option<NavKey>;
 }
 
 pub trait MouseButtonEvent {
     fn is_primary_button(&self) -> bool;
+    fn is_secondary_button(&self) -> bool;
     fn is_pressed(&self) -> bool;
+    fn modifiers(&self) -> Modifiers;
 }
 
 pub enum InputEvent<Keyboard, MouseButton> {
     Keyboard(Keyboard),
     MouseMotion(Point),
     MouseButton(MouseButton),
+    /// Vertical wheel movement in pixels, positive when scrolling up.
     MouseWheel(f32),
 }
 
@@ -56,38 +106,109 @@ pub enum InputEvent<Keyboard, MouseButton> {
 pub struct GuiInput {
     pub blocked: bool,
     pub grabbed: bool,
+    /// Set while a key event is being delivered to the focused node, before the rest of the tree.
+    pub focused: bool,
+    /// Position of the pointer in layout coordinates, with the offsets of the scroll areas enclosing the
+    /// widget receiving the input removed.
     pub pointer: Point,
     pub button_pressed: bool,
+    pub modifiers: Modifiers,
     pub clicked: bool,
+    /// Set along with `clicked` when the previous click was recent and close to the pointer.
     pub double_clicked: bool,
+    pub secondary_pressed: bool,
+    pub secondary_clicked: bool,
     pub hotkey: Option<Hotkey>,
+    pub nav_key: Option<NavKey>,
+    /// Wheel movement of the current event, cleared once a scroll area uses it.
+    pub wheel: f32,
+    last_click: Option<(Instant, Point)>,
+    /// Cursor asked for by the innermost widget under the pointer, or the grabbed widget.
+    cursor: Option<CursorIcon>,
 }
 
 impl GuiInput {
-    fn process<K: KeyboardEvent, M: MouseButtonEvent>(&mut self, event: &InputEvent<K, M>) {
+    const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+    const DOUBLE_CLICK_DISTANCE: i32 = 4;
+    /// The pointer position relative to the top left corner of `area`'s content rect.
+    pub fn local_pointer(&self, area: &Area) -> Point {
+        (self.pointer - area.content_rect.origin).to_point()
+    }
+    fn process<K: KeyboardEvent, M: MouseButtonEvent>(&mut self, event: &InputEvent<K, M>, time: Instant) {
         match event {
-            InputEvent::Keyboard(keyboard_event) => self.hotkey = keyboard_event.to_hotkey(),
+            InputEvent::Keyboard(keyboard_event) => {
+                self.hotkey = keyboard_event.to_hotkey();
+                self.nav_key = keyboard_event.to_nav_key();
+            }
             InputEvent::MouseMotion(point) => self.pointer = *point,
             InputEvent::MouseButton(mouse_button_event) => {
+                self.modifiers = mouse_button_event.modifiers();
                 if mouse_button_event.is_primary_button() {
                     if !self.button_pressed && mouse_button_event.is_pressed() {
                         self.clicked = true;
+                        self.detect_double_click(time);
                     }
                     self.button_pressed = mouse_button_event.is_pressed();
+                } else if mouse_button_event.is_secondary_button() {
+                    if !self.secondary_pressed && mouse_button_event.is_pressed() {
+                        self.secondary_clicked = true;
+                    }
+                    self.secondary_pressed = mouse_button_event.is_pressed();
                 }
             }
-            InputEvent::MouseWheel(_) => {}
+            InputEvent::MouseWheel(delta) => self.wheel = *delta,
+        }
+    }
+    fn detect_double_click(&mut self, now: Instant) {
+        let last_click = self.last_click.take();
+        self.double_clicked = last_click.is_some_and(|(time, position)| {
+            let distance = (self.pointer - position).abs();
+            now.duration_since(time) <= Self::DOUBLE_CLICK_TIME
+                && distance.x <= Self::DOUBLE_CLICK_DISTANCE
+                && distance.y <= Self::DOUBLE_CLICK_DISTANCE
+        });
+        // A third click starts a new double click instead of completing another one.
+        if !self.double_clicked {
+            self.last_click = Some((now, self.pointer));
         }
     }
     fn reset(&mut self) {
         self.blocked = false;
         self.grabbed = false;
+        self.focused = false;
         self.clicked = false;
         self.double_clicked = false;
+        self.secondary_clicked = false;
         self.hotkey = None;
+        self.nav_key = None;
+        self.wheel = 0.0;
+        self.cursor = None;
     }
 }
 
+/// Pointer cursor shapes widgets can ask for, named like CSS cursors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CursorIcon {
+    #[default]
+    Default,
+    Pointer,
+    Text,
+    Grab,
+    Grabbing,
+    ColResize,
+    RowResize,
+    NotAllowed,
+}
+
+/// Parts of the gui that act as the frame of a window without decorations, such as a custom title bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowRegion {
+    /// Pressing moves the window.
+    Drag,
+    /// Pressing near the edges of the window resizes it.
+    Resize,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum InputAction {
     Pass,
@@ -150,10 +271,16 @@ impl EventExecutor {
     pub fn queue(&mut self, event: EventFn, param: Option<Box<dyn Any>>) {
         self.funcs.push((event, param));
     }
-    pub fn execute(self, context: &mut impl EventContext) {
-        for func in self.funcs {
+    pub fn execute(mut self, context: &mut impl EventContext) {
+        self.execute_and_clear(context);
+    }
+    /// Runs all queued events and resets the executor, keeping its allocation so it can be reused for
+    /// the next input event. Check [`EventExecutor::needs_redraw`] before calling this.
+    pub fn execute_and_clear(&mut self, context: &mut impl EventContext) {
+        for func in self.funcs.drain(..) {
             func.0.0(context, func.1);
         }
+        self.redraw = false;
     }
     pub fn request_redraw(&mut self) {
         self.redraw = true;
@@ -163,6 +290,9 @@ impl EventExecutor {
     }
 }
 
+/// A shared handle to a font system. Clones share the same fonts and caches, so one font system can be
+/// used by several themes and guis. It's borrowed while text is shaped, measured, and prepared for
+/// drawing, so a borrow from [`FontSystem::borrow_mut`] must not be held while calling into a gui.
 #[derive(Clone)]
 pub struct FontSystem(Rc<RefCell<glyphon::FontSystem>>);
 
@@ -187,11 +317,16 @@ impl FontSystem {
     pub fn borrow_mut(&self) -> std::cell::RefMut<'_, glyphon::FontSystem> {
         self.0.borrow_mut()
     }
+    /// Whether both refer to the same font system.
+    pub fn ptr_eq(&self, other: &FontSystem) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 pub trait Upcast {
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn type_name(&self) -> &'static str;
 }
 
 #[allow(unused)]
@@ -200,6 +335,29 @@ pub trait Widget: Upcast + 'static {
         Size::zero()
     }
     fn layout(&mut self, area: &Area) {}
+    /// Called when the widget's node is given a parent. The widget is detached from its node during the
+    /// call, so `gui` can't access it.
+    fn on_mount(&mut self, gui: &mut Gui) {}
+    /// Called when the widget's node is deleted, before it's removed from the tree.
+    fn on_unmount(&mut self) {}
+    /// Called when the gui's theme is replaced. Widgets holding resources from the old theme, such as its
+    /// font system, should replace them.
+    fn theme_changed(&mut self, theme: &dyn Theme) {}
+    /// Advances animations by `dt` seconds. Returns true while the widget is animating and needs to be
+    /// redrawn.
+    fn update(&mut self, dt: f32) -> bool {
+        false
+    }
+    /// Whether clicking the widget gives it keyboard focus. Key events are delivered to the focused widget
+    /// first, with [`GuiInput::focused`] set.
+    fn focusable(&self) -> bool {
+        false
+    }
+    /// The cursor to show while the pointer is over the widget, or while it's grabbed. Widgets are asked
+    /// innermost first, and the first one that returns a cursor decides it.
+    fn cursor(&self, input: &GuiInput, area: &Area) -> Option<CursorIcon> {
+        None
+    }
     fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
         InputAction::Pass
     }
@@ -213,6 +371,11 @@ impl<T: Widget> Upcast for T {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+    fn type_name(&self) -> &'static str {
+        let name = std::any::type_name::<T>();
+        let path = name.split('<').next().unwrap_or(name);
+        &name[path.rfind("::").map(|i| i + 2).unwrap_or(0)..]
+    }
 }
 impl LayoutWidget for Box<dyn Widget> {
     fn measure(&mut self, available_space: Size) -> Size {
@@ -246,17 +409,35 @@ pub struct Gui {
     theme: Rc<dyn Theme>,
     nodes: SlotMap<NodeId, Node>,
     parents: SecondaryMap<NodeId, NodeId>,
+    owned: SecondaryMap<NodeId, Vec<NodeId>>,
     children: SecondaryMap<NodeId, Vec<NodeId>>,
     root: NodeId,
     input: GuiInput,
     grabbed_node: Option<NodeId>,
+    focused_node: Option<NodeId>,
+    cursor: CursorIcon,
+    popups: Vec<(NodeId, Point)>,
+    modals: Vec<NodeId>,
+    tooltips: SecondaryMap<NodeId, String>,
+    tooltip_delay: f32,
+    hovered_tooltip: Option<(NodeId, f32)>,
+    tooltip_popup: Option<(NodeId, WidgetId<Label>)>,
+    window_regions: SecondaryMap<NodeId, WindowRegion>,
     layout_area: Rect,
+    content_size: Size,
     needs_layout: bool,
-    batcher: Option<ImmediateBatcher<render::Quad>>,
+    needs_redraw: bool,
+    full_damage: bool,
+    damage: Option<Rect>,
+    batchers: Option<(ImmediateBatcher<render::Quad>, ImmediateBatcher<render::Quad>)>,
+    font_system_changed: bool,
     exit_requested: bool,
 }
 
 impl Gui {
+    /// Drawn over the gui behind a modal.
+    const MODAL_BACKDROP: Rgba = Rgba::gray_alpha(0.0, 0.5);
+    const TOOLTIP_MAX_WIDTH: i32 = 320;
     pub fn new(theme: Rc<dyn Theme>) -> Self {
         let mut nodes = SlotMap::with_key();
         let root = nodes.insert(Node::default());
@@ -264,16 +445,45 @@ impl Gui {
             theme,
             nodes,
             parents: SecondaryMap::new(),
+            owned: SecondaryMap::new(),
             children: SecondaryMap::new(),
             root,
             input: GuiInput::default(),
             grabbed_node: None,
+            focused_node: None,
+            cursor: CursorIcon::Default,
+            popups: Vec::new(),
+            modals: Vec::new(),
+            tooltips: SecondaryMap::new(),
+            tooltip_delay: 0.5,
+            hovered_tooltip: None,
+            tooltip_popup: None,
+            window_regions: SecondaryMap::new(),
             layout_area: Rect::zero(),
+            content_size: Size::zero(),
             needs_layout: false,
-            batcher: None,
+            needs_redraw: false,
+            full_damage: true,
+            damage: None,
+            batchers: None,
+            font_system_changed: false,
             exit_requested: false,
         }
     }
+    pub fn theme(&self) -> Rc<dyn Theme> {
+        self.theme.clone()
+    }
+    /// Replaces the theme, updating every widget and laying out again.
+    pub fn set_theme(&mut self, theme: Rc<dyn Theme>) {
+        self.font_system_changed |= !theme.font_system().ptr_eq(self.theme.font_system());
+        self.theme = theme;
+        for node in self.nodes.values_mut() {
+            if let Some(widget) = node.widget.as_mut() {
+                widget.theme_changed(self.theme.as_ref());
+            }
+        }
+        self.request_layout();
+    }
     pub fn font_system(&self) -> &FontSystem {
         self.theme.font_system()
     }
@@ -283,17 +493,195 @@ impl Gui {
     pub fn root(&self) -> NodeId {
         self.root
     }
+    /// The cursor the widgets under the pointer ask for, as of the last pointer event.
+    pub fn cursor(&self) -> CursorIcon {
+        self.cursor
+    }
+    /// The pointer position as of the last input event, such as where to open a context menu.
+    pub fn pointer(&self) -> Point {
+        self.input.pointer
+    }
+    pub fn focused_node(&self) -> Option<NodeId> {
+        self.focused_node.filter(|node| self.nodes.contains_key(*node))
+    }
+    pub fn set_focus(&mut self, node: Option<NodeId>) {
+        self.focused_node = node;
+    }
     pub fn set_root(&mut self, root: impl Into<NodeId>) {
         self.root = root.into();
         self.needs_layout = true;
     }
     pub fn clear(&mut self) {
+        for node in self.nodes.values_mut() {
+            if let Some(widget) = node.widget.as_mut() {
+                widget.on_unmount();
+            }
+        }
         self.nodes.clear();
         self.parents.clear();
+        self.owned.clear();
         self.children.clear();
+        self.popups.clear();
+        self.modals.clear();
+        self.tooltips.clear();
+        self.hovered_tooltip = None;
+        self.tooltip_popup = None;
+        self.window_regions.clear();
         self.root = self.nodes.insert(Node::default());
         self.needs_layout = true;
     }
+    /// Shows `node` above the rest of the gui with its top left corner at `position`, moved if needed to
+    /// keep it inside the gui's area. The node shouldn't have a parent. Popups receive input before the
+    /// root, with the most recently opened first.
+    pub fn open_popup(&mut self, node: impl Into<NodeId>, position: Point) {
+        let node = node.into();
+        self.popups.retain(|(popup, _)| *popup != node);
+        self.popups.push((node, position));
+        self.nodes[node].mark_dirty();
+        self.needs_layout = true;
+        self.request_redraw();
+    }
+    pub fn close_popup(&mut self, node: impl Into<NodeId>) {
+        let node = node.into();
+        self.popups.retain(|(popup, _)| *popup != node);
+        if self.focused_node == Some(node) {
+            self.focused_node = None;
+        }
+        self.request_redraw();
+    }
+    pub fn is_popup_open(&self, node: impl Into<NodeId>) -> bool {
+        let node = node.into();
+        self.popups.iter().any(|(popup, _)| *popup == node)
+    }
+    /// Shows `node` centered over the rest of the gui, which is darkened and can't be interacted with until
+    /// the modal is popped. The node shouldn't have a parent. Popups that are open are closed, since they
+    /// belong to the gui behind the modal; popups opened afterwards are shown above it.
+    pub fn push_modal(&mut self, node: impl Into<NodeId>) {
+        let node = node.into();
+        self.popups.clear();
+        self.hovered_tooltip = None;
+        self.focused_node = None;
+        self.grabbed_node = None;
+        self.modals.retain(|modal| *modal != node);
+        self.modals.push(node);
+        self.nodes[node].mark_dirty();
+        self.needs_layout = true;
+        self.request_redraw();
+    }
+    /// Removes the topmost modal, returning it. The node isn't deleted.
+    pub fn pop_modal(&mut self) -> Option<NodeId> {
+        let modal = self.modals.pop()?;
+        self.popups.clear();
+        self.focused_node = None;
+        self.grabbed_node = None;
+        self.request_redraw();
+        Some(modal)
+    }
+    /// The topmost modal, which is the only part of the gui (besides popups) that receives input.
+    pub fn modal(&self) -> Option<NodeId> {
+        self.modals.last().copied()
+    }
+    /// Sets the text shown near the pointer after it rests over `node` for [`Gui::tooltip_delay`] seconds.
+    pub fn set_tooltip(&mut self, node: impl Into<NodeId>, tooltip: Option<&str>) {
+        let node = node.into();
+        match tooltip {
+            Some(tooltip) => {
+                self.tooltips.insert(node, tooltip.to_string());
+            }
+            None => {
+                self.tooltips.remove(node);
+            }
+        }
+    }
+    pub fn tooltip_delay(&self) -> f32 {
+        self.tooltip_delay
+    }
+    pub fn set_tooltip_delay(&mut self, delay: f32) {
+        self.tooltip_delay = delay;
+    }
+    /// Marks `node` as part of the window frame. Only presses that aren't handled by the gui move or resize
+    /// the window, so nodes with a background should set [`Style::input_transparent`].
+    pub fn set_window_region(&mut self, node: impl Into<NodeId>, region: Option<WindowRegion>) {
+        let node = node.into();
+        match region {
+            Some(region) => {
+                self.window_regions.insert(node, region);
+            }
+            None => {
+                self.window_regions.remove(node);
+            }
+        }
+    }
+    /// Whether `point` is over a visible node marked with `region`.
+    pub fn in_window_region(&self, point: Point, region: WindowRegion) -> bool {
+        self.window_regions
+            .iter()
+            .filter(|(node, node_region)| **node_region == region && self.is_visible(*node))
+            .any(|(node, _)| self.screen_rect(node).contains(point))
+    }
+    /// Whether a node and all of its ancestors are shown, and it's in the tree under the root or a popup.
+    fn is_visible(&self, mut id: NodeId) -> bool {
+        loop {
+            match self.nodes.get(id) {
+                Some(node) if !node.style.hidden && node.style.opacity > 0.0 => {}
+                _ => return false,
+            }
+            match self.parents.get(id) {
+                Some(&parent) => id = parent,
+                None => return id == self.root || self.is_popup_open(id) || self.modals.contains(&id),
+            }
+        }
+    }
+    fn update_hovered_tooltip(&mut self) {
+        let pointer = self.input.pointer;
+        // The smallest node is the innermost one if several nested nodes have tooltips.
+        let hovered = self
+            .tooltips
+            .keys()
+            .filter(|node| self.is_visible(*node))
+            .map(|node| (node, self.screen_rect(node)))
+            .filter(|(_, rect)| rect.contains(pointer))
+            .min_by_key(|(_, rect)| rect.area())
+            .map(|(node, _)| node);
+        if hovered != self.hovered_tooltip.map(|(node, _)| node) {
+            self.hide_tooltip();
+            self.hovered_tooltip = hovered.map(|node| (node, 0.0));
+            // Start drawing frames so that update is called and the delay runs.
+            self.needs_redraw |= hovered.is_some();
+        }
+    }
+    fn show_tooltip(&mut self, node: NodeId) {
+        let Some(text) = self.tooltips.get(node).cloned() else {
+            return;
+        };
+        let (popup, label) = match self.tooltip_popup {
+            Some(tooltip_popup) => tooltip_popup,
+            None => {
+                let label = LabelBuilder::new("").build(self);
+                // Long tooltips wrap instead of stretching across the gui.
+                let popup = NodeBuilder::new()
+                    .style(Style {
+                        max_size: Size::new(Self::TOOLTIP_MAX_WIDTH, i32::MAX),
+                        background_color: Some(Color::Background),
+                        border_color: Some(Color::Border),
+                        border: SideOffsets::new_all_same(1),
+                        padding: SideOffsets::new(2, 4, 2, 4),
+                        ..Default::default()
+                    })
+                    .child(label)
+                    .build(self);
+                self.tooltip_popup = Some((popup, label));
+                (popup, label)
+            }
+        };
+        label.set_text(self, &text);
+        self.open_popup(popup, self.input.pointer + Vector::new(12, 16));
+    }
+    fn hide_tooltip(&mut self) {
+        if let Some((popup, _)) = self.tooltip_popup {
+            self.close_popup(popup);
+        }
+    }
     pub fn get_widget<W: Widget>(&self, id: WidgetId<W>) -> Option<&W> {
         self.nodes
             .get(id.into())
@@ -301,11 +689,22 @@ impl Gui {
             .map(|widget| widget.as_any().downcast_ref().expect("WidgetId has incorrect type"))
     }
     pub fn get_widget_mut<W: Widget>(&mut self, id: WidgetId<W>) -> Option<&mut W> {
+        if self.nodes.contains_key(id.into()) {
+            self.request_node_redraw(id);
+        }
         self.nodes
             .get_mut(id.into())
             .and_then(|node| node.widget.as_mut())
             .map(|widget| widget.as_any_mut().downcast_mut().expect("WidgetId has incorrect type"))
     }
+    /// Iterates over every widget of type `W`.
+    pub fn widgets_mut<W: Widget>(&mut self) -> impl Iterator<Item = (WidgetId<W>, &mut W)> {
+        self.request_redraw();
+        self.nodes.iter_mut().filter_map(|(id, node)| {
+            let widget = node.widget.as_mut()?.as_any_mut().downcast_mut()?;
+            Some((WidgetId(id, PhantomData), widget))
+        })
+    }
     #[must_use]
     pub fn create_widget<W: Widget>(&mut self, style: Style, widget: W) -> WidgetId<W> {
         WidgetId(self.nodes.insert(Node::new(style, Some(Box::new(widget)))), PhantomData)
@@ -321,8 +720,24 @@ impl Gui {
             for child in children.iter() {
                 self.parents.insert(*child, node);
             }
-            self.children.insert(node, children);
-            self.needs_layout = true;
+            self.children.insert(node, children.clone());
+            self.invalidate(node);
+            for child in children {
+                self.mount_node(child);
+            }
+        }
+    }
+    fn mount_node(&mut self, node: NodeId) {
+        if let Some(mut widget) = self.nodes.get_mut(node).and_then(|node| node.widget.take()) {
+            widget.on_mount(self);
+            if let Some(node) = self.nodes.get_mut(node) {
+                node.widget = Some(widget);
+            }
+        }
+    }
+    fn unmount_node(&mut self, node: NodeId) {
+        if let Some(widget) = self.nodes.get_mut(node).and_then(|node| node.widget.as_mut()) {
+            widget.on_unmount();
         }
     }
     pub fn delete(&mut self, node: impl Into<NodeId>) {
@@ -331,16 +746,38 @@ impl Gui {
             self.remove_child(parent, node);
         }
         self.delete_children(node);
+        self.delete_owned(node);
+        self.unmount_node(node);
         self.nodes.remove(node);
+        self.popups.retain(|(popup, _)| *popup != node);
+        self.modals.retain(|modal| *modal != node);
+        self.tooltips.remove(node);
+        self.window_regions.remove(node);
     }
     pub fn delete_children(&mut self, parent: impl Into<NodeId>) {
-        if let Some(children) = self.children.remove(parent.into()) {
+        let parent = parent.into();
+        if let Some(children) = self.children.remove(parent) {
             for child in children {
                 self.delete_children(child);
+                self.delete_owned(child);
+                self.unmount_node(child);
                 self.parents.remove(child);
                 self.nodes.remove(child);
+                self.tooltips.remove(child);
+                self.window_regions.remove(child);
             }
-            self.needs_layout = true;
+            self.invalidate(parent);
+        }
+    }
+    /// Deletes `node` when `owner` is deleted, for nodes that aren't in the tree, such as popups.
+    pub fn add_owned(&mut self, owner: impl Into<NodeId>, node: impl Into<NodeId>) {
+        if let Some(owned) = self.owned.entry(owner.into()) {
+            owned.or_default().push(node.into());
+        }
+    }
+    fn delete_owned(&mut self, owner: NodeId) {
+        for node in self.owned.remove(owner).unwrap_or_default() {
+            self.delete(node);
         }
     }
     pub fn add_child(&mut self, parent: impl Into<NodeId>, child: impl Into<NodeId>) {
@@ -350,36 +787,122 @@ impl Gui {
             self.remove_child(prev_parent, child);
         }
         self.children.entry(parent).unwrap().or_default().push(child);
-        self.needs_layout = true;
+        self.invalidate(parent);
+        self.mount_node(child);
     }
     pub fn remove_child(&mut self, parent: impl Into<NodeId>, child: impl Into<NodeId>) {
-        if let Some(children) = self.children.get_mut(parent.into()) {
+        let parent = parent.into();
+        if let Some(children) = self.children.get_mut(parent) {
             let child = child.into();
             children.retain(|c| *c != child);
             self.parents.remove(child);
-            self.needs_layout = true;
+            self.invalidate(parent);
+        }
+    }
+    /// Returns the content rect of a node as it appears on screen, with the offsets and clipping of any
+    /// enclosing scroll areas and clipped overflowing nodes applied. Fully clipped nodes have an empty rect.
+    pub fn screen_rect(&self, node: impl Into<NodeId>) -> Rect {
+        let id = node.into();
+        self.clip_to_screen(id, self.nodes.get(id).unwrap().area.content_rect)
+    }
+    /// Moves a rect in the layout coordinates of a node to where it appears on screen, clipped like the
+    /// node.
+    fn clip_to_screen(&self, mut id: NodeId, mut rect: Rect) -> Rect {
+        loop {
+            if let Some(clip_rect) = self.nodes.get(id).unwrap().area.clip_rect {
+                rect = rect.intersection(&clip_rect).unwrap_or_default();
+            }
+            let Some(&parent) = self.parents.get(id) else {
+                break;
+            };
+            let node = self.nodes.get(parent).unwrap();
+            if let Some(scroll_area) = node
+                .widget
+                .as_ref()
+                .and_then(|widget| widget.as_any().downcast_ref::<ScrollArea>())
+            {
+                rect = rect
+                    .translate(scroll_area.offset(&node.area))
+                    .intersection(&node.area.content_rect)
+                    .unwrap_or_default();
+            }
+            id = parent;
         }
+        rect
     }
     pub fn get_style(&self, node: impl Into<NodeId>) -> &Style {
         &self.nodes.get(node.into()).unwrap().style
     }
     pub fn set_style(&mut self, node: impl Into<NodeId>, style: Style) {
-        self.nodes.get_mut(node.into()).unwrap().style = style;
-        self.needs_layout = true;
+        let node = node.into();
+        self.nodes.get_mut(node).unwrap().style = style;
+        self.invalidate(node);
     }
     pub fn modify_style<F>(&mut self, node: impl Into<NodeId>, f: F)
     where
         F: FnOnce(&mut Style),
     {
-        f(&mut self.nodes.get_mut(node.into()).unwrap().style);
-        self.needs_layout = true;
+        let node = node.into();
+        f(&mut self.nodes.get_mut(node).unwrap().style);
+        self.invalidate(node);
     }
     pub fn needs_layout(&self) -> bool {
         self.needs_layout
     }
+    /// Lays out the whole gui again, measuring every node.
     pub fn request_layout(&mut self) {
+        for node in self.nodes.values_mut() {
+            node.mark_dirty();
+        }
         self.needs_layout = true;
     }
+    /// Lays out the gui again, measuring only this node and its ancestors. Use this after changing a widget
+    /// in a way that changes its measured size.
+    pub fn request_node_layout(&mut self, node: impl Into<NodeId>) {
+        self.invalidate(node.into());
+    }
+    fn invalidate(&mut self, mut node: NodeId) {
+        loop {
+            if let Some(node) = self.nodes.get_mut(node) {
+                node.mark_dirty();
+            }
+            match self.parents.get(node) {
+                Some(&parent) => node = parent,
+                None => break,
+            }
+        }
+        self.needs_layout = true;
+    }
+    /// Whether the gui needs to be drawn again. Mutable access to a widget or a pending layout will
+    /// request a redraw.
+    pub fn needs_redraw(&self) -> bool {
+        self.needs_redraw || self.needs_layout
+    }
+    pub fn request_redraw(&mut self) {
+        self.needs_redraw = true;
+        self.full_damage = true;
+    }
+    /// Requests a redraw when only `node` has changed, so [`Gui::damage_rect`] only grows to cover it.
+    pub fn request_node_redraw(&mut self, node: impl Into<NodeId>) {
+        let node = node.into();
+        let rect = self.clip_to_screen(node, self.nodes[node].area.background_rect);
+        self.damage = Some(match self.damage {
+            Some(damage) => damage.union(&rect),
+            None => rect,
+        });
+        self.needs_redraw = true;
+    }
+    /// The area changed since the last render, in screen coordinates, or `None` if nothing changed.
+    /// Widgets that animate or are changed through [`Gui::get_widget_mut`] add only their own area; other
+    /// changes, such as layout, input handled by widgets, and [`Gui::request_redraw`], damage the whole gui.
+    /// Render with [`render::LoadOp::LoadDamaged`] to only draw this area.
+    pub fn damage_rect(&self) -> Option<Rect> {
+        if self.full_damage || self.needs_layout {
+            Some(self.layout_area)
+        } else {
+            self.damage
+        }
+    }
     pub fn exit_requested(&self) -> bool {
         self.exit_requested
     }
@@ -387,18 +910,156 @@ impl Gui {
         self.exit_requested = true;
     }
 
+    /// Advances the animations of all visible widgets by `dt` seconds. Returns true, and requests a
+    /// redraw, if any widget is animating.
+    pub fn update(&mut self, dt: f32) -> bool {
+        let mut animating_nodes = Vec::new();
+        Self::update_node(self.root, &mut self.nodes, &self.children, dt, &mut animating_nodes);
+        for &(popup, _) in self.popups.iter() {
+            Self::update_node(popup, &mut self.nodes, &self.children, dt, &mut animating_nodes);
+        }
+        let mut animating = !animating_nodes.is_empty();
+        for node in animating_nodes {
+            self.request_node_redraw(node);
+        }
+        if let Some((node, time)) = self.hovered_tooltip
+            && time < self.tooltip_delay
+        {
+            let time = time + dt;
+            self.hovered_tooltip = Some((node, time));
+            if time >= self.tooltip_delay {
+                self.show_tooltip(node);
+            } else {
+                animating = true;
+            }
+        }
+        self.needs_redraw |= animating;
+        animating
+    }
+    fn update_node(
+        id: NodeId,
+        nodes: &mut SlotMap<NodeId, Node>,
+        children: &SecondaryMap<NodeId, Vec<NodeId>>,
+        dt: f32,
+        animating_nodes: &mut Vec<NodeId>,
+    ) {
+        let node = nodes.get_mut(id).unwrap();
+        if node.area.hidden {
+            return;
+        }
+        if node.widget.as_mut().is_some_and(|widget| widget.update(dt)) {
+            animating_nodes.push(id);
+        }
+        if let Some(node_children) = children.get(id) {
+            for child in node_children.iter() {
+                Self::update_node(*child, nodes, children, dt, animating_nodes);
+            }
+        }
+    }
+    /// Returns an indented description of the tree starting from the root, for debugging layout.
+    pub fn dump_tree(&self) -> String {
+        let mut out = String::new();
+        self.dump_node(self.root, 0, &mut out);
+        out
+    }
+    fn dump_node(&self, id: NodeId, depth: usize, out: &mut String) {
+        use std::fmt::Write;
+        let node = self.nodes.get(id).unwrap();
+        let name = node.widget.as_ref().map(|widget| widget.type_name()).unwrap_or("Node");
+        let rect = node.area.content_rect;
+        let measured = node.area.measured_size;
+        let _ = writeln!(
+            out,
+            "{:indent$}{name} {:?} {:?} at {},{} size {}x{} measured {}x{}{}",
+            "",
+            node.style.layout,
+            node.style.direction,
+            rect.origin.x,
+            rect.origin.y,
+            rect.size.width,
+            rect.size.height,
+            measured.width,
+            measured.height,
+            if node.area.hidden { " hidden" } else { "" },
+            indent = depth * 2,
+        );
+        if let Some(children) = self.children.get(id) {
+            for child in children.iter() {
+                self.dump_node(*child, depth + 1, out);
+            }
+        }
+    }
+
+    /// The area the gui is laid out in.
+    pub fn area(&self) -> Rect {
+        self.layout_area
+    }
     pub fn set_area(&mut self, area: Rect) {
         if self.layout_area != area {
             self.layout_area = area;
             self.needs_layout = true;
         }
     }
+    /// The smallest size the gui can be laid out in, as of the last layout.
+    pub fn content_size(&self) -> Size {
+        self.content_size
+    }
     pub fn layout(&mut self) {
         if self.needs_layout {
-            measure_and_layout(&mut self.nodes, &self.children, self.root, self.layout_area);
+            trace_scope!("gui layout");
+            self.content_size = measure(&mut self.nodes, &self.children, self.root, Size::zero());
+            // Showing or hiding auto scrollbars changes the available space, so lay out again until they
+            // settle. Showing a scrollbar can only make content overflow more, so this can't oscillate.
+            for _ in 0..4 {
+                measure_and_layout(&mut self.nodes, &self.children, self.root, self.layout_area);
+                if !self.update_auto_scrollbars() {
+                    break;
+                }
+            }
+            // Popups are measured with the whole area available, since measuring with no space would give
+            // their labels no size.
+            for &(popup, position) in self.popups.iter() {
+                let size =
+                    measure(&mut self.nodes, &self.children, popup, self.layout_area.size).min(self.layout_area.size);
+                let max = self.layout_area.max() - size.to_vector();
+                let origin = position.min(max).max(self.layout_area.origin);
+                layout(&mut self.nodes, &self.children, popup, Rect::new(origin, size));
+            }
+            for &modal in self.modals.iter() {
+                let size =
+                    measure(&mut self.nodes, &self.children, modal, self.layout_area.size).min(self.layout_area.size);
+                let origin = self.layout_area.center() - (size / 2).to_vector();
+                layout(&mut self.nodes, &self.children, modal, Rect::new(origin, size));
+            }
             self.needs_layout = false;
         }
     }
+    fn update_auto_scrollbars(&mut self) -> bool {
+        let mut changes = Vec::new();
+        for node in self.nodes.values() {
+            if node.area.hidden {
+                continue;
+            }
+            if let Some(scroll_area) = node
+                .widget
+                .as_ref()
+                .and_then(|widget| widget.as_any().downcast_ref::<ScrollArea>())
+            {
+                let overflows = node.area.overflows();
+                for &(scrollbar, vertical) in scroll_area.auto_scrollbars() {
+                    let hidden = !if vertical { overflows.y } else { overflows.x };
+                    if self.nodes[scrollbar].style.hidden != hidden {
+                        changes.push((scrollbar, hidden));
+                    }
+                }
+            }
+        }
+        for (scrollbar, hidden) in changes.iter() {
+            self.nodes[*scrollbar].style = Slider::scrollbar_style(*hidden);
+            self.invalidate(*scrollbar);
+        }
+        !changes.is_empty()
+    }
 
     fn render_node(
         id: NodeId,
@@ -407,16 +1068,19 @@ impl Gui {
         renderer: &mut GuiRenderer,
     ) {
         let node = nodes.get_mut(id).unwrap();
-        if node.area.hidden {
+        let parent_opacity = renderer.opacity();
+        let opacity = parent_opacity * node.style.opacity.clamp(0.0, 1.0);
+        if node.area.hidden || opacity <= 0.0 {
             return;
         }
+        renderer.set_opacity(opacity);
+        let scroll_count = renderer.scroll.len();
+        if let Some(clip_rect) = node.area.clip_rect {
+            renderer.push_scroll_area(clip_rect, Vector::zero());
+        }
         if let Some(background_color) = node.style.background_color {
             let color = renderer.theme().color(background_color);
-            renderer.draw_theme_quad(render::Quad {
-                rect: node.area.background_rect.to_box2d(),
-                uv: GuiRenderer::UV_WHITE,
-                color,
-            });
+            renderer.draw_opaque_quad(node.area.background_rect.to_box2d(), color);
         }
         if let Some(border_color) = node.style.border_color {
             let color = renderer.theme().color(border_color);
@@ -428,7 +1092,6 @@ impl Gui {
                 color,
             );
         }
-        let scroll_count = renderer.scroll.len();
         if let Some(widget) = node.widget.as_mut() {
             widget.draw(renderer, &node.area);
         }
@@ -440,41 +1103,191 @@ impl Gui {
         while renderer.scroll.len() > scroll_count {
             renderer.pop_scroll_area();
         }
+        renderer.set_opacity(parent_opacity);
     }
     pub fn render(&mut self, context: &Context, pass: &mut wgpu::RenderPass, resources: &mut render::GuiResources) {
+        self.render_clipped(context, pass, resources, false);
+    }
+    fn render_clipped(
+        &mut self,
+        context: &Context,
+        pass: &mut wgpu::RenderPass,
+        resources: &mut render::GuiResources,
+        damaged_only: bool,
+    ) {
+        trace_scope!("gui render");
+        let damage = self.damage_rect();
         self.layout();
-        let batcher = self.batcher.take().unwrap_or_else(|| ImmediateBatcher::new(context));
+        self.needs_redraw = false;
+        self.full_damage = false;
+        self.damage = None;
+        let clip = match damage {
+            Some(damage) if damaged_only => Some(damage),
+            None if damaged_only => return,
+            _ => None,
+        };
+        if std::mem::take(&mut self.font_system_changed) {
+            // Glyphs cached by the old font system may have the same keys as glyphs from the new one.
+            resources.clear_text_atlas(context);
+        }
+        let (batcher, opaque_batcher) = self
+            .batchers
+            .take()
+            .unwrap_or_else(|| (ImmediateBatcher::new(context), ImmediateBatcher::new(context)));
         let mut renderer = GuiRenderer {
             theme: self.theme.clone(),
             resources,
             batcher,
+            opaque_batcher,
+            blended_rects: Vec::new(),
             context,
             pass,
             scroll: Vec::new(),
+            opacity: 1.0,
         };
+        if let Some(clip) = clip {
+            // The target keeps the previous frame, so only the damaged area is cleared and drawn again.
+            renderer.push_scroll_area(clip, Vector::zero());
+            renderer.draw_opaque_quad(clip.to_box2d(), self.background_color());
+        }
         Self::render_node(self.root, &mut self.nodes, &self.children, &mut renderer);
+        for &modal in self.modals.iter() {
+            renderer.draw_theme_quad(render::Quad {
+                rect: self.layout_area.to_box2d(),
+                uv: GuiRenderer::UV_WHITE,
+                color: Self::MODAL_BACKDROP,
+            });
+            Self::render_node(modal, &mut self.nodes, &self.children, &mut renderer);
+        }
+        for &(popup, _) in self.popups.iter() {
+            Self::render_node(popup, &mut self.nodes, &self.children, &mut renderer);
+        }
+        if clip.is_some() {
+            renderer.pop_scroll_area();
+        }
         renderer.finish();
-        self.batcher = Some(renderer.batcher);
+        self.batchers = Some((renderer.batcher, renderer.opaque_batcher));
+    }
+
+    /// Begins a render pass on `view` and renders the GUI into it. With [`render::LoadOp::Load`] the GUI is
+    /// drawn over whatever the target already contains, such as a game world rendered earlier in the frame.
+    pub fn render_to_view(
+        &mut self,
+        context: &Context,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        load: render::LoadOp,
+        resources: &mut render::GuiResources,
+    ) {
+        let damaged_only = load == render::LoadOp::LoadDamaged;
+        let load = match load {
+            render::LoadOp::Clear => {
+                let background_color = self.background_color();
+                wgpu::LoadOp::Clear(wgpu::Color {
+                    r: background_color.r as f64,
+                    g: background_color.g as f64,
+                    b: background_color.b as f64,
+                    a: 1.0,
+                })
+            }
+            render::LoadOp::Load | render::LoadOp::LoadDamaged => wgpu::LoadOp::Load,
+        };
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        self.render_clipped(context, &mut pass, resources, damaged_only);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn dispatch_input_event(
         id: NodeId,
         nodes: &mut SlotMap<NodeId, Node>,
         children: &SecondaryMap<NodeId, Vec<NodeId>>,
+        theme: &dyn Theme,
         input: &mut GuiInput,
         grabbed_node: &mut Option<NodeId>,
+        focused_node: &mut Option<NodeId>,
         executor: &mut EventExecutor,
     ) {
-        if nodes.get(id).unwrap().area.hidden {
+        let node = nodes.get(id).unwrap();
+        // Fully transparent nodes are click-through, so fading something out stops it taking input.
+        if node.area.hidden || node.style.opacity <= 0.0 {
             return;
         }
+        let scroll = node
+            .widget
+            .as_ref()
+            .and_then(|widget| widget.as_any().downcast_ref::<ScrollArea>())
+            .map(|scroll_area| {
+                (
+                    scroll_area.offset(&node.area),
+                    node.area.content_rect.contains(input.pointer),
+                )
+            });
+        let blocked = input.blocked;
+        if let Some((offset, pointer_inside)) = scroll {
+            input.pointer -= offset;
+            // Children scrolled out of view can't be under the pointer.
+            input.blocked |= !pointer_inside;
+        }
         if let Some(node_children) = children.get(id) {
             for child in node_children.iter().rev() {
-                Self::dispatch_input_event(*child, nodes, children, input, grabbed_node, executor);
+                Self::dispatch_input_event(
+                    *child,
+                    nodes,
+                    children,
+                    theme,
+                    input,
+                    grabbed_node,
+                    focused_node,
+                    executor,
+                );
+            }
+        }
+        if let Some((offset, pointer_inside)) = scroll {
+            input.pointer += offset;
+            if !pointer_inside {
+                input.blocked = blocked;
             }
         }
         let node = nodes.get_mut(id).unwrap();
+        // The innermost scroll area under the pointer that can still move takes the wheel.
+        if let Some((_, true)) = scroll
+            && input.wheel != 0.0
+            && !blocked
+            && let Some(scroll_area) = node
+                .widget
+                .as_mut()
+                .and_then(|widget| widget.as_any_mut().downcast_mut::<ScrollArea>())
+            && scroll_area.scroll_wheel(input.wheel, &node.area, executor)
+        {
+            input.wheel = 0.0;
+        }
         if let Some(widget) = node.widget.as_mut() {
+            if input.cursor.is_none()
+                && (input.grabbed || (!input.blocked && node.area.background_rect.contains(input.pointer)))
+            {
+                input.cursor = widget.cursor(input, &node.area);
+            }
+            // Children are visited first, so the innermost focusable widget under the pointer gets focus.
+            if input.clicked
+                && focused_node.is_none()
+                && widget.focusable()
+                && node.area.background_rect.contains(input.pointer)
+            {
+                *focused_node = Some(id);
+            }
             match widget.input(input, executor, &node.area) {
                 InputAction::Pass => {}
                 InputAction::Block => {
@@ -485,39 +1298,124 @@ impl Gui {
                     *grabbed_node = Some(id);
                 }
             }
-        } else if node.style.background_color.is_some() && node.area.background_rect.contains(input.pointer) {
+        } else if let Some(background_color) = node.style.background_color
+            && !node.style.input_transparent
+            && theme.color(background_color).a > 0.0
+            && node.area.background_rect.contains(input.pointer)
+        {
             input.blocked = true;
         }
     }
+    /// Dispatches the input to a single node outside of the usual tree walk, with the pointer moved into
+    /// the node's scrolled coordinates.
+    fn dispatch_input_event_to(&mut self, id: NodeId, executor: &mut EventExecutor) {
+        let offset = self.scroll_offset(id);
+        self.input.pointer -= offset;
+        Self::dispatch_input_event(
+            id,
+            &mut self.nodes,
+            &self.children,
+            self.theme.as_ref(),
+            &mut self.input,
+            &mut self.grabbed_node,
+            &mut self.focused_node,
+            executor,
+        );
+        self.input.pointer += offset;
+    }
+    /// Total offset of the scroll areas enclosing a node.
+    fn scroll_offset(&self, mut id: NodeId) -> Vector {
+        let mut offset = Vector::zero();
+        while let Some(&parent) = self.parents.get(id) {
+            let node = &self.nodes[parent];
+            if let Some(scroll_area) = node
+                .widget
+                .as_ref()
+                .and_then(|widget| widget.as_any().downcast_ref::<ScrollArea>())
+            {
+                offset += scroll_area.offset(&node.area);
+            }
+            id = parent;
+        }
+        offset
+    }
+    /// Delivers an input event to the widgets. `time` is when the event happened, which is used to detect
+    /// double clicks.
     pub fn handle_input<K: KeyboardEvent, M: MouseButtonEvent>(
         &mut self,
         event: InputEvent<K, M>,
+        time: Instant,
     ) -> (EventExecutor, Option<InputEvent<K, M>>) {
-        self.input.process(&event);
         let mut executor = EventExecutor::new();
+        let unhandled_event = self.handle_input_with(event, time, &mut executor);
+        (executor, unhandled_event)
+    }
+    /// Same as [`Gui::handle_input`], but queues events on an existing executor instead of allocating a
+    /// new one.
+    pub fn handle_input_with<K: KeyboardEvent, M: MouseButtonEvent>(
+        &mut self,
+        event: InputEvent<K, M>,
+        time: Instant,
+        executor: &mut EventExecutor,
+    ) -> Option<InputEvent<K, M>> {
+        self.input.process(&event, time);
+        match &event {
+            InputEvent::MouseMotion(_) => self.update_hovered_tooltip(),
+            InputEvent::MouseButton(_) | InputEvent::MouseWheel(_) => {
+                self.hide_tooltip();
+                self.hovered_tooltip = None;
+            }
+            InputEvent::Keyboard(_) => {}
+        }
+        if self.input.clicked {
+            self.focused_node = None;
+        }
+        // Keys go to the focused node first, then to the whole tree (as hotkeys) if it doesn't block them.
+        let focused_node = self.focused_node().filter(|_| matches!(event, InputEvent::Keyboard(_)));
         if let Some(id) = self.grabbed_node.take() {
             self.input.grabbed = true;
-            Self::dispatch_input_event(
-                id,
-                &mut self.nodes,
-                &self.children,
-                &mut self.input,
-                &mut self.grabbed_node,
-                &mut executor,
-            );
+            self.dispatch_input_event_to(id, executor);
         } else {
-            Self::dispatch_input_event(
-                self.root,
-                &mut self.nodes,
-                &self.children,
-                &mut self.input,
-                &mut self.grabbed_node,
-                &mut executor,
-            );
+            if let Some(id) = focused_node {
+                self.input.focused = true;
+                self.dispatch_input_event_to(id, executor);
+                self.input.focused = false;
+            }
+            if !self.input.blocked {
+                for &(popup, _) in self.popups.iter().rev() {
+                    Self::dispatch_input_event(
+                        popup,
+                        &mut self.nodes,
+                        &self.children,
+                        self.theme.as_ref(),
+                        &mut self.input,
+                        &mut self.grabbed_node,
+                        &mut self.focused_node,
+                        executor,
+                    );
+                }
+                // Only the topmost modal is visited while there is one, and it blocks everything else.
+                Self::dispatch_input_event(
+                    self.modal().unwrap_or(self.root),
+                    &mut self.nodes,
+                    &self.children,
+                    self.theme.as_ref(),
+                    &mut self.input,
+                    &mut self.grabbed_node,
+                    &mut self.focused_node,
+                    executor,
+                );
+                self.input.blocked |= !self.modals.is_empty();
+            }
+        }
+        // Input can change any part of the gui, such as siblings of the widget that handled it.
+        self.full_damage |= executor.needs_redraw();
+        if !matches!(event, InputEvent::Keyboard(_)) {
+            self.cursor = self.input.cursor.unwrap_or_default();
         }
         let unhandled_event = if self.input.blocked { None } else { Some(event) };
         self.input.reset();
-        (executor, unhandled_event)
+        unhandled_event
     }
 }
 impl EventContext for Gui {
@@ -529,3 +1427,838 @@ impl EventContext for Gui {
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::cell::Cell;
+
+    use silica_asset::DirectorySource;
+    use silica_wgpu::{AdapterFeatures, SurfaceSize, Texture, TextureConfig};
+
+    use super::*;
+    use crate::{render::GuiResources, theme::StandardTheme};
+
+    pub(crate) fn theme_source() -> DirectorySource {
+        DirectorySource::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../theme/dark_theme").into())
+    }
+
+    /// Measures text with the dark theme's font, but has no texture, so it can't be rendered.
+    struct TestTheme {
+        font_system: FontSystem,
+    }
+
+    impl Theme for TestTheme {
+        fn font_system(&self) -> &FontSystem {
+            &self.font_system
+        }
+        fn texture(&self) -> &Texture {
+            unimplemented!("the test theme can't be rendered")
+        }
+        fn color(&self, color: Color) -> Rgba {
+            match color {
+                Color::Custom(rgba) => rgba,
+                _ => Rgba::BLACK,
+            }
+        }
+        fn button_foreground_color(&self, _state: ButtonState) -> Rgba {
+            Rgba::WHITE
+        }
+        fn draw_gutter(&self, _renderer: &mut GuiRenderer, _rect: Rect) {}
+        fn draw_button(
+            &self,
+            _renderer: &mut GuiRenderer,
+            _rect: Rect,
+            _style: ButtonStyle,
+            _toggled: bool,
+            _state: ButtonState,
+        ) {
+        }
+    }
+
+    /// Creates an empty gui with a `size` area, for tests that don't render.
+    pub(crate) fn test_gui(size: Size) -> Gui {
+        let font_system = FontSystem::with_font_asset(&mut theme_source(), "Rubik-Light.ttf").unwrap();
+        let mut gui = Gui::new(Rc::new(TestTheme { font_system }));
+        gui.set_area(Rect::new(Point::origin(), size));
+        gui
+    }
+
+    /// Takes up to 50x40 pixels of the available space, and fills it with a color.
+    pub(crate) struct TestWidget(pub(crate) Rgba);
+
+    impl Widget for TestWidget {
+        fn measure(&mut self, available_space: Size) -> Size {
+            available_space.min(Size::new(50, 40))
+        }
+        fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+            renderer.draw_opaque_quad(area.content_rect.to_box2d(), self.0);
+        }
+    }
+
+    /// Creates a context for rendering into `Rgba8Unorm` textures.
+    pub(crate) fn test_context() -> Context {
+        let mut context = Context::init(AdapterFeatures::default());
+        context.surface_format = Some(wgpu::TextureFormat::Rgba8Unorm);
+        context
+    }
+
+    /// A texture guis are rendered into and read back from, with the dark theme.
+    pub(crate) struct TestTarget {
+        pub(crate) context: Context,
+        pub(crate) texture_config: TextureConfig,
+        pub(crate) resources: GuiResources,
+        texture: wgpu::Texture,
+    }
+
+    impl TestTarget {
+        pub(crate) fn new(size: SurfaceSize) -> Self {
+            let context = test_context();
+            let texture_config = TextureConfig::new(&context, wgpu::FilterMode::Nearest);
+            let mut resources = GuiResources::new(&context, &texture_config);
+            resources.surface_resize(&context, size);
+            let texture = context.device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: size.width,
+                    height: size.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+            TestTarget {
+                context,
+                texture_config,
+                resources,
+                texture,
+            }
+        }
+        pub(crate) fn theme(&self) -> Rc<dyn Theme> {
+            Rc::new(StandardTheme::load(&self.context, &self.texture_config, &mut theme_source()).unwrap())
+        }
+        /// Creates an empty gui with the dark theme, covering the whole target.
+        pub(crate) fn gui(&self) -> Gui {
+            let mut gui = Gui::new(self.theme());
+            let size = self.texture.size();
+            gui.set_area(Rect::new(
+                Point::origin(),
+                Size::new(size.width as i32, size.height as i32),
+            ));
+            gui
+        }
+        pub(crate) fn render(&mut self, gui: &mut Gui, load: render::LoadOp) {
+            let view = self.texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let mut encoder = self
+                .context
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            gui.render_to_view(&self.context, &mut encoder, &view, load, &mut self.resources);
+            self.context.queue.submit([encoder.finish()]);
+        }
+        pub(crate) fn clear(&mut self, color: wgpu::Color) {
+            let view = self.texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let mut encoder = self
+                .context
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            self.context.queue.submit([encoder.finish()]);
+        }
+        /// Reads back the target's pixels, row by row.
+        pub(crate) fn pixels(&self) -> Vec<[u8; 4]> {
+            let size = self.texture.size();
+            let row_size = size.width * 4;
+            let padded_row_size = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+            let buffer = self.context.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: (padded_row_size * size.height) as u64,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let mut encoder = self
+                .context
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.copy_texture_to_buffer(
+                self.texture.as_image_copy(),
+                wgpu::TexelCopyBufferInfo {
+                    buffer: &buffer,
+                    layout: wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(padded_row_size),
+                        rows_per_image: None,
+                    },
+                },
+                size,
+            );
+            self.context.queue.submit([encoder.finish()]);
+            buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, |result| result.unwrap());
+            self.context.device.poll(wgpu::PollType::Wait).unwrap();
+            let data = buffer.slice(..).get_mapped_range();
+            data.chunks(padded_row_size as usize)
+                .flat_map(|row| row[..row_size as usize].chunks(4))
+                .map(|pixel| pixel.try_into().unwrap())
+                .collect()
+        }
+        pub(crate) fn pixel(&self, point: Point) -> [u8; 4] {
+            self.pixels()[(point.y * self.texture.width() as i32 + point.x) as usize]
+        }
+    }
+
+    /// A key press that's a hotkey, a nav key, or both.
+    pub(crate) struct TestKey(pub(crate) Option<Hotkey>, pub(crate) Option<NavKey>);
+
+    impl KeyboardEvent for TestKey {
+        fn to_hotkey(&self) -> Option<Hotkey> {
+            self.0
+        }
+        fn to_nav_key(&self) -> Option<NavKey> {
+            self.1
+        }
+    }
+
+    /// A primary or secondary button press or release.
+    pub(crate) struct TestButton {
+        pressed: bool,
+        secondary: bool,
+    }
+
+    impl TestButton {
+        pub(crate) fn primary(pressed: bool) -> Self {
+            TestButton {
+                pressed,
+                secondary: false,
+            }
+        }
+        pub(crate) fn secondary(pressed: bool) -> Self {
+            TestButton {
+                pressed,
+                secondary: true,
+            }
+        }
+    }
+    impl MouseButtonEvent for TestButton {
+        fn is_primary_button(&self) -> bool {
+            !self.secondary
+        }
+        fn is_secondary_button(&self) -> bool {
+            self.secondary
+        }
+        fn is_pressed(&self) -> bool {
+            self.pressed
+        }
+        fn modifiers(&self) -> Modifiers {
+            Modifiers::default()
+        }
+    }
+
+    pub(crate) type TestEvent = InputEvent<TestKey, TestButton>;
+
+    /// Delivers `event` to the gui, and runs the events it queues.
+    pub(crate) fn send(gui: &mut Gui, event: TestEvent) {
+        let (executor, _) = gui.handle_input(event, Instant::now());
+        executor.execute(gui);
+    }
+
+    /// Lays out the gui, then moves the pointer to `point` and clicks the primary button there.
+    pub(crate) fn click(gui: &mut Gui, point: Point) {
+        gui.layout();
+        send(gui, TestEvent::MouseMotion(point));
+        send(gui, TestEvent::MouseButton(TestButton::primary(true)));
+        send(gui, TestEvent::MouseButton(TestButton::primary(false)));
+    }
+
+    /// Lays out the gui, then moves the pointer to `point` and clicks the secondary button there.
+    pub(crate) fn secondary_click(gui: &mut Gui, point: Point) {
+        gui.layout();
+        send(gui, TestEvent::MouseMotion(point));
+        send(gui, TestEvent::MouseButton(TestButton::secondary(true)));
+        send(gui, TestEvent::MouseButton(TestButton::secondary(false)));
+    }
+
+    /// Presses the primary button at `time` and returns whether it was a double click.
+    fn press_at(input: &mut GuiInput, time: Instant) -> bool {
+        input.process(&TestEvent::MouseButton(TestButton::primary(true)), time);
+        let double_clicked = input.double_clicked;
+        input.reset();
+        input.process(&TestEvent::MouseButton(TestButton::primary(false)), time);
+        input.reset();
+        double_clicked
+    }
+
+    #[test]
+    fn quick_clicks_double_click() {
+        let mut input = GuiInput::default();
+        let start = Instant::now();
+        assert!(!press_at(&mut input, start));
+        assert!(press_at(&mut input, start + Duration::from_millis(200)));
+        // A third click starts over.
+        assert!(!press_at(&mut input, start + Duration::from_millis(300)));
+    }
+
+    #[test]
+    fn slow_clicks_dont_double_click() {
+        let mut input = GuiInput::default();
+        let start = Instant::now();
+        assert!(!press_at(&mut input, start));
+        assert!(!press_at(&mut input, start + Duration::from_secs(1)));
+    }
+
+    /// Builds a gui with two white test widgets in a column.
+    fn two_widgets(gui: &mut Gui) -> (WidgetId<TestWidget>, WidgetId<TestWidget>) {
+        let first = NodeBuilder::new().build_widget(gui, TestWidget(Rgba::WHITE));
+        let second = NodeBuilder::new().build_widget(gui, TestWidget(Rgba::WHITE));
+        let root = NodeBuilder::new()
+            .modify_style(|style| style.direction = Direction::Column)
+            .child(first)
+            .child(second)
+            .build(gui);
+        gui.set_root(root);
+        (first, second)
+    }
+
+    #[test]
+    fn widget_change_damages_its_area() {
+        let mut gui = test_gui(Size::new(200, 200));
+        let (_, second) = two_widgets(&mut gui);
+        gui.layout();
+        assert_eq!(gui.damage_rect(), Some(gui.area()));
+        // Drawing a frame resets the damage.
+        gui.full_damage = false;
+        assert_eq!(gui.damage_rect(), None);
+
+        gui.get_widget_mut(second).unwrap().0 = Rgba::RED;
+        assert_eq!(gui.damage_rect(), Some(gui.nodes[second.into()].area.background_rect));
+    }
+
+    #[test]
+    fn load_damaged_only_draws_damage() {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 96));
+        let mut gui = target.gui();
+        let (_, second) = two_widgets(&mut gui);
+        target.render(&mut gui, render::LoadOp::Clear);
+        assert_eq!(target.pixel(Point::new(10, 10)), [255, 255, 255, 255]);
+
+        target.clear(wgpu::Color::GREEN);
+        gui.get_widget_mut(second).unwrap().0 = Rgba::RED;
+        target.render(&mut gui, render::LoadOp::LoadDamaged);
+        assert_eq!(target.pixel(Point::new(10, 10)), [0, 255, 0, 255]);
+        assert_eq!(target.pixel(Point::new(10, 50)), [255, 0, 0, 255]);
+        assert_eq!(target.pixel(Point::new(10, 90)), [0, 255, 0, 255]);
+    }
+
+    /// Records the names of entered spans.
+    #[cfg(feature = "tracing")]
+    #[derive(Default)]
+    struct SpanRecorder {
+        spans: std::sync::Mutex<Vec<&'static str>>,
+        entered: std::sync::Mutex<Vec<&'static str>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata().name());
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+        fn event(&self, _event: &tracing::Event<'_>) {}
+        fn enter(&self, span: &tracing::span::Id) {
+            let name = self.spans.lock().unwrap()[span.into_u64() as usize - 1];
+            self.entered.lock().unwrap().push(name);
+        }
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn layout_enters_span() {
+        let mut gui = test_gui(Size::new(200, 200));
+        two_widgets(&mut gui);
+        let recorder = std::sync::Arc::new(SpanRecorder::default());
+        tracing::subscriber::with_default(recorder.clone(), || gui.layout());
+        assert_eq!(*recorder.entered.lock().unwrap(), ["gui layout"]);
+    }
+
+    /// Creates a node that's always `size`.
+    pub(crate) fn fixed_node(gui: &mut Gui, size: Size) -> NodeId {
+        NodeBuilder::new()
+            .modify_style(|style| {
+                style.min_size = size;
+                style.max_size = size;
+            })
+            .build(gui)
+    }
+
+    /// Makes a vertical scroll area with `policy` the root of the gui, holding `children` in a column.
+    pub(crate) fn scroll_root(gui: &mut Gui, policy: OverflowPolicy, children: Vec<NodeId>) -> WidgetId<ScrollArea> {
+        let container = ScrollAreaBuilder::new(
+            gui,
+            Style {
+                direction: Direction::Column,
+                cross_align: Align::Start,
+                grow: true,
+                ..Default::default()
+            },
+        )
+        .vertical_overflow(gui, policy)
+        .children(children)
+        .build(gui);
+        gui.set_root(container);
+        gui.widgets_mut::<ScrollArea>().next().unwrap().0
+    }
+
+    #[test]
+    fn screen_rect_applies_scroll() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let items: Vec<_> = (0..4).map(|_| fixed_node(&mut gui, Size::new(50, 50))).collect();
+        let area = scroll_root(&mut gui, OverflowPolicy::Scroll, items.clone());
+        gui.layout();
+        let viewport = gui.nodes[area.into()].area.content_rect;
+        assert_eq!((viewport.origin, viewport.height()), (Point::new(1, 1), 98));
+        assert_eq!(
+            gui.screen_rect(items[1]),
+            Rect::new(Point::new(1, 51), Size::new(50, 48))
+        );
+
+        // Scrolled to the end, the 200 pixel column is moved up by 102 pixels to fit in the 98 pixel viewport.
+        area.set_scroll(&mut gui, 1.0, true);
+        assert_eq!(gui.nodes[items[3]].area.content_rect.min_y(), 151);
+        assert_eq!(
+            gui.screen_rect(items[3]),
+            Rect::new(Point::new(1, 49), Size::new(50, 50))
+        );
+        assert_eq!(
+            gui.screen_rect(items[2]),
+            Rect::new(Point::new(1, 1), Size::new(50, 48))
+        );
+        assert!(gui.screen_rect(items[0]).is_empty());
+    }
+
+    #[test]
+    fn widgets_mut_finds_one_type() {
+        let mut gui = test_gui(Size::new(200, 100));
+        let first = Label::create(&mut gui, "first");
+        let other = NodeBuilder::new().build_widget(&mut gui, TestWidget(Rgba::RED));
+        let second = Label::create(&mut gui, "second");
+        let mut labels: Vec<NodeId> = gui.widgets_mut::<Label>().map(|(id, _)| id.into()).collect();
+        labels.sort();
+        assert_eq!(labels, [first.into(), second.into()]);
+
+        for (_, widget) in gui.widgets_mut::<TestWidget>() {
+            widget.0 = Rgba::BLUE;
+        }
+        assert_eq!(gui.get_widget(other).unwrap().0, Rgba::BLUE);
+    }
+
+    #[test]
+    fn execute_and_clear_keeps_allocation() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let mut executor = EventExecutor::new();
+        let exit = EventFn::new(|gui: &mut Gui| gui.request_exit());
+        for _ in 0..8 {
+            executor.queue(exit.clone(), None);
+        }
+        executor.request_redraw();
+        let buffer = executor.funcs.as_ptr();
+        let capacity = executor.funcs.capacity();
+        executor.execute_and_clear(&mut gui);
+        assert!(gui.exit_requested());
+        assert!(executor.funcs.is_empty() && !executor.needs_redraw());
+        assert_eq!((executor.funcs.as_ptr(), executor.funcs.capacity()), (buffer, capacity));
+
+        // Reusing the executor doesn't run the events again.
+        let mut gui = test_gui(Size::new(100, 100));
+        executor.execute_and_clear(&mut gui);
+        assert!(!gui.exit_requested());
+    }
+
+    #[test]
+    fn dump_tree_nests_children() {
+        let mut gui = test_gui(Size::new(200, 100));
+        let widget = NodeBuilder::new().build_widget(&mut gui, TestWidget(Rgba::RED));
+        let hidden = NodeBuilder::new()
+            .modify_style(|style| style.hidden = true)
+            .build(&mut gui);
+        let inner = NodeBuilder::new()
+            .modify_style(|style| style.direction = Direction::Column)
+            .child(widget)
+            .child(hidden)
+            .build(&mut gui);
+        let root = NodeBuilder::new().child(inner).build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        let expected = "\
+Node Box Row at 0,0 size 200x100 measured 50x40
+  Node Box Column at 0,0 size 50x100 measured 50x40
+    TestWidget Box Row at 0,0 size 50x40 measured 50x40
+    Node Box Row at 0,0 size 0x0 measured 0x0 hidden
+";
+        assert_eq!(gui.dump_tree(), expected);
+    }
+
+    #[test]
+    fn load_keeps_target_contents() {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let widget = NodeBuilder::new().build_widget(&mut gui, TestWidget(Rgba::BLUE));
+        let root = NodeBuilder::new().child(widget).build(&mut gui);
+        gui.set_root(root);
+        target.clear(wgpu::Color::RED);
+        target.render(&mut gui, render::LoadOp::Load);
+        assert_eq!(target.pixel(Point::new(10, 10)), [0, 0, 255, 255]);
+        assert_eq!(target.pixel(Point::new(60, 60)), [255, 0, 0, 255]);
+
+        target.render(&mut gui, render::LoadOp::Clear);
+        assert_ne!(target.pixel(Point::new(60, 60)), [255, 0, 0, 255]);
+    }
+
+    /// Records when it's mounted and unmounted.
+    struct MountLog(&'static str, Rc<RefCell<Vec<String>>>);
+
+    impl Widget for MountLog {
+        fn on_mount(&mut self, _gui: &mut Gui) {
+            self.1.borrow_mut().push(format!("mount {}", self.0));
+        }
+        fn on_unmount(&mut self) {
+            self.1.borrow_mut().push(format!("unmount {}", self.0));
+        }
+        fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+    }
+
+    #[test]
+    fn delete_unmounts_subtree() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let child = NodeBuilder::new().build_widget(&mut gui, MountLog("child", log.clone()));
+        let parent = NodeBuilder::new()
+            .child(child)
+            .build_widget(&mut gui, MountLog("parent", log.clone()));
+        let root = NodeBuilder::new().child(parent).build(&mut gui);
+        gui.set_root(root);
+        assert_eq!(*log.borrow(), ["mount child", "mount parent"]);
+
+        log.borrow_mut().clear();
+        gui.delete(parent);
+        assert_eq!(*log.borrow(), ["unmount child", "unmount parent"]);
+        assert!(!gui.nodes.contains_key(child.into()));
+    }
+
+    #[test]
+    fn set_theme_replaces_background() {
+        let target = TestTarget::new(SurfaceSize::new(10, 10));
+        let mut gui = target.gui();
+        let dark_background = gui.background_color();
+        assert_ne!(dark_background, Rgba::BLACK);
+
+        let test_theme = test_gui(Size::new(10, 10)).theme();
+        gui.needs_layout = false;
+        gui.set_theme(test_theme.clone());
+        assert!(Rc::ptr_eq(&gui.theme(), &test_theme));
+        assert_eq!(gui.background_color(), Rgba::BLACK);
+        assert!(gui.needs_layout);
+
+        gui.set_theme(target.theme());
+        assert_eq!(gui.background_color(), dark_background);
+    }
+
+    /// Animates until `remaining` seconds have passed.
+    struct Countdown {
+        remaining: f32,
+    }
+
+    impl Widget for Countdown {
+        fn measure(&mut self, _available_space: Size) -> Size {
+            Size::new(20, 20)
+        }
+        fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+        fn update(&mut self, dt: f32) -> bool {
+            self.remaining = (self.remaining - dt).max(0.0);
+            self.remaining > 0.0
+        }
+    }
+
+    #[test]
+    fn update_advances_animations() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let animating = gui.create_widget(Style::default(), Countdown { remaining: 1.0 });
+        let idle = gui.create_widget(Style::default(), Countdown { remaining: 0.0 });
+        let root = NodeBuilder::new().child(animating).child(idle).build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        gui.needs_redraw = false;
+        gui.full_damage = false;
+
+        assert!(gui.update(0.75));
+        assert_eq!(gui.get_widget(animating).unwrap().remaining, 0.25);
+        assert!(gui.needs_redraw());
+        // Only the animating widget is damaged.
+        let animating_rect = gui.nodes[NodeId::from(animating)].area.background_rect;
+        assert_eq!(gui.damage_rect(), Some(animating_rect));
+
+        assert!(!gui.update(0.5));
+        assert_eq!(gui.get_widget(animating).unwrap().remaining, 0.0);
+        gui.needs_redraw = false;
+        assert!(!gui.update(0.5));
+        assert!(!gui.needs_redraw());
+    }
+
+    /// Records the local pointer of input over its content rect.
+    struct PointerLog(Rc<Cell<Option<Point>>>);
+
+    impl Widget for PointerLog {
+        fn input(&mut self, input: &GuiInput, _executor: &mut EventExecutor, area: &Area) -> InputAction {
+            if area.content_rect.contains(input.pointer) {
+                self.0.set(Some(input.local_pointer(area)));
+            }
+            InputAction::Pass
+        }
+        fn measure(&mut self, _available_space: Size) -> Size {
+            Size::new(50, 50)
+        }
+        fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+    }
+
+    #[test]
+    fn local_pointer_in_scrolled_area() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let local_pointer = Rc::new(Cell::new(None));
+        let mut items: Vec<_> = (0..3).map(|_| fixed_node(&mut gui, Size::new(50, 50))).collect();
+        let log = gui.create_widget(Style::default(), PointerLog(local_pointer.clone()));
+        items.push(log.into());
+        let area = scroll_root(&mut gui, OverflowPolicy::Scroll, items);
+        gui.layout();
+
+        // The last item is at 151 in layout coordinates, and 49 on screen once scrolled to the end.
+        area.set_scroll(&mut gui, 1.0, true);
+        gui.layout();
+        let rect = gui.screen_rect(log);
+        assert_eq!(rect.origin, Point::new(1, 49));
+        send(&mut gui, TestEvent::MouseMotion(rect.origin + Vector::new(10, 20)));
+        assert_eq!(local_pointer.take(), Some(Point::new(10, 20)));
+    }
+
+    #[test]
+    fn tooltip_appears_after_delay() {
+        let mut gui = test_gui(Size::new(200, 100));
+        let target = fixed_node(&mut gui, Size::new(200, 100));
+        gui.set_root(target);
+        gui.set_tooltip(target, Some("Saves the file"));
+        gui.layout();
+        let is_open = |gui: &Gui| gui.tooltip_popup.is_some_and(|(popup, _)| gui.is_popup_open(popup));
+
+        send(&mut gui, TestEvent::MouseMotion(Point::new(190, 90)));
+        assert!(gui.update(0.3));
+        assert!(!is_open(&gui));
+        gui.update(0.3);
+        assert!(is_open(&gui));
+        // Opened near the bottom right corner, the tooltip is moved to stay inside the gui.
+        gui.layout();
+        let (popup, _) = gui.tooltip_popup.unwrap();
+        let rect = gui.screen_rect(popup);
+        assert!(!rect.is_empty());
+        assert!(gui.layout_area.contains_rect(&rect));
+
+        send(&mut gui, TestEvent::MouseMotion(Point::new(300, 90)));
+        assert!(!is_open(&gui));
+    }
+
+    /// `clicked`, `button_pressed`, `secondary_clicked`, and `secondary_pressed` of an input.
+    type ButtonFlags = (bool, bool, bool, bool);
+
+    /// Records the button flags of every input it receives over its content rect.
+    struct ButtonLog(Rc<RefCell<Vec<ButtonFlags>>>);
+
+    impl Widget for ButtonLog {
+        fn input(&mut self, input: &GuiInput, _executor: &mut EventExecutor, area: &Area) -> InputAction {
+            if area.content_rect.contains(input.pointer) {
+                self.0.borrow_mut().push((
+                    input.clicked,
+                    input.button_pressed,
+                    input.secondary_clicked,
+                    input.secondary_pressed,
+                ));
+            }
+            InputAction::Pass
+        }
+        fn measure(&mut self, _available_space: Size) -> Size {
+            Size::new(50, 50)
+        }
+        fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+    }
+
+    #[test]
+    fn secondary_click_leaves_primary() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let widget = gui.create_widget(Style::default(), ButtonLog(log.clone()));
+        gui.set_root(widget);
+        secondary_click(&mut gui, Point::new(10, 10));
+        // Motion, press, and release.
+        assert_eq!(
+            *log.borrow(),
+            [
+                (false, false, false, false),
+                (false, false, true, true),
+                (false, false, false, false)
+            ]
+        );
+
+        log.borrow_mut().clear();
+        click(&mut gui, Point::new(10, 10));
+        assert!(
+            log.borrow()
+                .iter()
+                .all(|&(_, _, secondary_clicked, _)| !secondary_clicked)
+        );
+        assert!(log.borrow().iter().any(|&(clicked, _, _, _)| clicked));
+    }
+
+    /// Counts clicks over its content rect that no other node blocked.
+    struct ClickCount(Rc<Cell<u32>>);
+
+    impl Widget for ClickCount {
+        fn input(&mut self, input: &GuiInput, _executor: &mut EventExecutor, area: &Area) -> InputAction {
+            if input.clicked && !input.blocked && area.content_rect.contains(input.pointer) {
+                self.0.set(self.0.get() + 1);
+            }
+            InputAction::Pass
+        }
+        fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+    }
+
+    /// Clicks a widget covered by a node with a `background` color, and returns whether the widget saw it.
+    fn click_through(background: Rgba, input_transparent: bool) -> bool {
+        let mut gui = test_gui(Size::new(100, 100));
+        let clicks = Rc::new(Cell::new(0));
+        let widget = gui.create_widget(Style::default(), ClickCount(clicks.clone()));
+        let cover = gui.create_node(Style {
+            background_color: Some(Color::Custom(background)),
+            input_transparent,
+            ..Default::default()
+        });
+        let root = NodeBuilder::new()
+            .modify_style(|style| style.layout = Layout::Stack)
+            .children([widget.into(), cover])
+            .build(&mut gui);
+        gui.set_root(root);
+        click(&mut gui, Point::new(50, 50));
+        clicks.get() == 1
+    }
+
+    #[test]
+    fn transparent_background_passes_input() {
+        assert!(click_through(Rgba::new(1.0, 1.0, 1.0, 0.0), false));
+        assert!(!click_through(Rgba::BLACK, false));
+        assert!(!click_through(Rgba::gray_alpha(0.0, 0.01), false));
+        assert!(click_through(Rgba::BLACK, true));
+    }
+
+    #[test]
+    fn modal_blocks_input_behind_it() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let behind = Rc::new(Cell::new(0));
+        let root = gui.create_widget(Style::default(), ClickCount(behind.clone()));
+        gui.set_root(root);
+        let in_modal = Rc::new(Cell::new(0));
+        let button = gui.create_widget(Style::default(), ClickCount(in_modal.clone()));
+        let modal = NodeBuilder::new()
+            .modify_style(|style| {
+                style.layout = Layout::Stack;
+                style.min_size = Size::new(40, 40);
+            })
+            .child(button)
+            .build(&mut gui);
+        gui.push_modal(modal);
+        gui.layout();
+        assert_eq!(gui.screen_rect(modal), Rect::new(Point::new(30, 30), Size::new(40, 40)));
+
+        // The backdrop takes clicks outside the dialog.
+        click(&mut gui, Point::new(10, 10));
+        assert_eq!((behind.get(), in_modal.get()), (0, 0));
+        click(&mut gui, Point::new(50, 50));
+        assert_eq!((behind.get(), in_modal.get()), (0, 1));
+
+        assert_eq!(gui.pop_modal(), Some(modal));
+        click(&mut gui, Point::new(10, 10));
+        assert_eq!((behind.get(), in_modal.get()), (1, 1));
+    }
+
+    /// Clicks a widget covered by an opaque node with `opacity`, and returns whether the widget and the
+    /// cover's own child saw the click.
+    fn click_through_opacity(opacity: f32) -> (bool, bool) {
+        let mut gui = test_gui(Size::new(100, 100));
+        let behind = Rc::new(Cell::new(0));
+        let widget = gui.create_widget(Style::default(), ClickCount(behind.clone()));
+        let inside = Rc::new(Cell::new(0));
+        let child = gui.create_widget(Style::default(), ClickCount(inside.clone()));
+        let cover = NodeBuilder::new()
+            .style(Style {
+                layout: Layout::Stack,
+                background_color: Some(Color::Custom(Rgba::BLACK)),
+                opacity,
+                ..Default::default()
+            })
+            .child(child)
+            .build(&mut gui);
+        let root = NodeBuilder::new()
+            .modify_style(|style| style.layout = Layout::Stack)
+            .children([widget.into(), cover])
+            .build(&mut gui);
+        gui.set_root(root);
+        click(&mut gui, Point::new(50, 50));
+        (behind.get() == 1, inside.get() == 1)
+    }
+
+    #[test]
+    fn zero_opacity_passes_input() {
+        assert_eq!(click_through_opacity(0.0), (true, false));
+        assert_eq!(click_through_opacity(0.2), (false, true));
+        assert_eq!(click_through_opacity(1.0), (false, true));
+    }
+
+    #[test]
+    fn zero_opacity_is_not_drawn() {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let behind = NodeBuilder::new().build_widget(&mut gui, TestWidget(Rgba::BLUE));
+        let cover = NodeBuilder::new()
+            .modify_style(|style| style.opacity = 0.0)
+            .build_widget(&mut gui, TestWidget(Rgba::RED));
+        let root = NodeBuilder::new()
+            .modify_style(|style| style.layout = Layout::Stack)
+            .children([behind.into(), cover.into()])
+            .build(&mut gui);
+        gui.set_root(root);
+        target.render(&mut gui, render::LoadOp::Clear);
+        assert_eq!(target.pixel(Point::new(10, 10)), [0, 0, 255, 255]);
+
+        gui.modify_style(cover, |style| style.opacity = 1.0);
+        target.render(&mut gui, render::LoadOp::Clear);
+        assert_eq!(target.pixel(Point::new(10, 10)), [255, 0, 0, 255]);
+    }
+}
@@ -10,6 +10,20 @@ use silica_wgpu::{
 
 use crate::{FontSystem, Pixel, Rgba, theme::Theme};
 
+/// What happens to the existing contents of the target when the GUI is rendered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LoadOp {
+    /// Clear the target to the theme's background color.
+    #[default]
+    Clear,
+    /// Keep the existing contents, so the GUI is composited over them.
+    Load,
+    /// Only clear and redraw [`Gui::damage_rect`](crate::Gui::damage_rect), keeping the rest of the target.
+    /// The target must still hold the last frame the GUI rendered, so surface textures, which aren't
+    /// preserved between frames, need a copy of the frame kept in another texture.
+    LoadDamaged,
+}
+
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
 pub struct Quad {
@@ -74,7 +88,12 @@ impl Viewport {
 
 struct QuadPipeline {
     pipeline: wgpu::RenderPipeline,
+    opaque_pipeline: wgpu::RenderPipeline,
     viewport: Viewport,
+    opaque: bool,
+    /// Number of instances drawn with the opaque pipeline.
+    #[cfg(test)]
+    opaque_instances: std::cell::Cell<u32>,
 }
 
 impl QuadPipeline {
@@ -110,37 +129,48 @@ impl QuadPipeline {
             push_constant_ranges: &[],
         });
 
-        let pipeline = context.device.create_render_pipeline(&RenderPipelineDescriptor {
-            label: Some("silica pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[vertex_buffer_layout],
-                compilation_options: PipelineCompilationOptions::default(),
-            },
-            fragment: Some(FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(ColorTargetState {
-                    format: context.surface_format.expect("surface not created"),
-                    blend: Some(BlendState::ALPHA_BLENDING),
-                    write_mask: ColorWrites::default(),
-                })],
-                compilation_options: PipelineCompilationOptions::default(),
-            }),
-            primitive: PrimitiveState {
-                topology: PrimitiveTopology::TriangleStrip,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
+        let create_pipeline = |label, blend| {
+            context.device.create_render_pipeline(&RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: std::slice::from_ref(&vertex_buffer_layout),
+                    compilation_options: PipelineCompilationOptions::default(),
+                },
+                fragment: Some(FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(ColorTargetState {
+                        format: context.surface_format.expect("surface not created"),
+                        blend,
+                        write_mask: ColorWrites::default(),
+                    })],
+                    compilation_options: PipelineCompilationOptions::default(),
+                }),
+                primitive: PrimitiveState {
+                    topology: PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let pipeline = create_pipeline("silica pipeline", Some(BlendState::ALPHA_BLENDING));
+        let opaque_pipeline = create_pipeline("silica opaque pipeline", None);
         let viewport = Viewport::new(&context.device, &uniforms_layout);
 
-        QuadPipeline { pipeline, viewport }
+        QuadPipeline {
+            pipeline,
+            opaque_pipeline,
+            viewport,
+            opaque: false,
+            #[cfg(test)]
+            opaque_instances: Default::default(),
+        }
     }
     fn surface_resize(&mut self, context: &Context, size: SurfaceSize) {
         self.viewport.update(&context.queue, size);
@@ -148,7 +178,11 @@ impl QuadPipeline {
 }
 impl BatcherPipeline for QuadPipeline {
     fn bind(&self, pass: &mut wgpu::RenderPass) {
-        pass.set_pipeline(&self.pipeline);
+        if self.opaque {
+            pass.set_pipeline(&self.opaque_pipeline);
+        } else {
+            pass.set_pipeline(&self.pipeline);
+        }
         pass.set_bind_group(0, &self.viewport.bind_group, &[]);
     }
     fn set_buffer(&self, pass: &mut wgpu::RenderPass, buffer: &wgpu::Buffer) {
@@ -158,11 +192,17 @@ impl BatcherPipeline for QuadPipeline {
         pass.set_bind_group(1, texture, &[]);
     }
     fn draw(&self, pass: &mut wgpu::RenderPass, range: Range<u32>) {
+        #[cfg(test)]
+        if self.opaque {
+            self.opaque_instances
+                .set(self.opaque_instances.get() + range.len() as u32);
+        }
         pass.draw(0..4, range);
     }
 }
 
 pub struct TextResources {
+    pub cache: glyphon::Cache,
     pub swash_cache: glyphon::SwashCache,
     pub atlas: glyphon::TextAtlas,
     pub viewport: glyphon::Viewport,
@@ -181,11 +221,23 @@ impl TextResources {
         );
         let viewport = glyphon::Viewport::new(&context.device, &cache);
         TextResources {
+            cache,
             swash_cache,
             atlas,
             viewport,
         }
     }
+    /// Drops all cached glyphs. Text renderers created with the old atlas can't be used anymore.
+    fn clear_atlas(&mut self, context: &Context) {
+        self.atlas = glyphon::TextAtlas::with_color_mode(
+            &context.device,
+            &context.queue,
+            &self.cache,
+            context.surface_format.expect("surface not created"),
+            glyphon::ColorMode::Web,
+        );
+        self.swash_cache = glyphon::SwashCache::new();
+    }
     fn surface_resize(&mut self, context: &Context, size: SurfaceSize) {
         self.viewport.update(
             &context.queue,
@@ -220,6 +272,9 @@ impl GuiResources {
     pub fn text_resources(&mut self) -> &mut TextResources {
         &mut self.text_resources
     }
+    pub(crate) fn clear_text_atlas(&mut self, context: &Context) {
+        self.text_resources.clear_atlas(context);
+    }
 }
 
 pub(crate) struct ScrollArea {
@@ -227,43 +282,103 @@ pub(crate) struct ScrollArea {
     offset: Vector,
 }
 
+/// Draws the gui in tree order, so later nodes are drawn over earlier ones. Quads are batched, but any
+/// batch is drawn before text or a scroll area change, so the order quads and text are submitted in is
+/// always the order they appear in.
+///
+/// Opaque quads are queued separately and drawn in one pass without blending before the blended quads
+/// queued alongside them. This only keeps the tree order while no opaque quad covers a blended quad
+/// queued before it, so such an opaque quad draws both batches first.
 pub struct GuiRenderer<'a, 'b> {
     pub(crate) theme: Rc<dyn Theme>,
     pub(crate) resources: &'a mut GuiResources,
     pub(crate) batcher: ImmediateBatcher<Quad>,
+    pub(crate) opaque_batcher: ImmediateBatcher<Quad>,
+    pub(crate) blended_rects: Vec<Box2D<i32, Pixel>>,
     pub(crate) context: &'a Context,
     pub(crate) pass: &'a mut wgpu::RenderPass<'b>,
     pub(crate) scroll: Vec<ScrollArea>,
+    pub(crate) opacity: f32,
 }
 
 impl GuiRenderer<'_, '_> {
     pub const UV_WHITE: UvRect = UvRect::new(point2(-2.0, 0.0), point2(-2.0, 0.0));
     pub(crate) fn finish(&mut self) {
-        self.batcher.draw(self.pass, &self.resources.quad_pipeline);
+        self.flush();
+        self.opaque_batcher.finish(self.context);
         self.batcher.finish(self.context);
     }
+    /// Draws the queued opaque quads, then the queued blended quads.
+    fn flush(&mut self) {
+        self.resources.quad_pipeline.opaque = true;
+        self.opaque_batcher.draw(self.pass, &self.resources.quad_pipeline);
+        self.resources.quad_pipeline.opaque = false;
+        self.batcher.draw(self.pass, &self.resources.quad_pipeline);
+        self.blended_rects.clear();
+    }
+    fn queue_blended(&mut self, texture: &Texture, quad: Quad) {
+        // The blended batch must not be drawn before opaque quads queued ahead of it.
+        if self.batcher.would_draw(texture) {
+            self.flush();
+        }
+        let quad = self.faded(quad);
+        self.blended_rects.push(quad.rect);
+        self.batcher
+            .set_texture(self.pass, &self.resources.quad_pipeline, texture);
+        self.batcher
+            .queue(self.context, self.pass, &self.resources.quad_pipeline, quad);
+    }
     pub fn theme(&self) -> Rc<dyn Theme> {
         self.theme.clone()
     }
+    /// Opacity of the node being drawn, including its ancestors. Applied to everything drawn.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+    pub(crate) fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+    fn faded(&self, mut quad: Quad) -> Quad {
+        quad.color.a *= self.opacity;
+        quad.offset(self.scroll_offset())
+    }
     pub fn draw_theme_quad(&mut self, quad: Quad) {
-        self.batcher
+        let theme = self.theme.clone();
+        self.queue_blended(theme.texture(), quad);
+    }
+    /// Draws a solid color quad with blending disabled, which is cheaper for large backgrounds. Falls back
+    /// to [`GuiRenderer::draw_theme_quad`] if the color is not fully opaque.
+    pub fn draw_opaque_quad(&mut self, rect: Box2D<i32, Pixel>, color: Rgba) {
+        let quad = Quad {
+            rect,
+            uv: Self::UV_WHITE,
+            color,
+        };
+        if color.a < 1.0 || self.opacity < 1.0 {
+            self.draw_theme_quad(quad);
+            return;
+        }
+        let quad = self.faded(quad);
+        if self.blended_rects.iter().any(|rect| rect.intersects(&quad.rect)) {
+            self.flush();
+        }
+        // Opaque quads are only drawn with the opaque pipeline, so the batch can draw at any time.
+        self.resources.quad_pipeline.opaque = true;
+        self.opaque_batcher
             .set_texture(self.pass, &self.resources.quad_pipeline, self.theme.texture());
-        self.batcher.queue(
-            self.context,
-            self.pass,
-            &self.resources.quad_pipeline,
-            quad.offset(self.scroll_offset()),
-        );
+        self.opaque_batcher
+            .queue(self.context, self.pass, &self.resources.quad_pipeline, quad);
+        self.resources.quad_pipeline.opaque = false;
+    }
+    /// Draws a solid color rect with its corners rounded by `radius`, which is limited to half the smaller
+    /// side, so a square with a large radius is a circle. The edges are anti-aliased over about a pixel,
+    /// and `softness` widens them by that factor for a glow.
+    pub fn draw_rounded_rect(&mut self, rect: Box2D<i32, Pixel>, radius: f32, softness: f32, color: Rgba) {
+        let uv = UvRect::new(point2(-4.0, radius), point2(-4.0, softness));
+        self.draw_theme_quad(Quad { rect, uv, color });
     }
     pub fn draw_quad(&mut self, texture: &Texture, quad: Quad) {
-        self.batcher
-            .set_texture(self.pass, &self.resources.quad_pipeline, texture);
-        self.batcher.queue(
-            self.context,
-            self.pass,
-            &self.resources.quad_pipeline,
-            quad.offset(self.scroll_offset()),
-        );
+        self.queue_blended(texture, quad);
     }
     pub fn create_text_renderer(&mut self) -> TextRenderer {
         TextRenderer::new(
@@ -280,6 +395,7 @@ impl GuiRenderer<'_, '_> {
         text_areas: impl IntoIterator<Item = glyphon::TextArea<'a>>,
     ) {
         let offset = self.scroll_offset();
+        let opacity = self.opacity;
         text_renderer
             .prepare(
                 &self.context.device,
@@ -290,6 +406,15 @@ impl GuiRenderer<'_, '_> {
                 text_areas.into_iter().map(|mut area| {
                     area.left += offset.x as f32;
                     area.top += offset.y as f32;
+                    area.bounds.left = area.bounds.left.saturating_add(offset.x);
+                    area.bounds.top = area.bounds.top.saturating_add(offset.y);
+                    area.bounds.right = area.bounds.right.saturating_add(offset.x);
+                    area.bounds.bottom = area.bounds.bottom.saturating_add(offset.y);
+                    if opacity < 1.0 {
+                        let color = area.default_color;
+                        let alpha = (color.a() as f32 * opacity).round() as u8;
+                        area.default_color = glyphon::Color::rgba(color.r(), color.g(), color.b(), alpha);
+                    }
                     area
                 }),
                 &mut self.resources.text_resources.swash_cache,
@@ -297,7 +422,8 @@ impl GuiRenderer<'_, '_> {
             .unwrap();
     }
     pub fn draw_text(&mut self, text_renderer: &TextRenderer) {
-        self.batcher.draw(self.pass, &self.resources.quad_pipeline);
+        // Quads queued before this text must be drawn underneath it.
+        self.flush();
         text_renderer
             .render(
                 &self.resources.text_resources.atlas,
@@ -311,20 +437,36 @@ impl GuiRenderer<'_, '_> {
         self.scroll.last().map(|area| area.offset).unwrap_or_default()
     }
     fn set_scissor_rect(&mut self) {
-        let rect = self.scroll.last().map(|area| area.clip.to_u32()).unwrap_or_else(|| {
-            let res = self.resources.text_resources.viewport.resolution();
-            euclid::Rect::new(point2(0, 0), size2(res.width, res.height))
-        });
+        let res = self.resources.text_resources.viewport.resolution();
+        let viewport = euclid::Rect::new(point2(0, 0), size2(res.width, res.height));
+        let rect = match self.scroll.last() {
+            Some(area) => area
+                .clip
+                .intersection(&viewport.to_i32())
+                .map(|clip| clip.to_u32())
+                .unwrap_or_default(),
+            None => viewport,
+        };
         self.pass
             .set_scissor_rect(rect.origin.x, rect.origin.y, rect.size.width, rect.size.height);
     }
     pub fn push_scroll_area(&mut self, clip: Rect, offset: Vector) {
-        self.batcher.draw(self.pass, &self.resources.quad_pipeline);
+        self.flush();
+        // Nested scroll areas are positioned relative to the scroll area they are in.
+        let (clip, offset) = match self.scroll.last() {
+            Some(parent) => (
+                clip.translate(parent.offset)
+                    .intersection(&parent.clip)
+                    .unwrap_or_default(),
+                parent.offset + offset,
+            ),
+            None => (clip, offset),
+        };
         self.scroll.push(ScrollArea { clip, offset });
         self.set_scissor_rect();
     }
     pub fn pop_scroll_area(&mut self) {
-        self.batcher.draw(self.pass, &self.resources.quad_pipeline);
+        self.flush();
         self.scroll.pop();
         self.set_scissor_rect();
     }
@@ -334,3 +476,107 @@ impl DrawQuad<i32, Pixel> for GuiRenderer<'_, '_> {
         self.draw_theme_quad(Quad { rect, uv, color });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::TestTarget, *};
+
+    #[test]
+    fn later_opaque_sibling_covers_label() {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let label = LabelBuilder::new("MMM")
+            .font_size(40.0)
+            .color(Rgba::WHITE)
+            .build(&mut gui);
+        let panel = NodeBuilder::new()
+            .modify_style(|style| {
+                style.min_size = Size::new(64, 64);
+                style.background_color = Some(Color::Custom(Rgba::BLUE));
+            })
+            .build(&mut gui);
+        let root = NodeBuilder::new()
+            .modify_style(|style| style.layout = Layout::Stack)
+            .child(label)
+            .child(panel)
+            .build(&mut gui);
+        gui.set_root(root);
+        target.render(&mut gui, LoadOp::Clear);
+        assert!(target.pixels().iter().all(|pixel| *pixel == [0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn opaque_backgrounds_skip_blending() {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let panel = |gui: &mut Gui, color| {
+            NodeBuilder::new()
+                .modify_style(|style| {
+                    style.min_size = Size::new(32, 32);
+                    style.background_color = Some(Color::Custom(color));
+                })
+                .build(gui)
+        };
+        let opaque = panel(&mut gui, Rgba::BLUE);
+        let translucent = panel(&mut gui, Rgba::new(1.0, 0.0, 0.0, 0.5));
+        let root = NodeBuilder::new().child(opaque).child(translucent).build(&mut gui);
+        gui.set_root(root);
+        target.render(&mut gui, LoadOp::Clear);
+        assert_eq!(target.resources.quad_pipeline.opaque_instances.get(), 1);
+        assert_eq!(target.pixel(Point::new(16, 16)), [0, 0, 255, 255]);
+    }
+
+    /// Fills its area with a white rounded rect.
+    struct RoundedRect {
+        radius: f32,
+        softness: f32,
+    }
+
+    impl Widget for RoundedRect {
+        fn measure(&mut self, available_space: Size) -> Size {
+            available_space
+        }
+        fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+            let rect = area.content_rect.to_box2d();
+            renderer.draw_rounded_rect(rect, self.radius, self.softness, Rgba::WHITE);
+        }
+    }
+
+    /// Renders a circle of `diameter`, and returns the average width of its edge, found by dividing the
+    /// number of partly covered pixels by the circumference.
+    fn circle_edge_width(diameter: i32, softness: f32) -> f32 {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let radius = diameter as f32 / 2.0;
+        let circle = NodeBuilder::new()
+            .modify_style(|style| {
+                style.min_size = Size::splat(diameter);
+                style.max_size = Size::splat(diameter);
+            })
+            .build_widget(&mut gui, RoundedRect { radius, softness });
+        let root = NodeBuilder::new().child(circle).build(&mut gui);
+        gui.set_root(root);
+        target.render(&mut gui, LoadOp::Clear);
+        let pixels = target.pixels();
+        let background = pixels[64 * 64 - 1][0];
+        let center = diameter as usize / 2;
+        assert_eq!(pixels[64 * center + center], [255; 4]);
+        let edge_pixels = pixels
+            .iter()
+            .filter(|pixel| pixel[0] != 255 && pixel[0] != background)
+            .count();
+        edge_pixels as f32 / (std::f32::consts::PI * diameter as f32)
+    }
+
+    #[test]
+    fn rounded_rect_edges_stay_sharp() {
+        let small = circle_edge_width(16, 1.0);
+        let large = circle_edge_width(48, 1.0);
+        // The edge is about a pixel wide whatever the size, and only softness widens it.
+        assert!((0.5..1.5).contains(&small), "{small}");
+        assert!((0.5..1.5).contains(&large), "{large}");
+        assert!((small - large).abs() < 0.25);
+        assert!(circle_edge_width(48, 4.0) > large * 3.0);
+    }
+}
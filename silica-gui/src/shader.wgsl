@@ -9,6 +9,8 @@ struct VertexOutput {
     @invariant @builtin(position) position: vec4f,
     @location(0) uv: vec2f,
     @location(1) color: vec4f,
+    @location(2) @interpolate(flat) rect: vec4f,
+    @location(3) @interpolate(flat) shape: vec4f,
 }
 
 struct Params {
@@ -39,12 +41,26 @@ fn vs_main(in_vert: VertexInput) -> VertexOutput {
     out_vert.position.y *= -1.0;
     out_vert.uv = uv;
     out_vert.color = in_vert.color;
+    out_vert.rect = vec4f(in_vert.rect);
+    out_vert.shape = in_vert.uv;
     return out_vert;
 }
 
 @fragment
 fn fs_main(in_frag: VertexOutput) -> @location(0) vec4f {
-    if in_frag.uv.x < -1.0 {
+    // Rounded rects store (-4, radius, -4, softness) in the uv rect. The distance is computed for every
+    // quad, because derivatives are only available in uniform control flow.
+    let half_size = (in_frag.rect.zw - in_frag.rect.xy) * 0.5;
+    let radius = min(max(in_frag.shape.y, 0.0), min(half_size.x, half_size.y));
+    let p = abs(in_frag.position.xy - (in_frag.rect.xy + half_size)) - half_size + radius;
+    let distance = length(max(p, vec2f(0.0))) + min(max(p.x, p.y), 0.0) - radius;
+    // Scaling the edge by the screen space change in distance keeps it about a pixel wide at any size.
+    let edge = fwidth(distance) * max(in_frag.shape.w, 1.0);
+
+    if in_frag.shape.x < -3.0 {
+        let coverage = clamp(0.5 - distance / edge, 0.0, 1.0);
+        return vec4f(in_frag.color.rgb, in_frag.color.a * coverage);
+    } else if in_frag.uv.x < -1.0 {
         return in_frag.color;
     } else {
         return in_frag.color * textureSampleLevel(tex, sam, in_frag.uv, 0.0);
@@ -1,6 +1,8 @@
+use std::io::{Error as IoError, ErrorKind};
+
 use euclid::{Box2D, SideOffsets2D};
 use serde::Deserialize;
-use silica_asset::{AssetError, AssetSource, serde_util::string_or_struct};
+use silica_asset::{AssetError, AssetSource, image::ImageFormat, serde_util::string_or_struct};
 use silica_wgpu::{Context, Texture, TextureConfig, TextureRect, TextureSize, draw::*, wgpu::TextureFormat};
 
 use crate::{
@@ -72,10 +74,39 @@ impl ButtonThemeConfig {
     }
 }
 
+/// Formats the theme texture can be created with. Grayscale images are expanded when used for an RGBA
+/// format. The single and two channel formats keep grayscale art, such as distance fields, at one or two
+/// bytes per pixel, and are sampled as `(r, 0, 0, 1)` and `(r, g, 0, 1)`.
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+enum ThemeTextureFormat {
+    #[default]
+    Rgba8Unorm,
+    /// For art authored in sRGB, which is converted to linear when sampled.
+    Rgba8UnormSrgb,
+    /// For grayscale images.
+    R8Unorm,
+    /// For grayscale images with alpha.
+    Rg8Unorm,
+}
+
+impl ThemeTextureFormat {
+    fn texture_format(self) -> TextureFormat {
+        match self {
+            ThemeTextureFormat::Rgba8Unorm => TextureFormat::Rgba8Unorm,
+            ThemeTextureFormat::Rgba8UnormSrgb => TextureFormat::Rgba8UnormSrgb,
+            ThemeTextureFormat::R8Unorm => TextureFormat::R8Unorm,
+            ThemeTextureFormat::Rg8Unorm => TextureFormat::Rg8Unorm,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct StandardThemeConfig {
     font: String,
     texture: String,
+    #[serde(default)]
+    texture_format: ThemeTextureFormat,
     palette: StandardPalette,
     gutter: NineSliceConfig,
     button: ButtonThemeConfig,
@@ -142,14 +173,48 @@ impl StandardTheme {
         texture_config: &TextureConfig,
         asset_source: &mut S,
     ) -> Result<Self, AssetError> {
+        trace_scope!("theme load");
         let config: StandardThemeConfig = silica_asset::load_yaml(asset_source, "config.yaml")?;
         let font_system = FontSystem::with_font_asset(asset_source, &config.font)?;
-        let image = silica_asset::load_image(asset_source, &config.texture)?;
+        Self::load_config(context, texture_config, asset_source, config, font_system)
+    }
+    /// Loads the theme like [`StandardTheme::load`], but uses an existing font system instead of loading the
+    /// theme's font. Themes sharing a font system share its font database and caches.
+    pub fn load_with_font_system<S: AssetSource>(
+        context: &Context,
+        texture_config: &TextureConfig,
+        asset_source: &mut S,
+        font_system: FontSystem,
+    ) -> Result<Self, AssetError> {
+        trace_scope!("theme load");
+        let config: StandardThemeConfig = silica_asset::load_yaml(asset_source, "config.yaml")?;
+        Self::load_config(context, texture_config, asset_source, config, font_system)
+    }
+    fn load_config<S: AssetSource>(
+        context: &Context,
+        texture_config: &TextureConfig,
+        asset_source: &mut S,
+        config: StandardThemeConfig,
+        font_system: FontSystem,
+    ) -> Result<Self, AssetError> {
+        let mut image = silica_asset::load_image_unexpanded(asset_source, &config.texture)?;
+        let format = config.texture_format.texture_format();
+        let bytes_per_pixel = format.block_copy_size(None).unwrap_or_default();
+        if bytes_per_pixel == ImageFormat::Rgba.bytes_per_pixel() && image.format != ImageFormat::Rgba {
+            image = image.to_rgba();
+        }
+        if image.format.bytes_per_pixel() != bytes_per_pixel {
+            let error = IoError::new(
+                ErrorKind::InvalidData,
+                format!("{:?} image can't be used as a {:?} texture", image.format, format),
+            );
+            return Err(AssetError::with_path(asset_source, &config.texture, error));
+        }
         let texture = Texture::new_with_data(
             context,
             texture_config,
             TextureSize::new(image.width, image.height),
-            TextureFormat::Rgba8Unorm,
+            format,
             &image.data,
         );
         let texture_size = texture.size();
@@ -238,3 +303,67 @@ impl Theme for StandardTheme {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use png::{BitDepth, ColorType, Encoder};
+    use silica_asset::MemorySource;
+
+    use super::*;
+    use crate::tests::{test_context, theme_source};
+
+    /// The dark theme, with a `size` grayscale texture and `texture_format` added to its config.
+    fn gray_theme(texture_format: &str, size: u32) -> MemorySource {
+        let mut png = Vec::new();
+        let mut encoder = Encoder::new(&mut png, size, size);
+        encoder.set_color(ColorType::Grayscale);
+        encoder.set_depth(BitDepth::Eight);
+        let data = vec![128; (size * size) as usize];
+        encoder.write_header().unwrap().write_image_data(&data).unwrap();
+
+        let mut dark_theme = theme_source();
+        let config = silica_asset::load_string(&mut dark_theme, "config.yaml").unwrap();
+        let mut source = MemorySource::new();
+        source.insert("config.yaml", format!("{config}\ntexture_format: {texture_format}\n"));
+        source.insert(
+            "Rubik-Light.ttf",
+            silica_asset::load_bytes(&mut dark_theme, "Rubik-Light.ttf").unwrap(),
+        );
+        source.insert("theme.png", png);
+        source
+    }
+
+    #[test]
+    fn load_texture_format() {
+        let context = test_context();
+        let texture_config = TextureConfig::new(&context, silica_wgpu::wgpu::FilterMode::Nearest);
+        let load = |format| StandardTheme::load(&context, &texture_config, &mut gray_theme(format, 64));
+        let theme = load("r8-unorm").unwrap();
+        assert_eq!(theme.texture().format(), TextureFormat::R8Unorm);
+        let theme = load("rgba8-unorm-srgb").unwrap();
+        assert_eq!(theme.texture().format(), TextureFormat::Rgba8UnormSrgb);
+        assert!(load("rg8-unorm").is_err());
+    }
+
+    #[test]
+    fn themes_share_font_system() {
+        let context = test_context();
+        let texture_config = TextureConfig::new(&context, silica_wgpu::wgpu::FilterMode::Nearest);
+        let font_system = FontSystem::new(glyphon::fontdb::Database::new());
+        let load = || {
+            StandardTheme::load_with_font_system(&context, &texture_config, &mut theme_source(), font_system.clone())
+                .unwrap()
+        };
+        let first = load();
+        let second = load();
+        assert!(first.font_system().ptr_eq(second.font_system()));
+        let separate = StandardTheme::load(&context, &texture_config, &mut theme_source()).unwrap();
+        assert!(!first.font_system().ptr_eq(separate.font_system()));
+
+        // Fonts loaded through one theme are available to the other.
+        assert_eq!(second.font_system().borrow_mut().db().len(), 0);
+        let font = silica_asset::load_bytes(&mut theme_source(), "Rubik-Light.ttf").unwrap();
+        first.font_system().borrow_mut().db_mut().load_font_data(font);
+        assert_eq!(second.font_system().borrow_mut().db().len(), 1);
+    }
+}
@@ -125,6 +125,10 @@ impl ButtonBuilder {
         self.hotkey = Some(hotkey);
         self
     }
+    pub fn tooltip(mut self, tooltip: &str) -> Self {
+        self.node = self.node.tooltip(tooltip);
+        self
+    }
     pub fn label(mut self, gui: &mut Gui, label: &str) -> Self {
         let label = Button::create_label(gui, label);
         self.node = self.node.child(label);
@@ -135,29 +139,46 @@ impl ButtonBuilder {
         C: 'static,
         F: Fn(&mut C) + 'static,
     {
-        let mut button = Button::new(self.button_style, on_clicked);
-        button.set_enabled(self.enabled);
-        button.hotkey = self.hotkey;
-        self.node.build_widget(gui, button)
+        let button = Button::new(self.button_style, on_clicked);
+        self.build_button(gui, button)
     }
     pub fn build_toggle<C, F>(self, gui: &mut Gui, on_clicked: F) -> WidgetId<Button>
     where
         C: 'static,
         F: Fn(&mut C, bool) + 'static,
     {
-        let mut button = Button::new_toggle(self.button_style, self.toggled, on_clicked);
-        button.set_enabled(self.enabled);
-        button.hotkey = self.hotkey;
-        self.node.build_widget(gui, button)
+        let button = Button::new_toggle(self.button_style, self.toggled, on_clicked);
+        self.build_button(gui, button)
     }
     pub fn build_exclusive(self, gui: &mut Gui, group: &Rc<ExclusiveGroup>) -> WidgetId<Button> {
-        let mut button = Button::new_exclusive(self.button_style, self.toggled, group.clone());
-        button.set_enabled(self.enabled);
-        button.hotkey = self.hotkey;
-        let widget = self.node.build_widget(gui, button);
+        let button = Button::new_exclusive(self.button_style, self.toggled, group.clone());
+        let widget = self.build_button(gui, button);
         group.buttons.borrow_mut().push(widget);
         widget
     }
+    fn build_button(self, gui: &mut Gui, mut button: Button) -> WidgetId<Button> {
+        button.set_enabled(self.enabled);
+        button.hotkey = self.hotkey;
+        self.node.build_widget(gui, button)
+    }
+}
+/// Builds a normal button, with an event made by [`EventFn::new`].
+impl WidgetBuilder for ButtonBuilder {
+    type Widget = Button;
+    type Args = EventFn;
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        ButtonBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        ButtonBuilder::parent(self, parent.into())
+    }
+    fn build(self, gui: &mut Gui, args: Self::Args) -> WidgetId<Button> {
+        let button = Button::with_event(self.button_style, false, ButtonEvent::Normal(args));
+        self.build_button(gui, button)
+    }
 }
 impl Default for ButtonBuilder {
     fn default() -> Self {
@@ -182,14 +203,14 @@ pub struct Button {
 impl Button {
     const LABEL_FONT_SIZE: f32 = 20.0;
     const MIN_SIZE: Size = Size::new(128, 32);
-    fn default_style() -> Style {
+    pub(crate) fn default_style() -> Style {
         Style {
             min_size: Self::MIN_SIZE,
             cross_align: Align::Center,
             ..Default::default()
         }
     }
-    fn create_label(gui: &mut Gui, text: &str) -> WidgetId<Label> {
+    pub(crate) fn create_label(gui: &mut Gui, text: &str) -> WidgetId<Label> {
         LabelBuilder::new(text)
             .style(Style {
                 grow: true,
@@ -206,35 +227,30 @@ impl Button {
         C: 'static,
         F: Fn(&mut C) + 'static,
     {
-        Button {
-            button_style,
-            state: ButtonState::Normal,
-            hotkey: None,
-            toggled: false,
-            on_clicked: ButtonEvent::Normal(EventFn::new(on_clicked)),
-        }
+        Self::with_event(button_style, false, ButtonEvent::Normal(EventFn::new(on_clicked)))
     }
     pub fn new_toggle<C, F>(button_style: ButtonStyle, toggled: bool, on_clicked: F) -> Self
     where
         C: 'static,
         F: Fn(&mut C, bool) + 'static,
     {
-        Button {
+        Self::with_event(
             button_style,
-            state: ButtonState::Normal,
-            hotkey: None,
             toggled,
-            on_clicked: ButtonEvent::Toggle(EventFn::new_param(on_clicked)),
-        }
+            ButtonEvent::Toggle(EventFn::new_param(on_clicked)),
+        )
     }
     fn new_exclusive(button_style: ButtonStyle, toggled: bool, group: Rc<ExclusiveGroup>) -> Self {
         let index = group.buttons.borrow().len();
+        Self::with_event(button_style, toggled, ButtonEvent::Exclusive(group, index))
+    }
+    fn with_event(button_style: ButtonStyle, toggled: bool, on_clicked: ButtonEvent) -> Self {
         Button {
             button_style,
             state: ButtonState::Normal,
             hotkey: None,
             toggled,
-            on_clicked: ButtonEvent::Exclusive(group, index),
+            on_clicked,
         }
     }
     pub fn create<C, F>(gui: &mut Gui, label: &str, on_clicked: F) -> WidgetId<Self>
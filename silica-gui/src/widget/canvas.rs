@@ -0,0 +1,255 @@
+use euclid::Box2D;
+use silica_wgpu::{Texture, UvRect, draw::DrawQuad};
+
+use crate::{
+    render::{GuiRenderer, Quad},
+    *,
+};
+
+/// Drawing context passed to a [`Canvas`] callback. Coordinates are relative to the top left corner of
+/// the canvas, and drawing is clipped to it.
+pub struct CanvasPainter<'r, 'a, 'b> {
+    renderer: &'r mut GuiRenderer<'a, 'b>,
+    origin: Vector,
+    size: Size,
+}
+
+impl<'a, 'b> CanvasPainter<'_, 'a, 'b> {
+    pub fn size(&self) -> Size {
+        self.size
+    }
+    /// The underlying renderer, which draws in gui coordinates rather than canvas coordinates.
+    pub fn renderer(&mut self) -> &mut GuiRenderer<'a, 'b> {
+        self.renderer
+    }
+    pub fn fill_rect(&mut self, rect: Rect, color: Rgba) {
+        self.draw_quad(rect.to_box2d(), GuiRenderer::UV_WHITE, color);
+    }
+    pub fn draw_texture(&mut self, texture: &Texture, rect: Rect, uv: UvRect, color: Rgba) {
+        self.renderer.draw_quad(
+            texture,
+            Quad {
+                rect: rect.to_box2d().translate(self.origin),
+                uv,
+                color,
+            },
+        );
+    }
+}
+impl DrawQuad<i32, Pixel> for CanvasPainter<'_, '_, '_> {
+    fn draw_quad(&mut self, rect: Box2D<i32, Pixel>, uv: UvRect, color: Rgba) {
+        self.renderer.draw_theme_quad(Quad {
+            rect: rect.translate(self.origin),
+            uv,
+            color,
+        });
+    }
+}
+
+/// Pointer input over a [`Canvas`], relative to its top left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct CanvasInput {
+    pub pointer: Point,
+    pub button_pressed: bool,
+    pub clicked: bool,
+}
+
+type CanvasDrawFn = Box<dyn FnMut(&mut CanvasPainter)>;
+
+/// Widget that draws with a user callback. The callback is only called when the gui is redrawn, so use
+/// [`Gui::request_redraw`] after changing what it draws.
+pub struct Canvas {
+    on_draw: CanvasDrawFn,
+    on_input: Option<EventFn>,
+}
+
+impl Widget for Canvas {
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        if input.blocked || !(input.grabbed || area.content_rect.contains(input.pointer)) {
+            return InputAction::Pass;
+        }
+        let Some(on_input) = self.on_input.as_ref() else {
+            return InputAction::Pass;
+        };
+        let canvas_input = CanvasInput {
+            pointer: input.local_pointer(area),
+            button_pressed: input.button_pressed,
+            clicked: input.clicked,
+        };
+        executor.queue(on_input.clone(), Some(Box::new(canvas_input)));
+        if input.button_pressed {
+            InputAction::Grab
+        } else {
+            InputAction::Block
+        }
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        let rect = area.content_rect;
+        if rect.is_empty() {
+            return;
+        }
+        renderer.push_scroll_area(rect, Vector::zero());
+        let mut painter = CanvasPainter {
+            renderer,
+            origin: rect.origin.to_vector(),
+            size: rect.size,
+        };
+        (self.on_draw)(&mut painter);
+        renderer.pop_scroll_area();
+    }
+}
+
+#[must_use]
+pub struct CanvasBuilder {
+    node: NodeBuilder,
+    on_input: Option<EventFn>,
+}
+
+impl CanvasBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    /// Called with pointer input over the canvas, and while dragging after pressing on it.
+    pub fn on_input<C, F>(mut self, on_input: F) -> Self
+    where
+        C: 'static,
+        F: Fn(&mut C, CanvasInput) + 'static,
+    {
+        self.on_input = Some(EventFn::new_param(on_input));
+        self
+    }
+    pub fn build<F>(self, gui: &mut Gui, on_draw: F) -> WidgetId<Canvas>
+    where
+        F: FnMut(&mut CanvasPainter) + 'static,
+    {
+        let canvas = Canvas {
+            on_draw: Box::new(on_draw),
+            on_input: self.on_input,
+        };
+        self.node.build_widget(gui, canvas)
+    }
+}
+impl WidgetBuilder for CanvasBuilder {
+    type Widget = Canvas;
+    type Args = Box<dyn FnMut(&mut CanvasPainter)>;
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        CanvasBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        CanvasBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, args: Self::Args) -> WidgetId<Canvas> {
+        let canvas = Canvas {
+            on_draw: args,
+            on_input: self.on_input,
+        };
+        self.node.build_widget(gui, canvas)
+    }
+}
+impl Default for CanvasBuilder {
+    fn default() -> Self {
+        CanvasBuilder {
+            node: NodeBuilder::new().style(Style {
+                grow: true,
+                ..Default::default()
+            }),
+            on_input: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use silica_wgpu::SurfaceSize;
+
+    use super::*;
+    use crate::{
+        render::LoadOp,
+        tests::{TestTarget, click},
+    };
+
+    #[test]
+    fn builder_wires_both_handlers() {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let draws = Rc::new(Cell::new(0));
+        let inputs = Rc::new(RefCell::new(Vec::new()));
+        let canvas = CanvasBuilder::new()
+            .on_input({
+                let inputs = inputs.clone();
+                move |_: &mut Gui, input: CanvasInput| inputs.borrow_mut().push(input)
+            })
+            .build(&mut gui, {
+                let draws = draws.clone();
+                move |_painter| draws.set(draws.get() + 1)
+            });
+        gui.set_root(canvas);
+
+        target.render(&mut gui, LoadOp::Clear);
+        assert_eq!(draws.get(), 1);
+        assert!(inputs.borrow().is_empty());
+
+        click(&mut gui, Point::new(10, 20));
+        let inputs = inputs.borrow();
+        assert!(inputs.iter().all(|input| input.pointer == Point::new(10, 20)));
+        assert!(inputs.iter().any(|input| input.clicked));
+        assert_eq!(draws.get(), 1);
+    }
+
+    #[test]
+    fn painter_is_offset_and_clipped() {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let size = Rc::new(Cell::new(Size::zero()));
+        let canvas = CanvasBuilder::new()
+            .modify_style(|style| {
+                style.grow = false;
+                style.min_size = Size::new(30, 20);
+            })
+            .build(&mut gui, {
+                let size = size.clone();
+                move |painter| {
+                    size.set(painter.size());
+                    painter.fill_rect(Rect::new(Point::origin(), Size::new(5, 5)), Rgba::RED);
+                    // Reaches past the canvas, so it's clipped to the canvas.
+                    painter.fill_rect(Rect::new(Point::new(25, 15), Size::new(20, 20)), Rgba::GREEN);
+                }
+            });
+        let root = NodeBuilder::new()
+            .modify_style(|style| {
+                style.padding = SideOffsets::new(10, 0, 0, 20);
+                style.cross_align = Align::Start;
+            })
+            .child(canvas)
+            .build(&mut gui);
+        gui.set_root(root);
+        target.render(&mut gui, LoadOp::Clear);
+
+        assert_eq!(size.get(), Size::new(30, 20));
+        let red = [255, 0, 0, 255];
+        let green = [0, 255, 0, 255];
+        assert_eq!(target.pixel(Point::new(20, 10)), red);
+        assert_eq!(target.pixel(Point::new(24, 14)), red);
+        assert_ne!(target.pixel(Point::new(19, 10)), red);
+        assert_ne!(target.pixel(Point::new(20, 9)), red);
+        assert_eq!(target.pixel(Point::new(49, 29)), green);
+        assert_ne!(target.pixel(Point::new(50, 29)), green);
+        assert_ne!(target.pixel(Point::new(49, 30)), green);
+    }
+}
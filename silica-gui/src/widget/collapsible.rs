@@ -0,0 +1,252 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{render::GuiRenderer, *};
+
+struct CollapsibleState {
+    expanded: Cell<bool>,
+    content: NodeId,
+    indicator: WidgetId<Label>,
+    button_style: ButtonStyle,
+}
+
+impl CollapsibleState {
+    const EXPANDED_INDICATOR: &str = "▼";
+    const COLLAPSED_INDICATOR: &str = "▶";
+    fn indicator_text(expanded: bool) -> &'static str {
+        if expanded {
+            Self::EXPANDED_INDICATOR
+        } else {
+            Self::COLLAPSED_INDICATOR
+        }
+    }
+    fn set_expanded(&self, gui: &mut Gui, expanded: bool) {
+        self.expanded.set(expanded);
+        self.indicator.set_text(gui, Self::indicator_text(expanded));
+        gui.modify_style(self.content, |style| style.hidden = !expanded);
+        gui.request_redraw();
+    }
+}
+
+struct CollapsibleHeader {
+    state: Rc<CollapsibleState>,
+    button_state: ButtonState,
+}
+
+impl Widget for CollapsibleHeader {
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        let state_input = self.button_state.handle_input(input, None, area.content_rect);
+        if state_input.changed {
+            executor.request_redraw();
+        }
+        if state_input.clicked {
+            let toggle = EventFn::new_param(|gui: &mut Gui, state: Rc<CollapsibleState>| {
+                state.set_expanded(gui, !state.expanded.get());
+            });
+            executor.queue(toggle, Some(Box::new(self.state.clone())));
+        }
+        state_input.action
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        renderer.theme().draw_button(
+            renderer,
+            area.content_rect,
+            self.state.button_style,
+            self.state.expanded.get(),
+            self.button_state,
+        );
+    }
+}
+
+/// A header that shows or hides a content node when clicked. The content is hidden with
+/// [`Style::hidden`], so it takes up no space while collapsed.
+pub struct Collapsible {
+    state: Rc<CollapsibleState>,
+}
+
+impl Collapsible {
+    pub fn expanded(&self) -> bool {
+        self.state.expanded.get()
+    }
+    pub fn content(&self) -> NodeId {
+        self.state.content
+    }
+}
+impl Widget for Collapsible {
+    fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+}
+impl WidgetId<Collapsible> {
+    pub fn expanded(&self, gui: &Gui) -> bool {
+        gui.get_widget(*self)
+            .map(|collapsible| collapsible.expanded())
+            .unwrap_or(false)
+    }
+    pub fn set_expanded(&self, gui: &mut Gui, expanded: bool) {
+        if let Some(collapsible) = gui.get_widget(*self) {
+            let state = collapsible.state.clone();
+            state.set_expanded(gui, expanded);
+        }
+    }
+}
+
+#[must_use]
+pub struct CollapsibleBuilder {
+    node: NodeBuilder,
+    content: NodeBuilder,
+    title: String,
+    expanded: bool,
+    button_style: ButtonStyle,
+}
+
+impl CollapsibleBuilder {
+    const HEADER_HEIGHT: i32 = 32;
+    const INDICATOR_WIDTH: i32 = 24;
+    pub fn new(title: &str) -> Self {
+        CollapsibleBuilder {
+            node: NodeBuilder::new().style(Style {
+                direction: Direction::Column,
+                ..Default::default()
+            }),
+            content: NodeBuilder::new().style(Style {
+                direction: Direction::Column,
+                ..Default::default()
+            }),
+            title: title.to_string(),
+            expanded: true,
+            button_style: ButtonStyle::Flat,
+        }
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    /// Modifies the style of the node holding the content, which is shown below the header.
+    pub fn modify_content_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.content = self.content.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    pub fn child(mut self, child: impl Into<NodeId>) -> Self {
+        self.content = self.content.child(child);
+        self
+    }
+    pub fn children(mut self, iter: impl IntoIterator<Item = NodeId>) -> Self {
+        self.content = self.content.children(iter);
+        self
+    }
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+    /// Style of the header button. Usually [`ButtonStyle::Flat`] or [`ButtonStyle::Tab`].
+    pub fn button_style(mut self, button_style: ButtonStyle) -> Self {
+        self.button_style = button_style;
+        self
+    }
+    pub fn build(self, gui: &mut Gui) -> WidgetId<Collapsible> {
+        let expanded = self.expanded;
+        let content = self.content.modify_style(|style| style.hidden = !expanded).build(gui);
+        let indicator = LabelBuilder::new(CollapsibleState::indicator_text(expanded))
+            .style(Style {
+                min_size: Size::new(Self::INDICATOR_WIDTH, 0),
+                ..Default::default()
+            })
+            .align(TextAlign::Center)
+            .build(gui);
+        let title = LabelBuilder::new(&self.title)
+            .style(Style {
+                grow: true,
+                margin: SideOffsets::new(0, 4, 0, 0),
+                ..Default::default()
+            })
+            .build(gui);
+        let state = Rc::new(CollapsibleState {
+            expanded: Cell::new(expanded),
+            content,
+            indicator,
+            button_style: self.button_style,
+        });
+        let header = NodeBuilder::new()
+            .style(Style {
+                min_size: Size::new(0, Self::HEADER_HEIGHT),
+                cross_align: Align::Center,
+                ..Default::default()
+            })
+            .child(indicator)
+            .child(title)
+            .build_widget(
+                gui,
+                CollapsibleHeader {
+                    state: state.clone(),
+                    button_state: ButtonState::Normal,
+                },
+            );
+        self.node
+            .child(header)
+            .child(content)
+            .build_widget(gui, Collapsible { state })
+    }
+}
+impl WidgetBuilder for CollapsibleBuilder {
+    type Widget = Collapsible;
+    type Args = ();
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        CollapsibleBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        CollapsibleBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, _args: Self::Args) -> WidgetId<Collapsible> {
+        CollapsibleBuilder::build(self, gui)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{click, fixed_node, test_gui};
+
+    #[test]
+    fn toggling_collapses_content() {
+        let mut gui = test_gui(Size::new(200, 200));
+        let body = fixed_node(&mut gui, Size::new(100, 50));
+        let collapsible = CollapsibleBuilder::new("Section").child(body).build(&mut gui);
+        let root = NodeBuilder::new()
+            .style(Style {
+                direction: Direction::Column,
+                ..Default::default()
+            })
+            .child(collapsible)
+            .build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        let content = gui.get_widget(collapsible).unwrap().content();
+        assert!(collapsible.expanded(&gui));
+        assert!(!gui.nodes[content].area.hidden);
+        let expanded_height = gui.screen_rect(collapsible).height();
+
+        let header = gui.screen_rect(gui.children[collapsible.into()][0]).center();
+        click(&mut gui, header);
+        gui.layout();
+        assert!(!collapsible.expanded(&gui));
+        assert!(gui.nodes[content].area.hidden);
+        assert_eq!(gui.screen_rect(collapsible).height(), CollapsibleBuilder::HEADER_HEIGHT);
+
+        click(&mut gui, header);
+        gui.layout();
+        assert!(collapsible.expanded(&gui));
+        assert!(!gui.nodes[content].area.hidden);
+        assert_eq!(gui.screen_rect(collapsible).height(), expanded_height);
+    }
+}
@@ -0,0 +1,266 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{render::GuiRenderer, *};
+
+struct ContextMenuState {
+    events: Vec<EventFn>,
+    highlighted: Cell<Option<usize>>,
+    popup: Cell<NodeId>,
+}
+
+impl ContextMenuState {
+    fn close(gui: &mut Gui, state: Rc<ContextMenuState>) {
+        gui.close_popup(state.popup.get());
+    }
+    fn select(state: &Rc<ContextMenuState>, executor: &mut EventExecutor, index: usize) {
+        executor.queue(
+            EventFn::new_param(ContextMenuState::close),
+            Some(Box::new(state.clone())),
+        );
+        executor.queue(state.events[index].clone(), None);
+    }
+}
+
+struct ContextMenuItem {
+    state: Rc<ContextMenuState>,
+    index: usize,
+    button_state: ButtonState,
+}
+
+impl Widget for ContextMenuItem {
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        let state_input = self.button_state.handle_input(input, None, area.content_rect);
+        if state_input.changed {
+            executor.request_redraw();
+        }
+        if state_input.clicked {
+            ContextMenuState::select(&self.state, executor, self.index);
+        }
+        state_input.action
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        let state = if self.button_state == ButtonState::Normal && self.state.highlighted.get() == Some(self.index) {
+            ButtonState::Hover
+        } else {
+            self.button_state
+        };
+        renderer
+            .theme()
+            .draw_button(renderer, area.content_rect, ButtonStyle::Flat, false, state);
+    }
+}
+
+/// A vertical list of flat buttons shown in a popup, usually opened at [`Gui::pointer`] on right-click.
+/// Picking an item closes the menu and runs the item's event, and clicking outside of it closes it.
+pub struct ContextMenu {
+    state: Rc<ContextMenuState>,
+}
+
+impl Widget for ContextMenu {
+    fn focusable(&self) -> bool {
+        true
+    }
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        if input.focused {
+            let len = self.state.events.len();
+            match list_nav_input(input.nav_key, self.state.highlighted.get(), len) {
+                Some(ListNav::Highlight(index)) => self.state.highlighted.set(Some(index)),
+                Some(ListNav::Activate(index)) => ContextMenuState::select(&self.state, executor, index),
+                Some(ListNav::Cancel) => executor.queue(
+                    EventFn::new_param(ContextMenuState::close),
+                    Some(Box::new(self.state.clone())),
+                ),
+                None => return InputAction::Pass,
+            }
+            executor.request_redraw();
+            return InputAction::Block;
+        }
+        let (action, close) = popup_pointer_input(input, area);
+        if close {
+            executor.queue(
+                EventFn::new_param(ContextMenuState::close),
+                Some(Box::new(self.state.clone())),
+            );
+        }
+        action
+    }
+    fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+}
+impl WidgetId<ContextMenu> {
+    /// Opens the menu with its top left corner at `position`, moved if needed to stay inside the gui.
+    pub fn open(&self, gui: &mut Gui, position: Point) {
+        if let Some(menu) = gui.get_widget(*self) {
+            menu.state.highlighted.set(None);
+            gui.open_popup(*self, position);
+            gui.set_focus(Some((*self).into()));
+        }
+    }
+    pub fn close(&self, gui: &mut Gui) {
+        gui.close_popup(*self);
+    }
+    pub fn is_open(&self, gui: &Gui) -> bool {
+        gui.is_popup_open(*self)
+    }
+}
+
+#[must_use]
+pub struct ContextMenuBuilder {
+    node: NodeBuilder,
+    items: Vec<(WidgetId<Label>, EventFn)>,
+}
+
+impl ContextMenuBuilder {
+    const ITEM_MIN_WIDTH: i32 = 128;
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn item(mut self, gui: &mut Gui, label: &str, event: EventFn) -> Self {
+        let label = LabelBuilder::new(label)
+            .style(Style {
+                grow: true,
+                margin: SideOffsets::new(2, 8, 2, 8),
+                ..Default::default()
+            })
+            .build(gui);
+        self.items.push((label, event));
+        self
+    }
+    pub fn items<'a>(mut self, gui: &mut Gui, items: impl IntoIterator<Item = (&'a str, EventFn)>) -> Self {
+        for (label, event) in items {
+            self = self.item(gui, label, event);
+        }
+        self
+    }
+    /// Creates the menu without opening it. It stays around until deleted, so it can be opened again.
+    pub fn build(self, gui: &mut Gui) -> WidgetId<ContextMenu> {
+        let (labels, events): (Vec<_>, Vec<_>) = self.items.into_iter().unzip();
+        let state = Rc::new(ContextMenuState {
+            events,
+            highlighted: Cell::new(None),
+            popup: Cell::new(NodeId::default()),
+        });
+        let items = labels.into_iter().enumerate().map(|(index, label)| {
+            let item = ContextMenuItem {
+                state: state.clone(),
+                index,
+                button_state: ButtonState::Normal,
+            };
+            NodeBuilder::new()
+                .style(Style {
+                    min_size: Size::new(Self::ITEM_MIN_WIDTH, 0),
+                    ..Default::default()
+                })
+                .child(label)
+                .build_widget(gui, item)
+                .into()
+        });
+        let items = items.collect::<Vec<NodeId>>();
+        let menu = self
+            .node
+            .children(items)
+            .build_widget(gui, ContextMenu { state: state.clone() });
+        state.popup.set(menu.into());
+        menu
+    }
+}
+impl Default for ContextMenuBuilder {
+    fn default() -> Self {
+        ContextMenuBuilder {
+            node: NodeBuilder::new().style(Style {
+                direction: Direction::Column,
+                background_color: Some(Color::Background),
+                border_color: Some(Color::Border),
+                border: SideOffsets::new_all_same(1),
+                ..Default::default()
+            }),
+            items: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{TestEvent, TestKey, click, fixed_node, send, test_gui};
+
+    /// Builds a closed menu with three items, each adding its index to `picked` when activated.
+    fn test_menu(gui: &mut Gui, picked: &Rc<Cell<Vec<usize>>>) -> WidgetId<ContextMenu> {
+        let root = fixed_node(gui, Size::new(400, 300));
+        gui.set_root(root);
+        let items = (0..3).map(|index| {
+            let picked = picked.clone();
+            let event = EventFn::new(move |_: &mut Gui| {
+                let mut indices = picked.take();
+                indices.push(index);
+                picked.set(indices);
+            });
+            (["Cut", "Copy", "Paste"][index], event)
+        });
+        ContextMenuBuilder::new()
+            .items(gui, items.collect::<Vec<_>>())
+            .build(gui)
+    }
+
+    fn nav(gui: &mut Gui, nav_key: NavKey) {
+        send(gui, TestEvent::Keyboard(TestKey(None, Some(nav_key))));
+    }
+
+    #[test]
+    fn keyboard_navigation_activates_item() {
+        let mut gui = test_gui(Size::new(400, 300));
+        let picked = Rc::new(Cell::new(Vec::new()));
+        let menu = test_menu(&mut gui, &picked);
+        menu.open(&mut gui, Point::new(10, 10));
+        gui.layout();
+
+        // Down from no highlight goes to the first item, and Up wraps around to the last.
+        nav(&mut gui, NavKey::Down);
+        nav(&mut gui, NavKey::Down);
+        assert_eq!(gui.get_widget(menu).unwrap().state.highlighted.get(), Some(1));
+        nav(&mut gui, NavKey::Up);
+        nav(&mut gui, NavKey::Up);
+        assert_eq!(gui.get_widget(menu).unwrap().state.highlighted.get(), Some(2));
+        nav(&mut gui, NavKey::Enter);
+        assert_eq!(picked.take(), [2]);
+        assert!(!menu.is_open(&gui));
+
+        // Escape closes the menu without activating anything.
+        menu.open(&mut gui, Point::new(10, 10));
+        gui.layout();
+        nav(&mut gui, NavKey::Down);
+        nav(&mut gui, NavKey::Escape);
+        assert!(!menu.is_open(&gui));
+        assert_eq!(picked.take(), []);
+    }
+
+    #[test]
+    fn clicking_item_runs_event_and_closes() {
+        let mut gui = test_gui(Size::new(400, 300));
+        let picked = Rc::new(Cell::new(Vec::new()));
+        let menu = test_menu(&mut gui, &picked);
+        menu.open(&mut gui, Point::new(10, 10));
+        gui.layout();
+        let items = gui.children[menu.into()].clone();
+        assert_eq!(items.len(), 3);
+        // The item labels get their full height inside the popup.
+        assert!(items.iter().all(|&item| gui.screen_rect(item).height() > 8));
+
+        let item = gui.screen_rect(items[1]).center();
+        click(&mut gui, item);
+        assert_eq!(picked.take(), [1]);
+        assert!(!menu.is_open(&gui));
+
+        // Clicking outside closes the menu without running anything.
+        menu.open(&mut gui, Point::new(10, 10));
+        click(&mut gui, Point::new(390, 290));
+        assert!(!menu.is_open(&gui));
+        assert_eq!(picked.take(), []);
+    }
+}
@@ -0,0 +1,317 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{render::GuiRenderer, *};
+
+struct DropdownState {
+    options: Vec<String>,
+    selected: Cell<usize>,
+    highlighted: Cell<Option<usize>>,
+    button: Cell<NodeId>,
+    label: WidgetId<Label>,
+    popup: Cell<NodeId>,
+    on_selected: EventFn,
+}
+
+impl DropdownState {
+    fn open(gui: &mut Gui, state: Rc<DropdownState>) {
+        let rect = gui.screen_rect(state.button.get());
+        let popup = state.popup.get();
+        gui.modify_style(popup, |style| style.min_size.width = rect.width());
+        state.highlighted.set(Some(state.selected.get()));
+        gui.open_popup(popup, Point::new(rect.min_x(), rect.max_y()));
+        gui.set_focus(Some(popup));
+    }
+    fn close(gui: &mut Gui, state: Rc<DropdownState>) {
+        gui.close_popup(state.popup.get());
+    }
+    fn set_selected(&self, gui: &mut Gui, index: usize) {
+        if let Some(option) = self.options.get(index) {
+            self.selected.set(index);
+            self.label.set_text(gui, option);
+        }
+    }
+    fn select(state: &Rc<DropdownState>, executor: &mut EventExecutor, index: usize) {
+        let select = EventFn::new_param(|gui: &mut Gui, (state, index): (Rc<DropdownState>, usize)| {
+            state.set_selected(gui, index);
+            gui.close_popup(state.popup.get());
+        });
+        executor.queue(select, Some(Box::new((state.clone(), index))));
+        executor.queue(state.on_selected.clone(), Some(Box::new(index)));
+    }
+}
+
+pub struct Dropdown {
+    state: Rc<DropdownState>,
+    button_state: ButtonState,
+}
+
+impl Dropdown {
+    pub fn selected(&self) -> usize {
+        self.state.selected.get()
+    }
+    pub fn options(&self) -> &[String] {
+        &self.state.options
+    }
+}
+impl Widget for Dropdown {
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        let state_input = self.button_state.handle_input(input, None, area.content_rect);
+        if state_input.changed {
+            executor.request_redraw();
+        }
+        if state_input.clicked {
+            executor.queue(
+                EventFn::new_param(DropdownState::open),
+                Some(Box::new(self.state.clone())),
+            );
+        }
+        state_input.action
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        renderer.theme().draw_button(
+            renderer,
+            area.content_rect,
+            ButtonStyle::Normal,
+            false,
+            self.button_state,
+        );
+    }
+}
+impl WidgetId<Dropdown> {
+    pub fn selected(&self, gui: &Gui) -> usize {
+        gui.get_widget(*self).map(|dropdown| dropdown.selected()).unwrap_or(0)
+    }
+    pub fn set_selected(&self, gui: &mut Gui, index: usize) {
+        if let Some(dropdown) = gui.get_widget(*self) {
+            let state = dropdown.state.clone();
+            state.set_selected(gui, index);
+        }
+    }
+}
+
+struct DropdownItem {
+    state: Rc<DropdownState>,
+    index: usize,
+    button_state: ButtonState,
+}
+
+impl Widget for DropdownItem {
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        let state_input = self.button_state.handle_input(input, None, area.content_rect);
+        if state_input.changed {
+            executor.request_redraw();
+        }
+        if state_input.clicked {
+            DropdownState::select(&self.state, executor, self.index);
+        }
+        state_input.action
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        let state = if self.state.selected.get() == self.index {
+            ButtonState::Press
+        } else if self.button_state == ButtonState::Normal && self.state.highlighted.get() == Some(self.index) {
+            ButtonState::Hover
+        } else {
+            self.button_state
+        };
+        renderer
+            .theme()
+            .draw_button(renderer, area.content_rect, ButtonStyle::Flat, false, state);
+    }
+}
+
+/// The list of options shown in a popup while the dropdown is open. Clicking outside of it closes it.
+struct DropdownPopup {
+    state: Rc<DropdownState>,
+}
+
+impl Widget for DropdownPopup {
+    fn focusable(&self) -> bool {
+        true
+    }
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        if input.focused {
+            let len = self.state.options.len();
+            match list_nav_input(input.nav_key, self.state.highlighted.get(), len) {
+                Some(ListNav::Highlight(index)) => self.state.highlighted.set(Some(index)),
+                Some(ListNav::Activate(index)) => DropdownState::select(&self.state, executor, index),
+                Some(ListNav::Cancel) => executor.queue(
+                    EventFn::new_param(DropdownState::close),
+                    Some(Box::new(self.state.clone())),
+                ),
+                None => return InputAction::Pass,
+            }
+            executor.request_redraw();
+            return InputAction::Block;
+        }
+        let (action, close) = popup_pointer_input(input, area);
+        if close {
+            executor.queue(
+                EventFn::new_param(DropdownState::close),
+                Some(Box::new(self.state.clone())),
+            );
+        }
+        action
+    }
+    fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+}
+
+#[must_use]
+pub struct DropdownBuilder {
+    node: NodeBuilder,
+    selected: usize,
+    options: Vec<(String, WidgetId<Label>)>,
+}
+
+impl DropdownBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    pub fn selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
+    pub fn option(mut self, gui: &mut Gui, option: &str) -> Self {
+        let label = LabelBuilder::new(option)
+            .style(Style {
+                grow: true,
+                margin: SideOffsets::new(2, 4, 2, 4),
+                ..Default::default()
+            })
+            .build(gui);
+        self.options.push((option.to_string(), label));
+        self
+    }
+    pub fn options(mut self, gui: &mut Gui, options: &[&str]) -> Self {
+        for option in options {
+            self = self.option(gui, option);
+        }
+        self
+    }
+    pub fn build<C, F>(self, gui: &mut Gui, on_selected: F) -> WidgetId<Dropdown>
+    where
+        C: 'static,
+        F: Fn(&mut C, usize) + 'static,
+    {
+        WidgetBuilder::build(self, gui, EventFn::new_param(on_selected))
+    }
+}
+/// Builds with an event made by [`EventFn::new_param`], with the selected index as its parameter.
+impl WidgetBuilder for DropdownBuilder {
+    type Widget = Dropdown;
+    type Args = EventFn;
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        DropdownBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        DropdownBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, on_selected: Self::Args) -> WidgetId<Dropdown> {
+        let selected = self.selected.min(self.options.len().saturating_sub(1));
+        let text = self.options.get(selected).map(|(text, _)| text.as_str()).unwrap_or("");
+        let label = Button::create_label(gui, text);
+        let (options, option_labels): (Vec<_>, Vec<_>) = self.options.into_iter().unzip();
+        let state = Rc::new(DropdownState {
+            options,
+            selected: Cell::new(selected),
+            highlighted: Cell::new(None),
+            button: Cell::new(NodeId::default()),
+            label,
+            popup: Cell::new(NodeId::default()),
+            on_selected,
+        });
+        let items = option_labels.into_iter().enumerate().map(|(index, option_label)| {
+            let item = DropdownItem {
+                state: state.clone(),
+                index,
+                button_state: ButtonState::Normal,
+            };
+            NodeBuilder::new().child(option_label).build_widget(gui, item).into()
+        });
+        let items = items.collect::<Vec<NodeId>>();
+        let popup = NodeBuilder::new()
+            .style(Style {
+                direction: Direction::Column,
+                background_color: Some(Color::Background),
+                border_color: Some(Color::Border),
+                border: SideOffsets::new_all_same(1),
+                ..Default::default()
+            })
+            .children(items)
+            .build_widget(gui, DropdownPopup { state: state.clone() });
+        state.popup.set(popup.into());
+        let dropdown = Dropdown {
+            state: state.clone(),
+            button_state: ButtonState::Normal,
+        };
+        let dropdown = self.node.child(label).build_widget(gui, dropdown);
+        state.button.set(dropdown.into());
+        // The popup isn't in the tree, so it has to be deleted along with the dropdown.
+        gui.add_owned(dropdown, popup);
+        dropdown
+    }
+}
+impl Default for DropdownBuilder {
+    fn default() -> Self {
+        DropdownBuilder {
+            node: NodeBuilder::new().style(Button::default_style()),
+            selected: 0,
+            options: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{click, test_gui};
+
+    #[test]
+    fn clicking_option_changes_index() {
+        let mut gui = test_gui(Size::new(400, 300));
+        let picked = Rc::new(Cell::new(None));
+        let dropdown = DropdownBuilder::new()
+            .options(&mut gui, &["Low", "Medium", "High"])
+            .build(&mut gui, {
+                let picked = picked.clone();
+                move |_: &mut Gui, index| picked.set(Some(index))
+            });
+        let root = NodeBuilder::new().child(dropdown).build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        let popup = gui.get_widget(dropdown).unwrap().state.popup.get();
+        assert_eq!(dropdown.selected(&gui), 0);
+        let button = gui.screen_rect(dropdown).center();
+
+        click(&mut gui, button);
+        assert!(gui.is_popup_open(popup));
+        gui.layout();
+        let item = gui.screen_rect(gui.children[popup][2]).center();
+        click(&mut gui, item);
+        assert_eq!(dropdown.selected(&gui), 2);
+        assert_eq!(picked.take(), Some(2));
+        assert!(!gui.is_popup_open(popup));
+
+        // Clicking outside closes the list without selecting.
+        click(&mut gui, button);
+        assert!(gui.is_popup_open(popup));
+        click(&mut gui, Point::new(390, 290));
+        assert!(!gui.is_popup_open(popup));
+        assert_eq!(dropdown.selected(&gui), 2);
+        assert_eq!(picked.take(), None);
+    }
+}
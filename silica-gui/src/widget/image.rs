@@ -0,0 +1,201 @@
+use silica_wgpu::{Texture, Uv, UvRect};
+
+use crate::{
+    render::{GuiRenderer, Quad},
+    *,
+};
+
+/// Displays part of a texture, stretched to the node's content rect.
+pub struct Image {
+    texture: Texture,
+    uv: UvRect,
+    tint: Rgba,
+}
+
+impl Image {
+    pub fn new(texture: Texture) -> Self {
+        Image {
+            texture,
+            uv: Uv::FULL,
+            tint: Rgba::WHITE,
+        }
+    }
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+    pub fn set_texture(&mut self, texture: Texture) {
+        self.texture = texture;
+    }
+    pub fn uv(&self) -> UvRect {
+        self.uv
+    }
+    pub fn set_uv(&mut self, uv: UvRect) {
+        self.uv = uv;
+    }
+    pub fn tint(&self) -> Rgba {
+        self.tint
+    }
+    pub fn set_tint(&mut self, tint: Rgba) {
+        self.tint = tint;
+    }
+    /// Size in pixels of the displayed part of the texture.
+    pub fn image_size(&self) -> Size {
+        let uv_size = self.uv.size().abs();
+        Size::new(
+            (uv_size.width * self.texture.width() as f32).round() as i32,
+            (uv_size.height * self.texture.height() as f32).round() as i32,
+        )
+    }
+}
+impl Widget for Image {
+    fn measure(&mut self, _available_space: Size) -> Size {
+        self.image_size()
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        renderer.draw_quad(
+            &self.texture,
+            Quad {
+                rect: area.content_rect.to_box2d(),
+                uv: self.uv,
+                color: self.tint,
+            },
+        );
+    }
+}
+impl WidgetId<Image> {
+    pub fn set_texture(&self, gui: &mut Gui, texture: Texture) {
+        if let Some(image) = gui.get_widget_mut(*self) {
+            image.set_texture(texture);
+            gui.request_node_layout(*self);
+        }
+    }
+    pub fn set_uv(&self, gui: &mut Gui, uv: UvRect) {
+        if let Some(image) = gui.get_widget_mut(*self) {
+            image.set_uv(uv);
+            gui.request_node_layout(*self);
+        }
+    }
+    pub fn set_tint(&self, gui: &mut Gui, tint: Rgba) {
+        if let Some(image) = gui.get_widget_mut(*self) {
+            image.set_tint(tint);
+        }
+    }
+}
+
+#[must_use]
+pub struct ImageBuilder {
+    node: NodeBuilder,
+    image: Image,
+}
+
+impl ImageBuilder {
+    pub fn new(texture: Texture) -> Self {
+        ImageBuilder {
+            node: NodeBuilder::new(),
+            image: Image::new(texture),
+        }
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    pub fn uv(mut self, uv: UvRect) -> Self {
+        self.image.uv = uv;
+        self
+    }
+    pub fn tint(mut self, tint: Rgba) -> Self {
+        self.image.tint = tint;
+        self
+    }
+    pub fn build(self, gui: &mut Gui) -> WidgetId<Image> {
+        self.node.build_widget(gui, self.image)
+    }
+}
+impl WidgetBuilder for ImageBuilder {
+    type Widget = Image;
+    type Args = ();
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        ImageBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        ImageBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, _args: Self::Args) -> WidgetId<Image> {
+        ImageBuilder::build(self, gui)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use silica_wgpu::{SurfaceSize, TextureSize};
+
+    use super::*;
+    use crate::{render::LoadOp, tests::TestTarget};
+
+    #[test]
+    fn tint_redraws_without_layout() {
+        let mut target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let texture = Texture::solid(
+            &target.context,
+            &target.texture_config,
+            Rgba::WHITE,
+            TextureSize::new(8, 8),
+        );
+        let image = ImageBuilder::new(texture).build(&mut gui);
+        let root = NodeBuilder::new().child(image).build(&mut gui);
+        gui.set_root(root);
+        target.render(&mut gui, LoadOp::Clear);
+        assert!(!gui.needs_redraw());
+
+        image.set_tint(&mut gui, Rgba::RED);
+        assert!(gui.needs_redraw());
+        assert!(!gui.needs_layout());
+        assert_eq!(gui.damage_rect(), Some(gui.nodes[image.into()].area.background_rect));
+    }
+
+    #[test]
+    fn measure_returns_texture_size() {
+        let target = TestTarget::new(SurfaceSize::new(64, 64));
+        let mut gui = target.gui();
+        let texture = || {
+            Texture::solid(
+                &target.context,
+                &target.texture_config,
+                Rgba::WHITE,
+                TextureSize::new(16, 8),
+            )
+        };
+        let full = ImageBuilder::new(texture()).build(&mut gui);
+        let half = ImageBuilder::new(texture())
+            .uv(UvRect::new(euclid::point2(0.0, 0.0), euclid::point2(0.5, 1.0)))
+            .build(&mut gui);
+        let limited = ImageBuilder::new(texture())
+            .modify_style(|style| style.max_size = Size::new(10, 4))
+            .build(&mut gui);
+        let root = NodeBuilder::new()
+            .modify_style(|style| {
+                style.direction = Direction::Column;
+                style.cross_align = Align::Start;
+            })
+            .children([full.into(), half.into(), limited.into()])
+            .build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+
+        let size = |image: WidgetId<Image>| gui.nodes[image.into()].area.content_rect.size;
+        assert_eq!(size(full), Size::new(16, 8));
+        assert_eq!(size(half), Size::new(8, 8));
+        assert_eq!(size(limited), Size::new(10, 4));
+    }
+}
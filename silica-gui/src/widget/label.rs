@@ -17,6 +17,32 @@ impl BufferExt for Buffer {
     }
 }
 
+/// Scrolls text that's wider than the label back and forth, instead of wrapping it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marquee {
+    /// Scroll speed in pixels per second.
+    pub speed: f32,
+    /// Seconds to wait at each end before scrolling.
+    pub pause: f32,
+}
+
+impl Marquee {
+    fn cycle_time(&self, overflow: f32) -> f32 {
+        self.pause * 2.0 + overflow / self.speed
+    }
+    fn offset(&self, time: f32, overflow: f32) -> f32 {
+        ((time - self.pause) * self.speed).clamp(0.0, overflow)
+    }
+}
+impl Default for Marquee {
+    fn default() -> Self {
+        Marquee {
+            speed: 40.0,
+            pause: 1.0,
+        }
+    }
+}
+
 #[must_use]
 pub struct LabelBuilder<'a> {
     node: NodeBuilder,
@@ -24,6 +50,7 @@ pub struct LabelBuilder<'a> {
     line_height: f32,
     attrs: Attrs<'static>,
     align: Option<TextAlign>,
+    marquee: Option<Marquee>,
     text: &'a str,
 }
 
@@ -35,6 +62,7 @@ impl<'a> LabelBuilder<'a> {
             line_height: 1.0,
             attrs: Attrs::new(),
             align: None,
+            marquee: None,
             text,
         }
     }
@@ -85,14 +113,20 @@ impl<'a> LabelBuilder<'a> {
         self.align = Some(align);
         self
     }
+    pub fn marquee(mut self, marquee: Marquee) -> Self {
+        self.marquee = Some(marquee);
+        self
+    }
     pub fn build_label(self, gui: &Gui) -> Label {
-        Label::new(
+        let mut label = Label::new(
             gui.font_system(),
             Metrics::relative(self.font_size, self.line_height),
             self.attrs,
             self.align,
             self.text,
-        )
+        );
+        label.set_marquee(self.marquee);
+        label
     }
     pub fn build(mut self, gui: &mut Gui) -> WidgetId<Label> {
         let node = std::mem::take(&mut self.node);
@@ -100,6 +134,22 @@ impl<'a> LabelBuilder<'a> {
         node.build_widget(gui, label)
     }
 }
+impl<'a> WidgetBuilder for LabelBuilder<'a> {
+    type Widget = Label;
+    type Args = ();
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        LabelBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        LabelBuilder::parent(self, parent.into())
+    }
+    fn build(self, gui: &mut Gui, _args: Self::Args) -> WidgetId<Label> {
+        LabelBuilder::build(self, gui)
+    }
+}
 
 pub struct Label {
     font_system: FontSystem,
@@ -107,6 +157,9 @@ pub struct Label {
     buffer: Buffer,
     attrs: Attrs<'static>,
     align: Option<TextAlign>,
+    marquee: Option<Marquee>,
+    marquee_time: f32,
+    overflow: f32,
 }
 
 impl Label {
@@ -135,6 +188,9 @@ impl Label {
             buffer,
             attrs,
             align,
+            marquee: None,
+            marquee_time: 0.0,
+            overflow: 0.0,
         }
     }
     pub fn new_default(font_system: &FontSystem, text: &str) -> Self {
@@ -151,6 +207,11 @@ impl Label {
         gui.create_widget(Style::default(), label)
     }
 
+    /// The text of the label, with its lines joined by newlines.
+    pub fn text(&self) -> String {
+        let lines: Vec<&str> = self.buffer.lines.iter().map(|line| line.text()).collect();
+        lines.join("\n")
+    }
     pub fn set_text(&mut self, text: &str) {
         self.buffer.set_rich_text(
             &mut self.font_system.borrow_mut(),
@@ -160,6 +221,17 @@ impl Label {
             self.align,
         );
     }
+    /// Sets whether text that doesn't fit is scrolled, and restarts the scrolling.
+    pub fn set_marquee(&mut self, marquee: Option<Marquee>) {
+        self.marquee = marquee;
+        self.marquee_time = 0.0;
+    }
+    /// How far the text is currently scrolled to the left by the marquee.
+    pub fn marquee_offset(&self) -> f32 {
+        self.marquee
+            .map(|marquee| marquee.offset(self.marquee_time, self.overflow))
+            .unwrap_or(0.0)
+    }
     pub fn set_text_and_color(&mut self, text: &str, color: Option<Rgba>) {
         self.attrs.color_opt = color.map(|color| glyphon::Color(color.to_u32()));
         self.buffer.set_rich_text(
@@ -172,11 +244,33 @@ impl Label {
     }
 }
 impl Widget for Label {
+    fn theme_changed(&mut self, theme: &dyn Theme) {
+        // Rebuilding also drops the text renderer, which belongs to the glyph atlas the gui clears when the
+        // font system changes.
+        if !self.font_system.ptr_eq(theme.font_system()) {
+            let text = self
+                .buffer
+                .lines
+                .iter()
+                .map(|line| line.text())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let marquee = self.marquee;
+            *self = Label::new(
+                theme.font_system(),
+                self.buffer.metrics(),
+                self.attrs.clone(),
+                self.align,
+                &text,
+            );
+            self.marquee = marquee;
+        }
+    }
     fn measure(&mut self, available_space: Size) -> Size {
         if available_space.is_empty() {
             return Size::zero();
         }
-        let width_constraint = if available_space.width == i32::MAX {
+        let width_constraint = if available_space.width == i32::MAX || self.marquee.is_some() {
             None
         } else {
             Some(available_space.width as f32)
@@ -188,15 +282,44 @@ impl Widget for Label {
         };
         self.buffer
             .set_size(&mut self.font_system.borrow_mut(), width_constraint, height_constraint);
-        self.buffer.text_size()
+        let mut size = self.buffer.text_size();
+        size.width = size.width.min(available_space.width);
+        size
     }
     fn layout(&mut self, area: &Area) {
         let size = area.content_rect.size.to_f32();
-        self.buffer
-            .set_size(&mut self.font_system.borrow_mut(), Some(size.width), Some(size.height));
+        if self.marquee.is_some() {
+            self.buffer
+                .set_size(&mut self.font_system.borrow_mut(), None, Some(size.height));
+            self.overflow = (self.buffer.text_size().width as f32 - size.width).max(0.0);
+        } else {
+            self.buffer
+                .set_size(&mut self.font_system.borrow_mut(), Some(size.width), Some(size.height));
+        }
+    }
+    fn update(&mut self, dt: f32) -> bool {
+        match self.marquee {
+            Some(marquee) if self.overflow > 0.0 => {
+                self.marquee_time = (self.marquee_time + dt) % marquee.cycle_time(self.overflow);
+                true
+            }
+            _ => false,
+        }
     }
     fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
         let point = area.content_rect.origin;
+        let marquee_offset = self.marquee_offset();
+        let bounds = if self.marquee.is_some() {
+            let rect = area.content_rect;
+            TextBounds {
+                left: rect.min_x(),
+                top: rect.min_y(),
+                right: rect.max_x(),
+                bottom: rect.max_y(),
+            }
+        } else {
+            TextBounds::default()
+        };
         let default_color = glyphon::Color(renderer.theme().color(Color::Foreground).to_u32());
         let text_renderer = self
             .text_renderer
@@ -206,10 +329,10 @@ impl Widget for Label {
             text_renderer,
             [TextArea {
                 buffer: &self.buffer,
-                left: point.x as f32,
+                left: point.x as f32 - marquee_offset,
                 top: point.y as f32,
                 scale: 1.0,
-                bounds: TextBounds::default(),
+                bounds,
                 default_color,
                 custom_glyphs: &[],
             }],
@@ -221,11 +344,52 @@ impl WidgetId<Label> {
     pub fn set_text(&self, gui: &mut Gui, text: &str) {
         if let Some(label) = gui.get_widget_mut(*self) {
             label.set_text(text);
+            gui.request_node_layout(*self);
         }
     }
     pub fn set_text_and_color(&self, gui: &mut Gui, text: &str, color: Option<Rgba>) {
         if let Some(label) = gui.get_widget_mut(*self) {
             label.set_text_and_color(text, color);
+            gui.request_node_layout(*self);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_gui;
+
+    #[test]
+    fn marquee_scrolls_and_restarts() {
+        let mut gui = test_gui(Size::new(200, 100));
+        let marquee = Marquee {
+            speed: 10.0,
+            pause: 1.0,
+        };
+        let label = LabelBuilder::new("A status message that is far too long to fit")
+            .marquee(marquee)
+            .style(Style {
+                max_size: Size::new(50, i32::MAX),
+                ..Default::default()
+            })
+            .build(&mut gui);
+        let root = NodeBuilder::new().child(label).build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        let overflow = gui.get_widget(label).unwrap().overflow;
+        assert!(overflow > 10.0);
+
+        // Paused at the start.
+        assert!(gui.update(0.5));
+        assert_eq!(gui.get_widget(label).unwrap().marquee_offset(), 0.0);
+        assert!(gui.update(1.0));
+        assert_eq!(gui.get_widget(label).unwrap().marquee_offset(), 5.0);
+        // Scrolled to the end, and paused there.
+        gui.update(overflow / 10.0);
+        assert_eq!(gui.get_widget(label).unwrap().marquee_offset(), overflow);
+        // Restarted after a full cycle.
+        gui.update(1.0);
+        assert_eq!(gui.get_widget(label).unwrap().marquee_offset(), 0.0);
+    }
+}
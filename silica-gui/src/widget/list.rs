@@ -0,0 +1,298 @@
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+
+use crate::{render::GuiRenderer, *};
+
+#[derive(Default)]
+struct SelectionState {
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+    highlighted: Option<usize>,
+}
+
+impl SelectionState {
+    fn click(&mut self, index: usize, modifiers: Modifiers) {
+        self.highlighted = Some(index);
+        if modifiers.shift
+            && let Some(anchor) = self.anchor
+        {
+            self.selected = (anchor.min(index)..=anchor.max(index)).collect();
+        } else if modifiers.mod1 {
+            if !self.selected.remove(&index) {
+                self.selected.insert(index);
+            }
+            self.anchor = Some(index);
+        } else {
+            self.selected.clear();
+            self.selected.insert(index);
+            self.anchor = Some(index);
+        }
+    }
+}
+
+pub struct ListSelection {
+    state: RefCell<SelectionState>,
+    on_changed: EventFn,
+}
+
+impl ListSelection {
+    pub fn new<C, F>(on_changed: F) -> Rc<Self>
+    where
+        C: 'static,
+        F: Fn(&mut C, Vec<usize>) + 'static,
+    {
+        Rc::new(ListSelection {
+            state: RefCell::new(SelectionState::default()),
+            on_changed: EventFn::new_param(on_changed),
+        })
+    }
+    pub fn selected(&self) -> Vec<usize> {
+        self.state.borrow().selected.iter().copied().collect()
+    }
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.state.borrow().selected.contains(&index)
+    }
+    /// The item highlighted by keyboard navigation, which is activated by pressing Enter.
+    pub fn highlighted(&self) -> Option<usize> {
+        self.state.borrow().highlighted
+    }
+    pub fn set_selected(&self, selected: impl IntoIterator<Item = usize>) {
+        let mut state = self.state.borrow_mut();
+        state.selected = selected.into_iter().collect();
+        state.anchor = state.selected.first().copied();
+    }
+}
+
+pub struct ListItem {
+    selection: Rc<ListSelection>,
+    index: usize,
+    state: ButtonState,
+}
+
+impl Widget for ListItem {
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        let state_input = self.state.handle_input(input, None, area.content_rect);
+        if state_input.changed {
+            executor.request_redraw();
+        }
+        if state_input.clicked {
+            let selected = {
+                let mut state = self.selection.state.borrow_mut();
+                state.click(self.index, input.modifiers);
+                state.selected.iter().copied().collect::<Vec<_>>()
+            };
+            executor.queue(self.selection.on_changed.clone(), Some(Box::new(selected)));
+            executor.request_redraw();
+        }
+        state_input.action
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        let state = if self.selection.is_selected(self.index) {
+            ButtonState::Press
+        } else if self.selection.highlighted() == Some(self.index) {
+            ButtonState::Hover
+        } else {
+            self.state
+        };
+        renderer
+            .theme()
+            .draw_button(renderer, area.content_rect, ButtonStyle::Flat, false, state);
+    }
+}
+
+pub struct SelectableList {
+    selection: Rc<ListSelection>,
+    len: usize,
+}
+
+impl Widget for SelectableList {
+    fn focusable(&self) -> bool {
+        true
+    }
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, _area: &Area) -> InputAction {
+        if !input.focused {
+            return InputAction::Pass;
+        }
+        let mut state = self.selection.state.borrow_mut();
+        // Moving the highlight starts from the last clicked item if nothing is highlighted yet.
+        let current = state.highlighted.or(state.anchor);
+        match list_nav_input(input.nav_key, current, self.len) {
+            Some(ListNav::Highlight(index)) => state.highlighted = Some(index),
+            Some(ListNav::Activate(_)) => {
+                let Some(index) = state.highlighted else {
+                    return InputAction::Pass;
+                };
+                state.click(index, Modifiers::default());
+                let selected = state.selected.iter().copied().collect::<Vec<_>>();
+                executor.queue(self.selection.on_changed.clone(), Some(Box::new(selected)));
+            }
+            Some(ListNav::Cancel) if state.highlighted.is_some() => state.highlighted = None,
+            _ => return InputAction::Pass,
+        }
+        executor.request_redraw();
+        InputAction::Block
+    }
+    fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+}
+
+#[must_use]
+pub struct SelectableListBuilder {
+    node: NodeBuilder,
+    selection: Rc<ListSelection>,
+    items: Vec<NodeId>,
+}
+
+impl SelectableListBuilder {
+    pub fn new(selection: Rc<ListSelection>) -> Self {
+        SelectableListBuilder {
+            node: NodeBuilder::new().style(Style {
+                direction: Direction::Column,
+                ..Default::default()
+            }),
+            selection,
+            items: Vec::new(),
+        }
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    pub fn item(mut self, gui: &mut Gui, label: &str) -> Self {
+        let label = LabelBuilder::new(label)
+            .style(Style {
+                grow: true,
+                margin: SideOffsets::new(2, 4, 2, 4),
+                ..Default::default()
+            })
+            .build(gui);
+        let item = ListItem {
+            selection: self.selection.clone(),
+            index: self.items.len(),
+            state: ButtonState::Normal,
+        };
+        let item = NodeBuilder::new().child(label).build_widget(gui, item);
+        self.items.push(item.into());
+        self
+    }
+    pub fn items<'a, I>(mut self, gui: &mut Gui, labels: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        for label in labels {
+            self = self.item(gui, label);
+        }
+        self
+    }
+    pub fn build(self, gui: &mut Gui) -> WidgetId<SelectableList> {
+        let list = SelectableList {
+            selection: self.selection,
+            len: self.items.len(),
+        };
+        self.node.children(self.items).build_widget(gui, list)
+    }
+}
+impl WidgetBuilder for SelectableListBuilder {
+    type Widget = SelectableList;
+    type Args = ();
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        SelectableListBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        SelectableListBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, _args: Self::Args) -> WidgetId<SelectableList> {
+        SelectableListBuilder::build(self, gui)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{click, test_gui};
+
+    const SHIFT: Modifiers = Modifiers {
+        shift: true,
+        mod1: false,
+        mod2: false,
+    };
+    const CTRL: Modifiers = Modifiers {
+        shift: false,
+        mod1: true,
+        mod2: false,
+    };
+
+    fn selected(state: &SelectionState) -> Vec<usize> {
+        state.selected.iter().copied().collect()
+    }
+
+    #[test]
+    fn plain_click_replaces_selection() {
+        let mut state = SelectionState::default();
+        state.click(2, Modifiers::default());
+        state.click(4, Modifiers::default());
+        assert_eq!(selected(&state), [4]);
+        assert_eq!(state.anchor, Some(4));
+    }
+
+    #[test]
+    fn shift_click_selects_range_from_anchor() {
+        let mut state = SelectionState::default();
+        state.click(5, Modifiers::default());
+        state.click(2, SHIFT);
+        assert_eq!(selected(&state), [2, 3, 4, 5]);
+        // The anchor stays put, so the range can be changed from the same end.
+        state.click(7, SHIFT);
+        assert_eq!(selected(&state), [5, 6, 7]);
+        assert_eq!(state.anchor, Some(5));
+    }
+
+    #[test]
+    fn shift_click_without_anchor_selects_one() {
+        let mut state = SelectionState::default();
+        state.click(3, SHIFT);
+        assert_eq!(selected(&state), [3]);
+        assert_eq!(state.anchor, Some(3));
+    }
+
+    #[test]
+    fn ctrl_click_toggles_and_moves_anchor() {
+        let mut state = SelectionState::default();
+        state.click(1, Modifiers::default());
+        state.click(3, CTRL);
+        assert_eq!(selected(&state), [1, 3]);
+        state.click(1, CTRL);
+        assert_eq!(selected(&state), [3]);
+        assert_eq!(state.anchor, Some(1));
+        state.click(4, SHIFT);
+        assert_eq!(selected(&state), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn clicking_item_fires_selection() {
+        let mut gui = test_gui(Size::new(200, 200));
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let selection = ListSelection::new({
+            let events = events.clone();
+            move |_: &mut Gui, selected: Vec<usize>| events.borrow_mut().push(selected)
+        });
+        let list = SelectableListBuilder::new(selection.clone())
+            .items(&mut gui, ["one", "two", "three"])
+            .build(&mut gui);
+        gui.set_root(list);
+        gui.layout();
+        let second = gui.children[NodeId::from(list)][1];
+        let point = gui.screen_rect(second).center();
+        click(&mut gui, point);
+        assert_eq!(*events.borrow(), [vec![1]]);
+        assert_eq!(selection.selected(), [1]);
+    }
+}
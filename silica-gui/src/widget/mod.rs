@@ -1,15 +1,92 @@
 mod button;
+mod canvas;
+mod collapsible;
+mod context_menu;
+mod dropdown;
+mod image;
 mod label;
+mod list;
+mod number;
 mod slider;
+mod spinner;
+mod split_pane;
+mod table;
 
-pub use self::{button::*, label::*, slider::*};
+pub use self::{
+    button::*, canvas::*, collapsible::*, context_menu::*, dropdown::*, image::*, label::*, list::*, number::*,
+    slider::*, spinner::*, split_pane::*, table::*,
+};
 use crate::*;
 
+/// How far a widget with an editable value can be interacted with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WidgetState {
+    /// Can be focused and edited.
+    #[default]
+    Enabled,
+    /// Can be focused, but its value can't be changed.
+    ReadOnly,
+    /// Can't be focused or changed, and is drawn dimmed.
+    Disabled,
+}
+
+/// What a navigation key does to a list of items with a keyboard highlight.
+pub(crate) enum ListNav {
+    Highlight(usize),
+    Activate(usize),
+    Cancel,
+}
+
+/// Keyboard navigation shared by dropdowns, context menus, and selectable lists. Up and Down move the
+/// highlight from `current`, wrapping around, Enter activates the current item, and Escape cancels.
+/// Returns `None` if the key does nothing, so the input can be passed on.
+pub(crate) fn list_nav_input(nav_key: Option<NavKey>, current: Option<usize>, len: usize) -> Option<ListNav> {
+    match nav_key? {
+        NavKey::Up | NavKey::Down if len == 0 => None,
+        NavKey::Up => Some(ListNav::Highlight(
+            current.map(|index| (index + len - 1) % len).unwrap_or(len - 1),
+        )),
+        NavKey::Down => Some(ListNav::Highlight(current.map(|index| (index + 1) % len).unwrap_or(0))),
+        NavKey::Enter => current.filter(|index| *index < len).map(ListNav::Activate),
+        NavKey::Escape => Some(ListNav::Cancel),
+        NavKey::Backspace => None,
+    }
+}
+
+/// Pointer input handling shared by popups that close when clicking outside of them, like dropdowns and
+/// context menus. Blocks input over the popup, and consumes clicks outside of it. Returns whether the
+/// popup should close.
+pub(crate) fn popup_pointer_input(input: &GuiInput, area: &Area) -> (InputAction, bool) {
+    if area.background_rect.contains(input.pointer) {
+        (InputAction::Block, false)
+    } else if (input.clicked || input.secondary_clicked) && !input.blocked {
+        (InputAction::Block, true)
+    } else {
+        (InputAction::Pass, false)
+    }
+}
+
+/// Methods shared by widget builders, so a widget's node can be set up and the widget built without
+/// knowing which widget it is. Implemented by the built-in widget builders, and can be implemented by
+/// builders of custom widgets.
+pub trait WidgetBuilder: Sized {
+    type Widget: Widget;
+    /// What [`WidgetBuilder::build`] takes besides the gui, such as the widget's event. Builders also
+    /// have an inherent `build` that takes events as closures, which is usually more convenient.
+    type Args;
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style);
+    fn parent(self, parent: impl Into<NodeId>) -> Self;
+    fn build(self, gui: &mut Gui, args: Self::Args) -> WidgetId<Self::Widget>;
+}
+
 #[derive(Default)]
 pub struct NodeBuilder {
     style: Style,
     parent: Option<NodeId>,
     children: Vec<NodeId>,
+    tooltip: Option<String>,
 }
 
 impl NodeBuilder {
@@ -39,9 +116,14 @@ impl NodeBuilder {
         self.children.extend(iter);
         self
     }
+    pub fn tooltip(mut self, tooltip: &str) -> Self {
+        self.tooltip = Some(tooltip.to_string());
+        self
+    }
     pub fn build(self, gui: &mut Gui) -> NodeId {
         let node = gui.create_node(self.style);
         gui.set_node_children(node, self.children);
+        gui.set_tooltip(node, self.tooltip.as_deref());
         if let Some(parent) = self.parent {
             gui.add_child(parent, node);
         }
@@ -50,9 +132,36 @@ impl NodeBuilder {
     pub fn build_widget<W: Widget>(self, gui: &mut Gui, widget: W) -> WidgetId<W> {
         let widget = gui.create_widget(self.style, widget);
         gui.set_node_children(widget, self.children);
+        gui.set_tooltip(widget, self.tooltip.as_deref());
         if let Some(parent) = self.parent {
             gui.add_child(parent, widget);
         }
         widget
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nav(nav_key: NavKey, current: Option<usize>, len: usize) -> Option<ListNav> {
+        list_nav_input(Some(nav_key), current, len)
+    }
+
+    #[test]
+    fn list_nav_wraps() {
+        assert!(matches!(nav(NavKey::Down, None, 3), Some(ListNav::Highlight(0))));
+        assert!(matches!(nav(NavKey::Down, Some(1), 3), Some(ListNav::Highlight(2))));
+        assert!(matches!(nav(NavKey::Down, Some(2), 3), Some(ListNav::Highlight(0))));
+        assert!(matches!(nav(NavKey::Up, None, 3), Some(ListNav::Highlight(2))));
+        assert!(matches!(nav(NavKey::Up, Some(0), 3), Some(ListNav::Highlight(2))));
+        assert!(nav(NavKey::Up, None, 0).is_none());
+
+        assert!(matches!(nav(NavKey::Enter, Some(1), 3), Some(ListNav::Activate(1))));
+        assert!(nav(NavKey::Enter, None, 3).is_none());
+        assert!(nav(NavKey::Enter, Some(3), 3).is_none());
+        assert!(matches!(nav(NavKey::Escape, None, 3), Some(ListNav::Cancel)));
+        assert!(nav(NavKey::Backspace, Some(0), 3).is_none());
+        assert!(list_nav_input(None, Some(0), 3).is_none());
+    }
+}
@@ -0,0 +1,432 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use crate::{render::GuiRenderer, *};
+
+struct NumberState {
+    value: Cell<i32>,
+    min: i32,
+    max: i32,
+    step: i32,
+    widget_state: Cell<WidgetState>,
+    /// Digits typed since the input was last clicked.
+    entry: RefCell<String>,
+    label: WidgetId<Label>,
+    on_changed: EventFn,
+}
+
+impl NumberState {
+    fn editable(&self) -> bool {
+        self.widget_state.get() == WidgetState::Enabled
+    }
+    fn set_value(state: &Rc<NumberState>, executor: &mut EventExecutor, value: i32) {
+        let value = value.clamp(state.min, state.max);
+        if state.value.replace(value) == value {
+            return;
+        }
+        let update_label = EventFn::new_param(|gui: &mut Gui, state: Rc<NumberState>| {
+            state.label.set_text(gui, &state.value.get().to_string());
+        });
+        executor.queue(update_label, Some(Box::new(state.clone())));
+        executor.queue(state.on_changed.clone(), Some(Box::new(value)));
+    }
+    fn step(state: &Rc<NumberState>, executor: &mut EventExecutor, steps: i32) {
+        state.entry.borrow_mut().clear();
+        let value = state.value.get().saturating_add(state.step.saturating_mul(steps));
+        Self::set_value(state, executor, value);
+    }
+}
+
+struct NumberInputButton {
+    state: Rc<NumberState>,
+    steps: i32,
+    button_state: ButtonState,
+}
+
+impl Widget for NumberInputButton {
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        if !self.state.editable() {
+            self.button_state = ButtonState::Normal;
+            return ButtonState::Disable.handle_input(input, None, area.content_rect).action;
+        }
+        let state_input = self.button_state.handle_input(input, None, area.content_rect);
+        if state_input.changed {
+            executor.request_redraw();
+        }
+        if state_input.clicked {
+            NumberState::step(&self.state, executor, self.steps);
+        }
+        state_input.action
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        let button_state = if self.state.editable() {
+            self.button_state
+        } else {
+            ButtonState::Disable
+        };
+        renderer
+            .theme()
+            .draw_button(renderer, area.content_rect, ButtonStyle::Normal, false, button_state);
+    }
+}
+
+/// Integer field with buttons to step the value up and down. While focused, typing digits replaces the
+/// value, Backspace deletes the last typed digit, and Up/Down step it. A read-only input can still be
+/// focused, but ignores these keys and its buttons.
+pub struct NumberInput {
+    state: Rc<NumberState>,
+}
+
+impl NumberInput {
+    pub fn value(&self) -> i32 {
+        self.state.value.get()
+    }
+    pub fn range(&self) -> (i32, i32) {
+        (self.state.min, self.state.max)
+    }
+    pub fn widget_state(&self) -> WidgetState {
+        self.state.widget_state.get()
+    }
+}
+impl Widget for NumberInput {
+    fn focusable(&self) -> bool {
+        self.widget_state() != WidgetState::Disabled
+    }
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        if input.clicked && area.content_rect.contains(input.pointer) {
+            self.state.entry.borrow_mut().clear();
+        }
+        if !input.focused || !self.state.editable() {
+            return InputAction::Pass;
+        }
+        let typed = input
+            .hotkey
+            .filter(|hotkey| !hotkey.mod1 && !hotkey.mod2)
+            .map(|hotkey| hotkey.key)
+            .filter(|key| key.is_ascii_digit() || *key == '-');
+        match (input.nav_key, typed) {
+            (Some(NavKey::Up), _) => NumberState::step(&self.state, executor, 1),
+            (Some(NavKey::Down), _) => NumberState::step(&self.state, executor, -1),
+            (Some(NavKey::Backspace), _) => {
+                let mut entry = self.state.entry.borrow_mut();
+                entry.pop();
+                let value = entry.parse::<i32>();
+                drop(entry);
+                if let Ok(value) = value {
+                    NumberState::set_value(&self.state, executor, value);
+                }
+            }
+            (_, Some('-')) => {
+                let mut entry = self.state.entry.borrow_mut();
+                if entry.is_empty() && self.state.min < 0 {
+                    entry.push('-');
+                }
+            }
+            (_, Some(key)) => {
+                let mut entry = self.state.entry.borrow_mut();
+                entry.push(key);
+                let value = entry
+                    .parse::<i64>()
+                    .map(|value| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+                drop(entry);
+                if let Ok(value) = value {
+                    NumberState::set_value(&self.state, executor, value);
+                }
+            }
+            _ => return InputAction::Pass,
+        }
+        InputAction::Block
+    }
+    fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+}
+impl WidgetId<NumberInput> {
+    pub fn value(&self, gui: &Gui) -> i32 {
+        gui.get_widget(*self).map(|input| input.value()).unwrap_or_default()
+    }
+    /// Sets the value, clamped to the input's range, without calling its `on_changed` event.
+    pub fn set_value(&self, gui: &mut Gui, value: i32) {
+        if let Some(input) = gui.get_widget(*self) {
+            let state = input.state.clone();
+            let value = value.clamp(state.min, state.max);
+            state.value.set(value);
+            state.entry.borrow_mut().clear();
+            state.label.set_text(gui, &value.to_string());
+        }
+    }
+    pub fn widget_state(&self, gui: &Gui) -> WidgetState {
+        gui.get_widget(*self)
+            .map(|input| input.widget_state())
+            .unwrap_or_default()
+    }
+    /// Sets whether the input can be focused and edited. Disabling it removes its focus and dims its value.
+    pub fn set_widget_state(&self, gui: &mut Gui, widget_state: WidgetState) {
+        if let Some(input) = gui.get_widget_mut(*self) {
+            let state = input.state.clone();
+            state.widget_state.set(widget_state);
+            state.entry.borrow_mut().clear();
+            let color = (widget_state == WidgetState::Disabled)
+                .then(|| gui.theme().button_foreground_color(ButtonState::Disable));
+            state
+                .label
+                .set_text_and_color(gui, &state.value.get().to_string(), color);
+            if widget_state == WidgetState::Disabled && gui.focused_node() == Some((*self).into()) {
+                gui.set_focus(None);
+            }
+        }
+    }
+}
+
+#[must_use]
+pub struct NumberInputBuilder {
+    node: NodeBuilder,
+    value: i32,
+    min: i32,
+    max: i32,
+    step: i32,
+    widget_state: WidgetState,
+}
+
+impl NumberInputBuilder {
+    const BUTTON_SIZE: Size = Size::new(32, 32);
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    pub fn value(mut self, value: i32) -> Self {
+        self.value = value;
+        self
+    }
+    pub fn range(mut self, min: i32, max: i32) -> Self {
+        self.min = min;
+        self.max = max.max(min);
+        self
+    }
+    pub fn step(mut self, step: i32) -> Self {
+        self.step = step;
+        self
+    }
+    pub fn widget_state(mut self, widget_state: WidgetState) -> Self {
+        self.widget_state = widget_state;
+        self
+    }
+    fn create_button(gui: &mut Gui, state: &Rc<NumberState>, text: &str, steps: i32) -> NodeId {
+        let button = NumberInputButton {
+            state: state.clone(),
+            steps,
+            button_state: ButtonState::Normal,
+        };
+        let label = LabelBuilder::new(text)
+            .style(Style {
+                grow: true,
+                ..Default::default()
+            })
+            .align(TextAlign::Center)
+            .build(gui);
+        NodeBuilder::new()
+            .style(Style {
+                min_size: Self::BUTTON_SIZE,
+                cross_align: Align::Center,
+                ..Default::default()
+            })
+            .child(label)
+            .build_widget(gui, button)
+            .into()
+    }
+    pub fn build<C, F>(self, gui: &mut Gui, on_changed: F) -> WidgetId<NumberInput>
+    where
+        C: 'static,
+        F: Fn(&mut C, i32) + 'static,
+    {
+        WidgetBuilder::build(self, gui, EventFn::new_param(on_changed))
+    }
+}
+/// Builds with an event made by [`EventFn::new_param`], with the new value as its parameter.
+impl WidgetBuilder for NumberInputBuilder {
+    type Widget = NumberInput;
+    type Args = EventFn;
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        NumberInputBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        NumberInputBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, on_changed: Self::Args) -> WidgetId<NumberInput> {
+        let value = self.value.clamp(self.min, self.max);
+        let label = LabelBuilder::new(&value.to_string())
+            .style(Style {
+                grow: true,
+                min_size: Size::new(64, 0),
+                ..Default::default()
+            })
+            .align(TextAlign::Center)
+            .build(gui);
+        let state = Rc::new(NumberState {
+            value: Cell::new(value),
+            min: self.min,
+            max: self.max,
+            step: self.step,
+            widget_state: Cell::new(WidgetState::Enabled),
+            entry: RefCell::new(String::new()),
+            label,
+            on_changed,
+        });
+        let decrement = Self::create_button(gui, &state, "-", -1);
+        let increment = Self::create_button(gui, &state, "+", 1);
+        let input = self
+            .node
+            .child(decrement)
+            .child(label)
+            .child(increment)
+            .build_widget(gui, NumberInput { state });
+        if self.widget_state != WidgetState::Enabled {
+            input.set_widget_state(gui, self.widget_state);
+        }
+        input
+    }
+}
+impl Default for NumberInputBuilder {
+    fn default() -> Self {
+        NumberInputBuilder {
+            node: NodeBuilder::new().style(Style {
+                cross_align: Align::Center,
+                gap: 4,
+                ..Default::default()
+            }),
+            value: 0,
+            min: i32::MIN,
+            max: i32::MAX,
+            step: 1,
+            widget_state: WidgetState::Enabled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{TestEvent, TestKey, click, send, test_gui};
+
+    fn number_input(gui: &mut Gui, widget_state: WidgetState) -> WidgetId<NumberInput> {
+        let input = NumberInputBuilder::new()
+            .value(5)
+            .widget_state(widget_state)
+            .build(gui, |_: &mut Gui, _| {});
+        let root = NodeBuilder::new().child(input).build(gui);
+        gui.set_root(root);
+        input
+    }
+
+    /// Clicks the middle of the input, types a digit, presses Up, and clicks the increment button.
+    fn try_edit(gui: &mut Gui, input: WidgetId<NumberInput>) {
+        let rect = gui.nodes[input.into()].area.content_rect;
+        click(gui, rect.center());
+        send(gui, TestEvent::Keyboard(TestKey(Some(Hotkey::new('7')), None)));
+        send(gui, TestEvent::Keyboard(TestKey(None, Some(NavKey::Up))));
+        gui.layout();
+        let rect = gui.nodes[input.into()].area.content_rect;
+        click(gui, Point::new(rect.max_x() - 1, rect.center().y));
+    }
+
+    #[test]
+    fn enabled_input_edits() {
+        let mut gui = test_gui(Size::new(300, 100));
+        let input = number_input(&mut gui, WidgetState::Enabled);
+        try_edit(&mut gui, input);
+        assert_eq!(gui.focused_node(), Some(input.into()));
+        assert_eq!(input.value(&gui), 9);
+    }
+
+    #[test]
+    fn read_only_input_focuses_without_editing() {
+        let mut gui = test_gui(Size::new(300, 100));
+        let input = number_input(&mut gui, WidgetState::ReadOnly);
+        try_edit(&mut gui, input);
+        assert_eq!(gui.focused_node(), Some(input.into()));
+        assert_eq!(input.value(&gui), 5);
+    }
+
+    #[test]
+    fn disabled_input_ignores_everything() {
+        let mut gui = test_gui(Size::new(300, 100));
+        let input = number_input(&mut gui, WidgetState::Enabled);
+        gui.layout();
+        let rect = gui.nodes[input.into()].area.content_rect;
+        click(&mut gui, rect.center());
+        input.set_widget_state(&mut gui, WidgetState::Disabled);
+        assert_eq!(gui.focused_node(), None);
+
+        try_edit(&mut gui, input);
+        assert_eq!(gui.focused_node(), None);
+        assert_eq!(input.value(&gui), 5);
+        assert_eq!(input.widget_state(&gui), WidgetState::Disabled);
+    }
+
+    /// Clicks the decrement (`steps` < 0) or increment button of the input.
+    fn click_step(gui: &mut Gui, input: WidgetId<NumberInput>, steps: i32) {
+        gui.layout();
+        let rect = gui.nodes[input.into()].area.content_rect;
+        let x = if steps < 0 { rect.min_x() + 1 } else { rect.max_x() - 1 };
+        click(gui, Point::new(x, rect.center().y));
+    }
+
+    fn type_key(gui: &mut Gui, key: char) {
+        send(gui, TestEvent::Keyboard(TestKey(Some(Hotkey::new(key)), None)));
+    }
+
+    #[test]
+    fn steps_and_digits_are_clamped() {
+        let mut gui = test_gui(Size::new(300, 100));
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let input = NumberInputBuilder::new()
+            .range(0, 10)
+            .step(3)
+            .value(8)
+            .build(&mut gui, {
+                let changes = changes.clone();
+                move |_: &mut Gui, value| changes.borrow_mut().push(value)
+            });
+        let root = NodeBuilder::new().child(input).build(&mut gui);
+        gui.set_root(root);
+
+        // Stepping past the maximum stops at it, and stepping at it changes nothing.
+        click_step(&mut gui, input, 1);
+        assert_eq!(input.value(&gui), 10);
+        click_step(&mut gui, input, 1);
+        click_step(&mut gui, input, -1);
+        assert_eq!(input.value(&gui), 7);
+        for _ in 0..3 {
+            click_step(&mut gui, input, -1);
+        }
+        assert_eq!(input.value(&gui), 0);
+        assert_eq!(changes.take(), [10, 7, 4, 1, 0]);
+
+        // Typed digits replace the value, clamped to the range.
+        let rect = gui.nodes[input.into()].area.content_rect;
+        click(&mut gui, rect.center());
+        type_key(&mut gui, '4');
+        assert_eq!(input.value(&gui), 4);
+        type_key(&mut gui, '2');
+        assert_eq!(input.value(&gui), 10);
+        send(&mut gui, TestEvent::Keyboard(TestKey(None, Some(NavKey::Backspace))));
+        assert_eq!(input.value(&gui), 4);
+        assert_eq!(changes.take(), [4, 10, 4]);
+
+        input.set_value(&mut gui, -5);
+        assert_eq!(input.value(&gui), 0);
+    }
+}
@@ -2,11 +2,22 @@ use std::{cell::Cell, rc::Rc};
 
 use euclid::Vector2D;
 
-use crate::{render::GuiRenderer, *};
+use crate::{
+    render::{GuiRenderer, Quad},
+    *,
+};
 
 pub struct Slider {
     vertical: bool,
+    /// Position of the handle along the track, from 0 to 1.
     value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    /// Interval between tick marks in the slider's units, or zero for no ticks.
+    tick_interval: f32,
+    /// Shows the value on the handle.
+    value_label: Option<Label>,
     scroll_size: Option<Rc<Cell<Size>>>,
     state: ButtonState,
     on_changed: EventFn,
@@ -14,10 +25,12 @@ pub struct Slider {
 
 impl Slider {
     const MIN_SIZE: Size = Size::new(32, 32);
-    fn scrollbar_style() -> Style {
+    /// Hidden scrollbars also have no minimum size, so they don't take up any space.
+    pub(crate) fn scrollbar_style(hidden: bool) -> Style {
         Style {
+            hidden,
             background_color: Some(Color::Gutter),
-            min_size: Self::MIN_SIZE,
+            min_size: if hidden { Size::zero() } else { Self::MIN_SIZE },
             ..Default::default()
         }
     }
@@ -26,27 +39,96 @@ impl Slider {
         C: 'static,
         F: Fn(&mut C, f32) + 'static,
     {
-        Slider {
-            vertical,
-            value: 0.0,
-            scroll_size: None,
-            state: ButtonState::Normal,
-            on_changed: EventFn::new_param(on_changed),
-        }
+        Self::with_event(vertical, None, EventFn::new_param(on_changed))
     }
     pub fn new_scrollbar<C, F>(vertical: bool, scroll_size: Option<Rc<Cell<Size>>>, on_changed: F) -> Self
     where
         C: 'static,
         F: Fn(&mut C, f32) + 'static,
     {
+        Self::with_event(vertical, scroll_size, EventFn::new_param(on_changed))
+    }
+    fn with_event(vertical: bool, scroll_size: Option<Rc<Cell<Size>>>, on_changed: EventFn) -> Self {
         Slider {
             vertical,
             value: 0.0,
+            min: 0.0,
+            max: 1.0,
+            step: 0.0,
+            tick_interval: 0.0,
+            value_label: None,
             scroll_size,
             state: ButtonState::Normal,
-            on_changed: EventFn::new_param(on_changed),
+            on_changed,
+        }
+    }
+    /// The value in the slider's range, from `min` at the start of the track to `max` at the end.
+    pub fn value(&self) -> f32 {
+        self.min + self.value * (self.max - self.min)
+    }
+    /// Sets the value, snapped to the step and clamped to the range.
+    pub fn set_value(&mut self, value: f32) {
+        let value = self.snap(value);
+        self.value = if self.max > self.min {
+            ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if let Some(label) = self.value_label.as_mut() {
+            let text = Self::value_text(value, self.step);
+            label.set_text(&text);
         }
     }
+    /// Formats a value with as many decimals as the step has, or two if there's no step.
+    fn value_text(value: f32, step: f32) -> String {
+        let decimals = if step > 0.0 {
+            (0..3)
+                .find(|decimals| (step * 10f32.powi(*decimals)).fract().abs() < 1e-3)
+                .unwrap_or(3)
+        } else {
+            2
+        };
+        format!("{:.*}", decimals as usize, value)
+    }
+    /// Shows the value as text on the handle, drawn with `label`'s font and alignment.
+    pub fn set_value_label(&mut self, label: Option<Label>) {
+        self.value_label = label;
+        self.set_value(self.value());
+    }
+    pub fn tick_interval(&self) -> f32 {
+        self.tick_interval
+    }
+    /// Draws tick marks along the track every `interval`, counted from `min`. Zero removes them.
+    pub fn set_tick_interval(&mut self, interval: f32) {
+        self.tick_interval = interval.max(0.0);
+    }
+    pub fn range(&self) -> (f32, f32) {
+        (self.min, self.max)
+    }
+    /// Sets the range of values, keeping the current value if it's still in range.
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        let value = self.value();
+        self.min = min;
+        self.max = max.max(min);
+        self.set_value(value);
+    }
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+    /// Sets the step values snap to, counted from `min`. A step of zero allows any value.
+    pub fn set_step(&mut self, step: f32) {
+        let value = self.value();
+        self.step = step.max(0.0);
+        self.set_value(value);
+    }
+    fn snap(&self, value: f32) -> f32 {
+        let value = if self.step > 0.0 {
+            self.min + ((value - self.min) / self.step).round() * self.step
+        } else {
+            value
+        };
+        value.clamp(self.min, self.max)
+    }
     fn handle_size(&self, area: &Area) -> i32 {
         if self.vertical {
             let scroll_size = self
@@ -55,7 +137,9 @@ impl Slider {
                 .map(|size| (area.content_rect.size.height as f32) / (size.get().height as f32).max(1.0))
                 .unwrap_or_default()
                 .min(1.0);
-            ((scroll_size * (area.content_rect.size.height as f32)) as i32).max(32)
+            ((scroll_size * (area.content_rect.size.height as f32)) as i32)
+                .max(32)
+                .min(area.content_rect.size.height)
         } else {
             let scroll_size = self
                 .scroll_size
@@ -63,7 +147,9 @@ impl Slider {
                 .map(|size| (area.content_rect.size.width as f32) / (size.get().width as f32).max(1.0))
                 .unwrap_or_default()
                 .min(1.0);
-            ((scroll_size * (area.content_rect.size.width as f32)) as i32).max(32)
+            ((scroll_size * (area.content_rect.size.width as f32)) as i32)
+                .max(32)
+                .min(area.content_rect.size.width)
         }
     }
 }
@@ -75,47 +161,265 @@ impl Widget for Slider {
         }
         if self.state == ButtonState::Press {
             let handle_size = self.handle_size(area);
-            self.value = if self.vertical {
-                ((input.pointer.y - area.content_rect.origin.y - (handle_size / 2)) as f32)
-                    / ((area.content_rect.size.height - handle_size) as f32)
+            let pointer = input.local_pointer(area);
+            let (pointer, track_size) = if self.vertical {
+                (pointer.y, area.content_rect.size.height - handle_size)
             } else {
-                ((input.pointer.x - area.content_rect.origin.x - (handle_size / 2)) as f32)
-                    / ((area.content_rect.size.width - handle_size) as f32)
+                (pointer.x, area.content_rect.size.width - handle_size)
             };
-            self.value = self.value.clamp(0.0, 1.0);
-            executor.queue(self.on_changed.clone(), Some(Box::new(self.value)));
-            executor.request_redraw();
+            // If the handle fills the track it can't move, so keep the current value.
+            if track_size > 0 {
+                let old_value = self.value;
+                let position = (((pointer - (handle_size / 2)) as f32) / (track_size as f32)).clamp(0.0, 1.0);
+                self.set_value(self.min + position * (self.max - self.min));
+                if self.value != old_value {
+                    executor.queue(self.on_changed.clone(), Some(Box::new(self.value())));
+                    executor.request_redraw();
+                }
+            }
             InputAction::Grab
         } else {
             state_input.action
         }
     }
+    fn cursor(&self, _input: &GuiInput, _area: &Area) -> Option<CursorIcon> {
+        match self.state {
+            ButtonState::Press => Some(CursorIcon::Grabbing),
+            ButtonState::Disable => None,
+            _ => Some(CursorIcon::Grab),
+        }
+    }
+    fn theme_changed(&mut self, theme: &dyn Theme) {
+        if let Some(label) = self.value_label.as_mut() {
+            label.theme_changed(theme);
+        }
+    }
     fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
         let handle_size = self.handle_size(area);
-        let handle_rect = if self.vertical {
-            let handle_pos = area.content_rect.origin.y
-                + (self.value * ((area.content_rect.size.height - handle_size) as f32)) as i32;
+        if self.tick_interval > 0.0 && self.max > self.min {
+            self.draw_ticks(renderer, area, handle_size);
+        }
+        let handle_rect = self.handle_rect(area, handle_size, self.value);
+        renderer
+            .theme()
+            .draw_button(renderer, handle_rect, ButtonStyle::Normal, false, self.state);
+        if let Some(label) = self.value_label.as_mut() {
+            let text_height = label.measure(handle_rect.size).height;
+            let mut label_area = Area::new();
+            label_area.content_rect = Rect::new(
+                handle_rect.origin + Vector::new(0, (handle_rect.height() - text_height) / 2),
+                Size::new(handle_rect.width(), text_height),
+            );
+            label.layout(&label_area);
+            label.draw(renderer, &label_area);
+        }
+    }
+}
+impl Slider {
+    const TICK_SIZE: i32 = 6;
+    /// The rect of the handle when it's at `position` along the track, from 0 to 1.
+    fn handle_rect(&self, area: &Area, handle_size: i32, position: f32) -> Rect {
+        let rect = area.content_rect;
+        if self.vertical {
+            let handle_pos = rect.origin.y + (position * ((rect.size.height - handle_size) as f32)) as i32;
             Rect::new(
-                Point::new(area.content_rect.origin.x, handle_pos),
-                Size::new(area.content_rect.size.width, handle_size),
+                Point::new(rect.origin.x, handle_pos),
+                Size::new(rect.size.width, handle_size),
             )
         } else {
-            let handle_pos = area.content_rect.origin.x
-                + (self.value * ((area.content_rect.size.width - handle_size) as f32)) as i32;
+            let handle_pos = rect.origin.x + (position * ((rect.size.width - handle_size) as f32)) as i32;
             Rect::new(
-                Point::new(handle_pos, area.content_rect.origin.y),
-                Size::new(handle_size, area.content_rect.size.height),
+                Point::new(handle_pos, rect.origin.y),
+                Size::new(handle_size, rect.size.height),
             )
+        }
+    }
+    /// Draws a tick at the end of the track's cross axis, under the handle's center at each tick value.
+    fn draw_ticks(&self, renderer: &mut GuiRenderer, area: &Area, handle_size: i32) {
+        let range = self.max - self.min;
+        let track_size = if self.vertical {
+            area.content_rect.size.height
+        } else {
+            area.content_rect.size.width
         };
-        renderer
-            .theme()
-            .draw_button(renderer, handle_rect, ButtonStyle::Normal, false, self.state);
+        // Ticks closer than two pixels apart would just fill the track.
+        let count = (range / self.tick_interval).floor() as i32;
+        if count <= 0 || count > track_size / 2 {
+            return;
+        }
+        let color = renderer.theme().color(Color::Border);
+        for tick in 0..=count {
+            let position = (tick as f32 * self.tick_interval / range).min(1.0);
+            let center = self.handle_rect(area, handle_size, position).center();
+            let rect = if self.vertical {
+                Rect::new(
+                    Point::new(area.content_rect.max_x() - Self::TICK_SIZE, center.y),
+                    Size::new(Self::TICK_SIZE, 1),
+                )
+            } else {
+                Rect::new(
+                    Point::new(center.x, area.content_rect.max_y() - Self::TICK_SIZE),
+                    Size::new(1, Self::TICK_SIZE),
+                )
+            };
+            renderer.draw_theme_quad(Quad {
+                rect: rect.to_box2d(),
+                uv: GuiRenderer::UV_WHITE,
+                color,
+            });
+        }
+    }
+}
+
+impl WidgetId<Slider> {
+    pub fn value(&self, gui: &Gui) -> f32 {
+        gui.get_widget(*self).map(|slider| slider.value()).unwrap_or_default()
+    }
+    /// Sets the value without calling the slider's `on_changed` event.
+    pub fn set_value(&self, gui: &mut Gui, value: f32) {
+        if let Some(slider) = gui.get_widget_mut(*self) {
+            slider.set_value(value);
+        }
+    }
+    pub fn set_range(&self, gui: &mut Gui, min: f32, max: f32) {
+        if let Some(slider) = gui.get_widget_mut(*self) {
+            slider.set_range(min, max);
+        }
+    }
+    pub fn set_step(&self, gui: &mut Gui, step: f32) {
+        if let Some(slider) = gui.get_widget_mut(*self) {
+            slider.set_step(step);
+        }
+    }
+}
+
+#[must_use]
+pub struct SliderBuilder {
+    node: NodeBuilder,
+    vertical: bool,
+    min: f32,
+    max: f32,
+    step: f32,
+    value: f32,
+    tick_interval: f32,
+    show_value: bool,
+}
+
+impl SliderBuilder {
+    const VALUE_FONT_SIZE: f32 = 14.0;
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+    pub fn value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+    /// Draws tick marks along the track every `interval`, counted from the start of the range.
+    pub fn ticks(mut self, interval: f32) -> Self {
+        self.tick_interval = interval;
+        self
+    }
+    /// Shows the current value as text on the handle.
+    pub fn show_value(mut self, show_value: bool) -> Self {
+        self.show_value = show_value;
+        self
+    }
+    pub fn build<C, F>(self, gui: &mut Gui, on_changed: F) -> WidgetId<Slider>
+    where
+        C: 'static,
+        F: Fn(&mut C, f32) + 'static,
+    {
+        WidgetBuilder::build(self, gui, EventFn::new_param(on_changed))
+    }
+}
+/// Builds with an event made by [`EventFn::new_param`], with the new value as its parameter.
+impl WidgetBuilder for SliderBuilder {
+    type Widget = Slider;
+    type Args = EventFn;
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        SliderBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        SliderBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, on_changed: Self::Args) -> WidgetId<Slider> {
+        let mut slider = Slider::with_event(self.vertical, None, on_changed);
+        slider.set_range(self.min, self.max);
+        slider.set_step(self.step);
+        slider.set_tick_interval(self.tick_interval);
+        if self.show_value {
+            let label = LabelBuilder::new("")
+                .font_size(Self::VALUE_FONT_SIZE)
+                .align(TextAlign::Center)
+                .build_label(gui);
+            slider.value_label = Some(label);
+        }
+        slider.set_value(self.value);
+        self.node.build_widget(gui, slider)
+    }
+}
+impl Default for SliderBuilder {
+    fn default() -> Self {
+        SliderBuilder {
+            node: NodeBuilder::new().style(Style {
+                background_color: Some(Color::Gutter),
+                min_size: Slider::MIN_SIZE,
+                ..Default::default()
+            }),
+            vertical: false,
+            min: 0.0,
+            max: 1.0,
+            step: 0.0,
+            value: 0.0,
+            tick_interval: 0.0,
+            show_value: false,
+        }
     }
 }
 
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Always show the scrollbar.
+    #[default]
+    Scroll,
+    /// Only show the scrollbar when the content doesn't fit.
+    Auto,
+    /// Clip the content without a scrollbar.
+    Hidden,
+}
+
 pub struct ScrollArea {
     size: Option<Rc<Cell<Size>>>,
     scroll: Vector2D<f32, Pixel>,
+    auto_scrollbars: Vec<(NodeId, bool)>,
+    scrollbars: Vec<(WidgetId<Slider>, bool)>,
 }
 
 impl ScrollArea {
@@ -123,8 +427,14 @@ impl ScrollArea {
         ScrollArea {
             size: scroll_size,
             scroll: Vector2D::zero(),
+            auto_scrollbars: Vec::new(),
+            scrollbars: Vec::new(),
         }
     }
+    /// Scrollbars with [`OverflowPolicy::Auto`], and whether each one is vertical.
+    pub(crate) fn auto_scrollbars(&self) -> &[(NodeId, bool)] {
+        &self.auto_scrollbars
+    }
     pub fn scroll(&self) -> Vector2D<f32, Pixel> {
         self.scroll
     }
@@ -135,6 +445,40 @@ impl ScrollArea {
             self.scroll.x = scroll;
         }
     }
+    /// Offset applied to the children of this scroll area when drawn.
+    pub fn offset(&self, area: &Area) -> Vector {
+        self.scroll
+            .component_mul((area.content_rect.size.to_vector() - area.children_size.to_vector()).to_f32())
+            .to_i32()
+            .min(Vector::zero())
+    }
+    /// Scrolls by a mouse wheel movement in pixels, along the vertical axis if the children overflow it
+    /// and the horizontal axis otherwise. Returns false if the scroll area can't move any further in that
+    /// direction, so the wheel can scroll an enclosing scroll area instead.
+    pub(crate) fn scroll_wheel(&mut self, delta: f32, area: &Area, executor: &mut EventExecutor) -> bool {
+        let overflow = area.children_size.to_vector() - area.content_rect.size.to_vector();
+        let (vertical, overflow) = if overflow.y > 0 {
+            (true, overflow.y)
+        } else if overflow.x > 0 {
+            (false, overflow.x)
+        } else {
+            return false;
+        };
+        let scroll = if vertical { self.scroll.y } else { self.scroll.x };
+        let new_scroll = (scroll - delta / overflow as f32).clamp(0.0, 1.0);
+        if new_scroll == scroll {
+            return false;
+        }
+        self.set_scroll(new_scroll, vertical);
+        let set_scrollbar = EventFn::new_param(|gui: &mut Gui, (scrollbar, value): (WidgetId<Slider>, f32)| {
+            scrollbar.set_value(gui, value);
+        });
+        for &(scrollbar, _) in self.scrollbars.iter().filter(|(_, axis)| *axis == vertical) {
+            executor.queue(set_scrollbar.clone(), Some(Box::new((scrollbar, new_scroll))));
+        }
+        executor.request_redraw();
+        true
+    }
 }
 impl Widget for ScrollArea {
     fn layout(&mut self, area: &Area) {
@@ -143,12 +487,7 @@ impl Widget for ScrollArea {
         }
     }
     fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
-        renderer.push_scroll_area(
-            area.content_rect,
-            self.scroll
-                .component_mul((area.content_rect.size.to_vector() - area.children_size.to_vector()).to_f32())
-                .to_i32(),
-        );
+        renderer.push_scroll_area(area.content_rect, self.offset(area));
     }
 }
 impl WidgetId<ScrollArea> {
@@ -168,6 +507,8 @@ pub struct ScrollAreaBuilder {
     children: Vec<NodeId>,
     size: Rc<Cell<Size>>,
     area: WidgetId<ScrollArea>,
+    horizontal: Option<OverflowPolicy>,
+    vertical: Option<OverflowPolicy>,
     horizontal_scrollbar: Option<WidgetId<Slider>>,
     vertical_scrollbar: Option<WidgetId<Slider>>,
 }
@@ -181,6 +522,8 @@ impl ScrollAreaBuilder {
             children: Vec::new(),
             size,
             area,
+            horizontal: None,
+            vertical: None,
             horizontal_scrollbar: None,
             vertical_scrollbar: None,
         }
@@ -197,52 +540,71 @@ impl ScrollAreaBuilder {
         self.children.extend(iter);
         self
     }
-    pub fn horizontal_scroll(mut self, gui: &mut Gui) -> Self {
-        let area = self.area;
-        let scrollbar = Slider::new_scrollbar(false, Some(self.size.clone()), move |gui, value| {
-            area.set_scroll(gui, value, false);
-        });
-        self.horizontal_scrollbar = Some(gui.create_widget(Slider::scrollbar_style(), scrollbar));
+    pub fn horizontal_scroll(self, gui: &mut Gui) -> Self {
+        self.horizontal_overflow(gui, OverflowPolicy::Scroll)
+    }
+    pub fn vertical_scroll(self, gui: &mut Gui) -> Self {
+        self.vertical_overflow(gui, OverflowPolicy::Scroll)
+    }
+    pub fn horizontal_overflow(mut self, gui: &mut Gui, policy: OverflowPolicy) -> Self {
+        self.horizontal = Some(policy);
+        if policy != OverflowPolicy::Hidden {
+            let area = self.area;
+            let scrollbar = Slider::new_scrollbar(false, Some(self.size.clone()), move |gui, value| {
+                area.set_scroll(gui, value, false);
+            });
+            self.horizontal_scrollbar = Some(gui.create_widget(Slider::scrollbar_style(false), scrollbar));
+        }
         self
     }
-    pub fn vertical_scroll(mut self, gui: &mut Gui) -> Self {
-        let area = self.area;
-        let scrollbar = Slider::new_scrollbar(true, Some(self.size.clone()), move |gui, value| {
-            area.set_scroll(gui, value, true);
-        });
-        self.vertical_scrollbar = Some(gui.create_widget(Slider::scrollbar_style(), scrollbar));
+    pub fn vertical_overflow(mut self, gui: &mut Gui, policy: OverflowPolicy) -> Self {
+        self.vertical = Some(policy);
+        if policy != OverflowPolicy::Hidden {
+            let area = self.area;
+            let scrollbar = Slider::new_scrollbar(true, Some(self.size.clone()), move |gui, value| {
+                area.set_scroll(gui, value, true);
+            });
+            self.vertical_scrollbar = Some(gui.create_widget(Slider::scrollbar_style(false), scrollbar));
+        }
         self
     }
     pub fn build(self, gui: &mut Gui) -> NodeId {
-        assert!(
-            self.horizontal_scrollbar.is_some() || self.vertical_scrollbar.is_some(),
-            "no scrollbars"
-        );
+        assert!(self.horizontal.is_some() || self.vertical.is_some(), "no overflow axis");
         gui.modify_style(self.area, |style| {
-            style.overflow.x = self.horizontal_scrollbar.is_some();
-            style.overflow.y = self.vertical_scrollbar.is_some();
+            style.overflow.x = self.horizontal.is_some();
+            style.overflow.y = self.vertical.is_some();
         });
-        let container = if let Some(horizontal_scrollbar) = self.horizontal_scrollbar {
-            let container = gui.create_node(Style {
-                direction: Direction::ColumnReverse,
-                border: SideOffsets::new_all_same(1),
-                ..Default::default()
-            });
-            gui.add_child(container, horizontal_scrollbar);
-            gui.add_child(container, self.area);
-            container
-        } else if let Some(vertical_scrollbar) = self.vertical_scrollbar {
-            let container = gui.create_node(Style {
-                direction: Direction::RowReverse,
-                border: SideOffsets::new_all_same(1),
-                ..Default::default()
-            });
-            gui.add_child(container, vertical_scrollbar);
-            gui.add_child(container, self.area);
-            container
-        } else {
-            todo!()
-        };
+        let mut auto_scrollbars = Vec::new();
+        if let Some(scrollbar) = self.horizontal_scrollbar
+            && self.horizontal == Some(OverflowPolicy::Auto)
+        {
+            auto_scrollbars.push((scrollbar.into(), false));
+        }
+        if let Some(scrollbar) = self.vertical_scrollbar
+            && self.vertical == Some(OverflowPolicy::Auto)
+        {
+            auto_scrollbars.push((scrollbar.into(), true));
+        }
+        let scrollbars = [(self.horizontal_scrollbar, false), (self.vertical_scrollbar, true)]
+            .into_iter()
+            .filter_map(|(scrollbar, vertical)| Some((scrollbar?, vertical)))
+            .collect();
+        let area = gui.get_widget_mut(self.area).unwrap();
+        area.auto_scrollbars = auto_scrollbars;
+        area.scrollbars = scrollbars;
+        let container = gui.create_node(Style {
+            direction: if self.horizontal_scrollbar.is_some() {
+                Direction::ColumnReverse
+            } else {
+                Direction::RowReverse
+            },
+            border: SideOffsets::new_all_same(1),
+            ..Default::default()
+        });
+        if let Some(scrollbar) = self.horizontal_scrollbar.or(self.vertical_scrollbar) {
+            gui.add_child(container, scrollbar);
+        }
+        gui.add_child(container, self.area);
         gui.set_node_children(self.area, self.children);
         if let Some(parent) = self.parent {
             gui.add_child(parent, container);
@@ -250,3 +612,204 @@ impl ScrollAreaBuilder {
         container
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{TestButton, TestEvent, fixed_node, scroll_root, send, test_gui};
+
+    /// Lays out a 100x100 vertical scroll area holding `rows` rows 40 pixels tall, and returns whether its
+    /// scrollbar is visible and the width left for the rows.
+    fn layout_rows(policy: OverflowPolicy, rows: usize) -> (bool, i32) {
+        let mut gui = test_gui(Size::new(100, 100));
+        let rows = (0..rows).map(|_| fixed_node(&mut gui, Size::new(20, 40))).collect();
+        let area = scroll_root(&mut gui, policy, rows);
+        gui.layout();
+        let scrollbar_visible = gui.children[gui.root]
+            .iter()
+            .any(|&node| node != area.into() && !gui.nodes[node].area.hidden);
+        (scrollbar_visible, gui.nodes[area.into()].area.content_rect.width())
+    }
+
+    #[test]
+    fn overflow_policies() {
+        let (_, full_width) = layout_rows(OverflowPolicy::Hidden, 1);
+        assert_eq!(full_width, 98);
+        let (_, narrow_width) = layout_rows(OverflowPolicy::Scroll, 1);
+        assert!(narrow_width < full_width);
+
+        assert_eq!(layout_rows(OverflowPolicy::Scroll, 1), (true, narrow_width));
+        assert_eq!(layout_rows(OverflowPolicy::Scroll, 5), (true, narrow_width));
+        assert_eq!(layout_rows(OverflowPolicy::Auto, 1), (false, full_width));
+        assert_eq!(layout_rows(OverflowPolicy::Auto, 5), (true, narrow_width));
+        assert_eq!(layout_rows(OverflowPolicy::Hidden, 1), (false, full_width));
+        assert_eq!(layout_rows(OverflowPolicy::Hidden, 5), (false, full_width));
+    }
+
+    #[test]
+    fn tiny_slider_keeps_value() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let changes = Rc::new(Cell::new(0));
+        let slider = SliderBuilder::new()
+            .value(0.5)
+            .modify_style(|style| {
+                style.min_size = Size::new(20, 10);
+            })
+            .build(&mut gui, {
+                let changes = changes.clone();
+                move |_: &mut Gui, _| changes.set(changes.get() + 1)
+            });
+        let root = NodeBuilder::new().child(slider).build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        assert_eq!(gui.nodes[slider.into()].area.content_rect.width(), 20);
+
+        send(&mut gui, TestEvent::MouseMotion(Point::new(5, 5)));
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(true)));
+        assert!(gui.get_widget(slider).unwrap().state == ButtonState::Press);
+        for x in [0, 15, 60, -40] {
+            send(&mut gui, TestEvent::MouseMotion(Point::new(x, 5)));
+            let value = slider.value(&gui);
+            assert!(value.is_finite() && (0.0..=1.0).contains(&value));
+            assert_eq!(value, 0.5);
+        }
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(false)));
+        assert_eq!(changes.get(), 0);
+    }
+
+    #[test]
+    fn wheel_scrolls_area_and_scrollbar() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let rows = (0..4).map(|_| fixed_node(&mut gui, Size::new(20, 50))).collect();
+        let area = scroll_root(&mut gui, OverflowPolicy::Scroll, rows);
+        gui.layout();
+        let scrollbar = gui.widgets_mut::<Slider>().next().unwrap().0;
+        send(&mut gui, TestEvent::MouseMotion(Point::new(10, 10)));
+
+        // The 200 pixel column overflows the 98 pixel viewport by 102 pixels.
+        send(&mut gui, TestEvent::MouseWheel(-51.0));
+        assert_eq!(area.scroll(&gui), Vector2D::new(0.0, 0.5));
+        assert_eq!(scrollbar.value(&gui), 0.5);
+        send(&mut gui, TestEvent::MouseWheel(-1000.0));
+        assert_eq!(area.scroll(&gui), Vector2D::new(0.0, 1.0));
+        assert_eq!(scrollbar.value(&gui), 1.0);
+        send(&mut gui, TestEvent::MouseWheel(102.0));
+        assert_eq!(area.scroll(&gui), Vector2D::new(0.0, 0.0));
+        assert_eq!(scrollbar.value(&gui), 0.0);
+
+        // Away from the scroll area the wheel does nothing.
+        send(&mut gui, TestEvent::MouseMotion(Point::new(200, 10)));
+        send(&mut gui, TestEvent::MouseWheel(-51.0));
+        assert_eq!(area.scroll(&gui), Vector2D::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn range_and_step_snap_values() {
+        let mut gui = test_gui(Size::new(200, 100));
+        let changed = Rc::new(Cell::new(None));
+        // The 32 pixel handle leaves a 100 pixel track.
+        let slider = SliderBuilder::new()
+            .range(0.0, 10.0)
+            .step(2.0)
+            .modify_style(|style| style.min_size = Size::new(132, 32))
+            .build(&mut gui, {
+                let changed = changed.clone();
+                move |_: &mut Gui, value| changed.set(Some(value))
+            });
+        let root = NodeBuilder::new()
+            .style(Style {
+                cross_align: Align::Start,
+                ..Default::default()
+            })
+            .child(slider)
+            .build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        let handle_x = |gui: &Gui| {
+            let area = &gui.nodes[slider.into()].area;
+            let slider = gui.get_widget(slider).unwrap();
+            slider.handle_rect(area, slider.handle_size(area), slider.value).min_x()
+        };
+
+        slider.set_value(&mut gui, 3.3);
+        assert_eq!(slider.value(&gui), 4.0);
+        assert_eq!(handle_x(&gui), 40);
+        slider.set_value(&mut gui, 11.0);
+        assert_eq!(slider.value(&gui), 10.0);
+        assert_eq!(handle_x(&gui), 100);
+        slider.set_value(&mut gui, -1.0);
+        assert_eq!(slider.value(&gui), 0.0);
+        assert_eq!(handle_x(&gui), 0);
+        assert_eq!(changed.take(), None);
+
+        // Dragging the handle center to 3.3 on the track reports the snapped value.
+        send(&mut gui, TestEvent::MouseMotion(Point::new(16, 16)));
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(true)));
+        send(&mut gui, TestEvent::MouseMotion(Point::new(16 + 33, 16)));
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(false)));
+        assert_eq!(changed.take(), Some(4.0));
+        assert_eq!(slider.value(&gui), 4.0);
+    }
+
+    #[test]
+    fn value_label_follows_drag() {
+        let mut gui = test_gui(Size::new(200, 100));
+        let slider = SliderBuilder::new()
+            .range(0.0, 10.0)
+            .step(0.5)
+            .value(2.0)
+            .ticks(1.0)
+            .show_value(true)
+            .modify_style(|style| style.min_size = Size::new(132, 32))
+            .build(&mut gui, |_: &mut Gui, _| {});
+        let root = NodeBuilder::new()
+            .style(Style {
+                cross_align: Align::Start,
+                ..Default::default()
+            })
+            .child(slider)
+            .build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        let label_text = |gui: &Gui| gui.get_widget(slider).unwrap().value_label.as_ref().unwrap().text();
+        assert_eq!(label_text(&gui), "2.0");
+
+        // The handle starts at 20 on the 100 pixel track, so its center is at 36.
+        send(&mut gui, TestEvent::MouseMotion(Point::new(36, 16)));
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(true)));
+        send(&mut gui, TestEvent::MouseMotion(Point::new(16 + 73, 16)));
+        assert_eq!(slider.value(&gui), 7.5);
+        assert_eq!(label_text(&gui), "7.5");
+        send(&mut gui, TestEvent::MouseMotion(Point::new(500, 16)));
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(false)));
+        assert_eq!(label_text(&gui), "10.0");
+    }
+
+    #[test]
+    fn hovering_slider_sets_cursor() {
+        let mut gui = test_gui(Size::new(200, 100));
+        let slider = SliderBuilder::new().build(&mut gui, |_: &mut Gui, _| {});
+        let root = NodeBuilder::new()
+            .style(Style {
+                cross_align: Align::Start,
+                ..Default::default()
+            })
+            .child(slider)
+            .build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+        assert_eq!(gui.screen_rect(slider).size, Slider::MIN_SIZE);
+
+        send(&mut gui, TestEvent::MouseMotion(Point::new(10, 10)));
+        assert_eq!(gui.cursor(), CursorIcon::Grab);
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(true)));
+        send(&mut gui, TestEvent::MouseMotion(Point::new(12, 10)));
+        assert_eq!(gui.cursor(), CursorIcon::Grabbing);
+        // The grabbed slider keeps its cursor when the pointer leaves it.
+        send(&mut gui, TestEvent::MouseMotion(Point::new(150, 80)));
+        assert_eq!(gui.cursor(), CursorIcon::Grabbing);
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(false)));
+        send(&mut gui, TestEvent::MouseMotion(Point::new(150, 81)));
+        assert_eq!(gui.cursor(), CursorIcon::Default);
+    }
+}
@@ -0,0 +1,142 @@
+use std::f32::consts::TAU;
+
+use euclid::{Box2D, Vector2D};
+
+use crate::{
+    render::{GuiRenderer, Quad},
+    *,
+};
+
+/// Loading indicator made of a ring of dots that fade out behind a rotating head.
+pub struct Spinner {
+    size: i32,
+    speed: f32,
+    rotation: f32,
+}
+
+impl Spinner {
+    const DOTS: usize = 8;
+    pub const DEFAULT_SIZE: i32 = 32;
+    /// `speed` is in turns per second.
+    pub fn new(size: i32, speed: f32) -> Self {
+        Spinner {
+            size,
+            speed,
+            rotation: 0.0,
+        }
+    }
+    /// Current rotation in turns, from 0 to 1.
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+}
+impl Widget for Spinner {
+    fn measure(&mut self, _available_space: Size) -> Size {
+        Size::splat(self.size)
+    }
+    fn update(&mut self, dt: f32) -> bool {
+        self.rotation = (self.rotation + self.speed * dt).rem_euclid(1.0);
+        true
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        let rect = area.content_rect;
+        let radius = (rect.width().min(rect.height()) as f32) / 2.0;
+        let dot_size = ((radius / 4.0) as i32).max(2);
+        let center = rect.center().to_f32();
+        let color = renderer.theme().color(Color::Accent);
+        let head = ((self.rotation * Self::DOTS as f32) as usize) % Self::DOTS;
+        for i in 0..Self::DOTS {
+            let angle = (i as f32 / Self::DOTS as f32) * TAU;
+            let offset = Vector2D::new(angle.cos(), angle.sin()) * (radius - dot_size as f32 / 2.0);
+            let min = (center + offset).to_i32() - Vector::splat(dot_size / 2);
+            let behind = (head + Self::DOTS - i) % Self::DOTS;
+            renderer.draw_theme_quad(Quad {
+                rect: Box2D::from_origin_and_size(min, Size::splat(dot_size)),
+                uv: GuiRenderer::UV_WHITE,
+                color: color.mul_alpha(1.0 - (behind as f32 / Self::DOTS as f32)),
+            });
+        }
+    }
+}
+
+#[must_use]
+pub struct SpinnerBuilder {
+    node: NodeBuilder,
+    size: i32,
+    speed: f32,
+}
+
+impl SpinnerBuilder {
+    pub fn new() -> Self {
+        SpinnerBuilder {
+            node: NodeBuilder::new(),
+            size: Spinner::DEFAULT_SIZE,
+            speed: 1.0,
+        }
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    pub fn size(mut self, size: i32) -> Self {
+        self.size = size;
+        self
+    }
+    /// Turns per second.
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+    pub fn build(self, gui: &mut Gui) -> WidgetId<Spinner> {
+        self.node.build_widget(gui, Spinner::new(self.size, self.speed))
+    }
+}
+impl WidgetBuilder for SpinnerBuilder {
+    type Widget = Spinner;
+    type Args = ();
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        SpinnerBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        SpinnerBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, _args: Self::Args) -> WidgetId<Spinner> {
+        SpinnerBuilder::build(self, gui)
+    }
+}
+impl Default for SpinnerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_gui;
+
+    #[test]
+    fn spinner_rotates_every_update() {
+        let mut gui = test_gui(Size::new(100, 100));
+        let spinner = SpinnerBuilder::new().speed(0.5).build(&mut gui);
+        let root = NodeBuilder::new().child(spinner).build(&mut gui);
+        gui.set_root(root);
+        gui.layout();
+
+        assert!(gui.update(0.5));
+        assert_eq!(gui.get_widget(spinner).unwrap().rotation(), 0.25);
+        assert!(gui.update(1.5));
+        assert_eq!(gui.get_widget(spinner).unwrap().rotation(), 0.0);
+        assert!(gui.needs_redraw());
+    }
+}
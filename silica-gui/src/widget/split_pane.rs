@@ -0,0 +1,289 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    render::{GuiRenderer, Quad},
+    *,
+};
+
+struct SplitPaneState {
+    vertical: bool,
+    fraction: Cell<f32>,
+    min_pane_size: i32,
+    first: NodeId,
+    /// Content rect of the split pane as of the last layout.
+    rect: Cell<Rect>,
+}
+
+impl SplitPaneState {
+    fn main_size(&self, size: Size) -> i32 {
+        if self.vertical { size.height } else { size.width }
+    }
+    /// Clamps a fraction so that neither pane is smaller than the minimum size.
+    fn clamp_fraction(&self, fraction: f32) -> f32 {
+        let size = self.main_size(self.rect.get().size);
+        if size <= 0 {
+            return fraction.clamp(0.0, 1.0);
+        }
+        let min = self.min_pane_size as f32 / size as f32;
+        let max = (size - SplitPane::GUTTER_SIZE - self.min_pane_size) as f32 / size as f32;
+        fraction.min(max).max(min).clamp(0.0, 1.0)
+    }
+    fn set_fraction(&self, gui: &mut Gui, fraction: f32) {
+        self.fraction.set(fraction);
+        let size_fraction = if self.vertical {
+            (None, Some(fraction))
+        } else {
+            (Some(fraction), None)
+        };
+        gui.modify_style(self.first, |style| style.size_fraction = Some(size_fraction));
+    }
+}
+
+struct SplitPaneGutter {
+    state: Rc<SplitPaneState>,
+    button_state: ButtonState,
+}
+
+impl Widget for SplitPaneGutter {
+    fn cursor(&self, _input: &GuiInput, _area: &Area) -> Option<CursorIcon> {
+        if self.state.vertical {
+            Some(CursorIcon::RowResize)
+        } else {
+            Some(CursorIcon::ColResize)
+        }
+    }
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        let state_input = self.button_state.handle_input(input, None, area.content_rect);
+        if state_input.changed {
+            executor.request_redraw();
+        }
+        if self.button_state != ButtonState::Press {
+            return state_input.action;
+        }
+        let rect = self.state.rect.get();
+        let offset = (input.pointer - rect.origin) - Vector::splat(SplitPane::GUTTER_SIZE / 2);
+        let offset = if self.state.vertical { offset.y } else { offset.x };
+        let size = self.state.main_size(rect.size);
+        if size > 0 {
+            let fraction = self.state.clamp_fraction(offset as f32 / size as f32);
+            if fraction != self.state.fraction.get() {
+                let set_fraction = EventFn::new_param(|gui: &mut Gui, (state, fraction): (Rc<SplitPaneState>, f32)| {
+                    state.set_fraction(gui, fraction);
+                });
+                executor.queue(set_fraction, Some(Box::new((self.state.clone(), fraction))));
+            }
+        }
+        InputAction::Grab
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        let theme = renderer.theme();
+        theme.draw_button(renderer, area.content_rect, ButtonStyle::Flat, false, self.button_state);
+        let center = area.content_rect.center();
+        let rect = if self.state.vertical {
+            Rect::new(
+                Point::new(area.content_rect.min_x(), center.y),
+                Size::new(area.content_rect.width(), 1),
+            )
+        } else {
+            Rect::new(
+                Point::new(center.x, area.content_rect.min_y()),
+                Size::new(1, area.content_rect.height()),
+            )
+        };
+        renderer.draw_theme_quad(Quad {
+            rect: rect.to_box2d(),
+            uv: GuiRenderer::UV_WHITE,
+            color: theme.color(Color::Border),
+        });
+    }
+}
+
+/// Lays out two panes side by side, or one above the other, with a gutter between them that can be
+/// dragged to move the split. The split is a fraction of the split pane's size given to the first pane,
+/// applied with [`Style::size_fraction`]; the second pane grows to fill the rest.
+pub struct SplitPane {
+    state: Rc<SplitPaneState>,
+}
+
+impl SplitPane {
+    const GUTTER_SIZE: i32 = 8;
+    pub fn fraction(&self) -> f32 {
+        self.state.fraction.get()
+    }
+}
+impl Widget for SplitPane {
+    fn layout(&mut self, area: &Area) {
+        self.state.rect.set(area.content_rect);
+    }
+    fn draw(&mut self, _renderer: &mut GuiRenderer, _area: &Area) {}
+}
+impl WidgetId<SplitPane> {
+    pub fn fraction(&self, gui: &Gui) -> f32 {
+        gui.get_widget(*self)
+            .map(|split_pane| split_pane.fraction())
+            .unwrap_or(0.5)
+    }
+    /// Moves the split, keeping both panes at least the minimum size.
+    pub fn set_fraction(&self, gui: &mut Gui, fraction: f32) {
+        if let Some(split_pane) = gui.get_widget(*self) {
+            let state = split_pane.state.clone();
+            let fraction = state.clamp_fraction(fraction);
+            state.set_fraction(gui, fraction);
+        }
+    }
+}
+
+#[must_use]
+pub struct SplitPaneBuilder {
+    node: NodeBuilder,
+    vertical: bool,
+    fraction: f32,
+    min_pane_size: i32,
+}
+
+impl SplitPaneBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    /// Places the panes one above the other instead of side by side.
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+    /// The fraction of the split pane's size given to the first pane.
+    pub fn fraction(mut self, fraction: f32) -> Self {
+        self.fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+    /// The smallest size either pane can be dragged to along the split direction.
+    pub fn min_pane_size(mut self, min_pane_size: i32) -> Self {
+        self.min_pane_size = min_pane_size.max(0);
+        self
+    }
+    /// Builds the split pane around two existing nodes. The first pane's size fraction is replaced by the
+    /// split, and only the second pane grows.
+    pub fn build(self, gui: &mut Gui, first: impl Into<NodeId>, second: impl Into<NodeId>) -> WidgetId<SplitPane> {
+        let first = first.into();
+        let second = second.into();
+        let state = Rc::new(SplitPaneState {
+            vertical: self.vertical,
+            fraction: Cell::new(self.fraction),
+            min_pane_size: self.min_pane_size,
+            first,
+            rect: Cell::new(Rect::zero()),
+        });
+        state.set_fraction(gui, self.fraction);
+        gui.modify_style(first, |style| style.grow = false);
+        gui.modify_style(second, |style| style.grow = true);
+        let gutter = NodeBuilder::new()
+            .style(Style {
+                min_size: Size::splat(SplitPane::GUTTER_SIZE),
+                max_size: if self.vertical {
+                    Size::new(i32::MAX, SplitPane::GUTTER_SIZE)
+                } else {
+                    Size::new(SplitPane::GUTTER_SIZE, i32::MAX)
+                },
+                ..Default::default()
+            })
+            .build_widget(
+                gui,
+                SplitPaneGutter {
+                    state: state.clone(),
+                    button_state: ButtonState::Normal,
+                },
+            );
+        self.node
+            .modify_style(|style| {
+                style.direction = if self.vertical {
+                    Direction::Column
+                } else {
+                    Direction::Row
+                };
+            })
+            .child(first)
+            .child(gutter)
+            .child(second)
+            .build_widget(gui, SplitPane { state })
+    }
+}
+/// Builds a split pane from its first and second pane.
+impl WidgetBuilder for SplitPaneBuilder {
+    type Widget = SplitPane;
+    type Args = (NodeId, NodeId);
+    fn modify_style<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        SplitPaneBuilder::modify_style(self, f)
+    }
+    fn parent(self, parent: impl Into<NodeId>) -> Self {
+        SplitPaneBuilder::parent(self, parent)
+    }
+    fn build(self, gui: &mut Gui, (first, second): Self::Args) -> WidgetId<SplitPane> {
+        SplitPaneBuilder::build(self, gui, first, second)
+    }
+}
+impl Default for SplitPaneBuilder {
+    fn default() -> Self {
+        SplitPaneBuilder {
+            node: NodeBuilder::new().style(Style {
+                grow: true,
+                ..Default::default()
+            }),
+            vertical: false,
+            fraction: 0.5,
+            min_pane_size: 32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{TestButton, TestEvent, send, test_gui};
+
+    #[test]
+    fn dragging_gutter_moves_split() {
+        let mut gui = test_gui(Size::new(200, 100));
+        let first = gui.create_node(Style::default());
+        let second = gui.create_node(Style::default());
+        let split_pane = SplitPaneBuilder::new().build(&mut gui, first, second);
+        gui.set_root(split_pane);
+        gui.layout();
+        let widths = |gui: &Gui| (gui.screen_rect(first).width(), gui.screen_rect(second).width());
+        assert_eq!(widths(&gui), (100, 92));
+
+        // Pressing at the gutter's center and dragging it to 54 puts the first pane's edge at 50.
+        send(&mut gui, TestEvent::MouseMotion(Point::new(104, 50)));
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(true)));
+        send(&mut gui, TestEvent::MouseMotion(Point::new(54, 50)));
+        gui.layout();
+        assert_eq!(split_pane.fraction(&gui), 0.25);
+        assert_eq!(widths(&gui), (50, 142));
+
+        // Neither pane can be dragged below the minimum size of 32.
+        send(&mut gui, TestEvent::MouseMotion(Point::new(0, 50)));
+        gui.layout();
+        assert_eq!(widths(&gui), (32, 160));
+        send(&mut gui, TestEvent::MouseMotion(Point::new(1000, 50)));
+        send(&mut gui, TestEvent::MouseButton(TestButton::primary(false)));
+        gui.layout();
+        assert_eq!(widths(&gui), (160, 32));
+
+        split_pane.set_fraction(&mut gui, 0.0);
+        gui.layout();
+        assert_eq!(split_pane.fraction(&gui), 0.16);
+        assert_eq!(widths(&gui), (32, 160));
+    }
+}
@@ -0,0 +1,207 @@
+use std::rc::Rc;
+
+use crate::*;
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// Sized to the widest cell in the column.
+    #[default]
+    Auto,
+    Fixed(i32),
+    /// Shares the space left over by the other columns.
+    Grow,
+}
+
+struct Column {
+    header: String,
+    width: ColumnWidth,
+    align: Align,
+}
+
+#[must_use]
+#[derive(Default)]
+pub struct TableBuilder {
+    node: NodeBuilder,
+    columns: Vec<Column>,
+    rows: Vec<Vec<NodeId>>,
+}
+
+impl TableBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    pub fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    pub fn column(mut self, header: &str, width: ColumnWidth, align: Align) -> Self {
+        self.columns.push(Column {
+            header: header.to_string(),
+            width,
+            align,
+        });
+        self
+    }
+    pub fn row(mut self, cells: impl IntoIterator<Item = NodeId>) -> Self {
+        self.rows.push(cells.into_iter().collect());
+        self
+    }
+    fn cell_style(column: &Column) -> Style {
+        let mut style = Style {
+            main_align: column.align,
+            cross_align: Align::Center,
+            padding: SideOffsets::new(2, 4, 2, 4),
+            ..Default::default()
+        };
+        if let ColumnWidth::Fixed(width) = column.width {
+            style.min_size.width = width;
+            style.max_size.width = width;
+        }
+        style
+    }
+    fn build_with_headers(self, gui: &mut Gui, headers: Vec<NodeId>) -> NodeId {
+        let mut cells = Vec::new();
+        for (column, header) in self.columns.iter().zip(headers) {
+            let mut style = Self::cell_style(column);
+            style.grow = column.width == ColumnWidth::Grow;
+            style.cross_align = Align::Stretch;
+            style.padding = SideOffsets::zero();
+            cells.push(NodeBuilder::new().style(style).child(header).build(gui));
+        }
+        for row in self.rows {
+            assert_eq!(row.len(), self.columns.len(), "table row has wrong number of cells");
+            for (column, cell) in self.columns.iter().zip(row) {
+                cells.push(
+                    NodeBuilder::new()
+                        .style(Self::cell_style(column))
+                        .child(cell)
+                        .build(gui),
+                );
+            }
+        }
+        let columns = self.columns.len();
+        self.node
+            .modify_style(|style| style.layout = Layout::Grid(columns))
+            .children(cells)
+            .build(gui)
+    }
+    pub fn build(self, gui: &mut Gui) -> NodeId {
+        let headers = self
+            .columns
+            .iter()
+            .map(|column| {
+                LabelBuilder::new(&column.header)
+                    .style(Style {
+                        margin: SideOffsets::new(2, 4, 2, 4),
+                        ..Default::default()
+                    })
+                    .build(gui)
+                    .into()
+            })
+            .collect();
+        self.build_with_headers(gui, headers)
+    }
+    /// Builds the table with clickable headers, which call `on_sort` with the column index.
+    pub fn build_sortable<C, F>(self, gui: &mut Gui, on_sort: F) -> NodeId
+    where
+        C: 'static,
+        F: Fn(&mut C, usize) + 'static,
+    {
+        let on_sort = Rc::new(on_sort);
+        let headers = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let on_sort = on_sort.clone();
+                ButtonBuilder::new()
+                    .modify_style(|style| {
+                        style.min_size.width = 0;
+                        style.grow = true;
+                    })
+                    .button_style(ButtonStyle::Flat)
+                    .label(gui, &column.header)
+                    .build(gui, move |context: &mut C| on_sort(context, index))
+                    .into()
+            })
+            .collect();
+        self.build_with_headers(gui, headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::tests::{click, test_gui};
+
+    fn cell(gui: &mut Gui, width: i32) -> NodeId {
+        NodeBuilder::new()
+            .modify_style(|style| {
+                style.min_size = Size::new(width, 10);
+                style.max_size = Size::new(width, 10);
+            })
+            .build(gui)
+    }
+
+    #[test]
+    fn columns_align_cells() {
+        let mut gui = test_gui(Size::new(400, 300));
+        let rows = [
+            [cell(&mut gui, 20), cell(&mut gui, 30)],
+            [cell(&mut gui, 40), cell(&mut gui, 10)],
+        ];
+        let table = rows
+            .iter()
+            .fold(
+                TableBuilder::new()
+                    .modify_style(|style| style.cross_align = Align::Start)
+                    .column("Name", ColumnWidth::Fixed(80), Align::End)
+                    .column("Value", ColumnWidth::Auto, Align::Start),
+                |table, row| table.row(row.iter().copied()),
+            )
+            .build(&mut gui);
+        gui.set_root(table);
+        gui.layout();
+
+        let rect = |node: NodeId| gui.nodes[node].area.background_rect;
+        // Right aligned in the fixed column, and left aligned in the next one. The fixed width doesn't
+        // include the cells' 4 pixel padding.
+        assert_eq!(rect(rows[0][0]).max_x(), rect(rows[1][0]).max_x());
+        assert_eq!(rect(rows[0][0]).max_x(), 4 + 80);
+        assert_eq!(rect(rows[0][1]).min_x(), rect(rows[1][1]).min_x());
+        assert_eq!(rect(rows[0][1]).min_x(), 88 + 4);
+        // Rows line up too.
+        assert_eq!(rect(rows[0][0]).min_y(), rect(rows[0][1]).min_y());
+        assert!(rect(rows[1][0]).min_y() > rect(rows[0][0]).max_y());
+    }
+
+    #[test]
+    fn clicking_header_sorts() {
+        let mut gui = test_gui(Size::new(400, 300));
+        let sorted = Rc::new(RefCell::new(Vec::new()));
+        let row = [cell(&mut gui, 20), cell(&mut gui, 20)];
+        let table = TableBuilder::new()
+            .column("Name", ColumnWidth::Fixed(80), Align::Start)
+            .column("Value", ColumnWidth::Fixed(80), Align::Start)
+            .row(row)
+            .build_sortable(&mut gui, {
+                let sorted = sorted.clone();
+                move |_: &mut Gui, column: usize| sorted.borrow_mut().push(column)
+            });
+        gui.set_root(table);
+        gui.layout();
+        let header = gui.children[table][1];
+        let point = gui.screen_rect(header).center();
+        click(&mut gui, point);
+        assert_eq!(*sorted.borrow(), [1]);
+    }
+}
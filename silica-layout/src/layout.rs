@@ -1,46 +1,129 @@
 use crate::*;
 
+/// Children that aren't absolutely positioned.
+fn flow_children<Id: Key, Widget>(
+    nodes: &SlotMap<Id, Node<Id, Widget>>,
+    children: &SecondaryMap<Id, Vec<Id>>,
+    id: Id,
+) -> Vec<Id> {
+    children
+        .get(id)
+        .map(|child_ids| {
+            child_ids
+                .iter()
+                .copied()
+                .filter(|child_id| nodes[*child_id].style.position.is_none())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 pub struct BoxLayout;
 
 impl BoxLayout {
+    fn main_size(horizontal: bool, size: Size) -> i32 {
+        if horizontal { size.width } else { size.height }
+    }
+    /// Splits the children into lines that each fit within `main_space`.
+    fn wrap_lines<Id: Key, Widget>(
+        nodes: &SlotMap<Id, Node<Id, Widget>>,
+        child_ids: Vec<Id>,
+        horizontal: bool,
+        gap: i32,
+        main_space: i32,
+    ) -> Vec<Vec<Id>> {
+        let mut lines: Vec<Vec<Id>> = Vec::new();
+        let mut line_size: i32 = 0;
+        for child_id in child_ids {
+            let child_size = Self::main_size(horizontal, nodes[child_id].area.measured_size);
+            match lines.last_mut() {
+                Some(line) if line_size.saturating_add(gap).saturating_add(child_size) <= main_space => {
+                    line_size = line_size.saturating_add(gap).saturating_add(child_size);
+                    line.push(child_id);
+                }
+                _ => {
+                    line_size = child_size;
+                    lines.push(vec![child_id]);
+                }
+            }
+        }
+        lines
+    }
+    fn measure_line<Id: Key, Widget>(
+        nodes: &SlotMap<Id, Node<Id, Widget>>,
+        child_ids: &[Id],
+        horizontal: bool,
+        gap: i32,
+    ) -> Size {
+        let mut size = Size::zero();
+        for child_id in child_ids.iter() {
+            let child_size = nodes[*child_id].area.measured_size;
+            if horizontal {
+                if size.width > 0 {
+                    size.width = size.width.saturating_add(gap);
+                }
+                size.width = size.width.saturating_add(child_size.width);
+                size.height = size.height.max(child_size.height);
+            } else {
+                size.width = size.width.max(child_size.width);
+                if size.height > 0 {
+                    size.height = size.height.saturating_add(gap);
+                }
+                size.height = size.height.saturating_add(child_size.height);
+            }
+        }
+        size
+    }
     pub fn measure<Id: Key, Widget: LayoutWidget>(
         nodes: &mut SlotMap<Id, Node<Id, Widget>>,
         children: &SecondaryMap<Id, Vec<Id>>,
         id: Id,
         mut available_space: Size,
     ) -> Size {
-        let child_ids = if let Some(child_ids) = children.get(id) {
-            child_ids
-        } else {
-            return Size::zero();
-        };
+        let child_ids = flow_children(nodes, children, id);
         let style = &nodes[id].style;
         let direction = style.direction;
         let gap = style.gap;
-        let mut size = Size::zero();
+        let main_space = Self::main_size(direction.horizontal(), available_space);
+        if style.wrap && main_space != i32::MAX {
+            let cross_gap = style.cross_gap();
+            for child_id in child_ids.iter() {
+                measure(nodes, children, *child_id, available_space);
+            }
+            let mut size = Size::zero();
+            for line in Self::wrap_lines(nodes, child_ids, direction.horizontal(), gap, main_space) {
+                let line_size = Self::measure_line(nodes, &line, direction.horizontal(), gap);
+                if direction.horizontal() {
+                    size.width = size.width.max(line_size.width);
+                    if size.height > 0 {
+                        size.height = size.height.saturating_add(cross_gap);
+                    }
+                    size.height = size.height.saturating_add(line_size.height);
+                } else {
+                    if size.width > 0 {
+                        size.width = size.width.saturating_add(cross_gap);
+                    }
+                    size.width = size.width.saturating_add(line_size.width);
+                    size.height = size.height.max(line_size.height);
+                }
+            }
+            return size;
+        }
         for child_id in child_ids.iter() {
             let child_size = measure(nodes, children, *child_id, available_space);
             if direction.horizontal() {
                 if available_space.width != i32::MAX {
-                    available_space.width -= child_size.width + gap;
+                    available_space.width = available_space
+                        .width
+                        .saturating_sub(child_size.width.saturating_add(gap));
                 }
-                if size.width > 0 {
-                    size.width += gap;
-                }
-                size.width += child_size.width;
-                size.height = size.height.max(child_size.height);
-            } else {
-                if available_space.height != i32::MAX {
-                    available_space.height -= child_size.height + gap;
-                }
-                size.width = size.width.max(child_size.width);
-                if size.height > 0 {
-                    size.height += gap;
-                }
-                size.height += child_size.height;
+            } else if available_space.height != i32::MAX {
+                available_space.height = available_space
+                    .height
+                    .saturating_sub(child_size.height.saturating_add(gap));
             }
         }
-        size
+        Self::measure_line(nodes, &child_ids, direction.horizontal(), gap)
     }
     pub fn layout<Id: Key, Widget: LayoutWidget>(
         nodes: &mut SlotMap<Id, Node<Id, Widget>>,
@@ -48,36 +131,90 @@ impl BoxLayout {
         id: Id,
         mut rect: Rect,
     ) {
-        let child_ids = if let Some(child_ids) = children.get(id) {
-            child_ids
-        } else {
+        let child_ids = flow_children(nodes, children, id);
+        let style = &nodes[id].style;
+        if !style.wrap {
+            Self::layout_line(nodes, children, id, &child_ids, rect);
             return;
+        }
+        let direction = style.direction;
+        let gap = style.gap;
+        let cross_gap = style.cross_gap();
+        let cross_direction = if direction.horizontal() {
+            Direction::Column
+        } else {
+            Direction::Row
         };
+        let main_space = Self::main_size(direction.horizontal(), rect.size);
+        for line in Self::wrap_lines(nodes, child_ids, direction.horizontal(), gap, main_space) {
+            let line_size = Self::measure_line(nodes, &line, direction.horizontal(), gap);
+            let cross_size = if direction.horizontal() {
+                Size::new(0, line_size.height)
+            } else {
+                Size::new(line_size.width, 0)
+            };
+            let line_rect = cross_direction.layout_area(&mut rect, cross_size, cross_gap);
+            Self::layout_line(nodes, children, id, &line, line_rect);
+        }
+    }
+    fn layout_line<Id: Key, Widget: LayoutWidget>(
+        nodes: &mut SlotMap<Id, Node<Id, Widget>>,
+        children: &SecondaryMap<Id, Vec<Id>>,
+        id: Id,
+        child_ids: &[Id],
+        mut rect: Rect,
+    ) {
         let style = &nodes[id].style;
-        let direction = style.direction;
+        let direction = style.layout_direction();
         let main_align = style.main_align;
         let cross_align = style.cross_align;
         let gap = style.gap;
         let mut used_size = Size::zero();
         let mut grow_count = 0;
+        let mut auto_margin_count = 0;
         for child_id in child_ids.iter() {
             let child = &nodes[*child_id];
             if direction.horizontal() {
-                used_size.width += child.area.measured_size.width + gap;
+                used_size.width = used_size
+                    .width
+                    .saturating_add(child.area.measured_size.width.saturating_add(gap));
             } else {
-                used_size.height += child.area.measured_size.height + gap;
+                used_size.height = used_size
+                    .height
+                    .saturating_add(child.area.measured_size.height.saturating_add(gap));
             }
             if child.style.grow {
                 grow_count += 1;
             }
+            if child.style.auto_margin {
+                auto_margin_count += 1;
+            }
         }
         let unused_size = if direction.horizontal() {
-            Size::new((rect.size.width - used_size.width + gap).max(0), 0)
+            Size::new(
+                rect.size
+                    .width
+                    .saturating_sub(used_size.width)
+                    .saturating_add(gap)
+                    .max(0),
+                0,
+            )
         } else {
-            Size::new(0, (rect.size.height - used_size.height + gap).max(0))
+            Size::new(
+                0,
+                rect.size
+                    .height
+                    .saturating_sub(used_size.height)
+                    .saturating_add(gap)
+                    .max(0),
+            )
         };
+        let mut auto_margin = Size::zero();
         let grow_space = if grow_count > 0 {
             unused_size / grow_count
+        } else if auto_margin_count > 0 {
+            auto_margin = unused_size / (auto_margin_count * 2);
+            Size::zero()
         } else {
             match main_align {
                 Align::End => {
@@ -96,7 +233,14 @@ impl BoxLayout {
             if child.style.grow {
                 child_size += grow_space;
             }
+            let child_margin = if child.style.auto_margin {
+                auto_margin
+            } else {
+                Size::zero()
+            };
+            direction.layout_area(&mut rect, child_margin, 0);
             let mut child_rect = direction.layout_area(&mut rect, child_size, gap);
+            direction.layout_area(&mut rect, child_margin, 0);
             child_rect = cross_align.align_area(!direction.horizontal(), child_rect, child_size);
             layout(nodes, children, *child_id, child_rect);
         }
@@ -115,6 +259,9 @@ impl StackLayout {
         let mut size = Size::zero();
         if let Some(child_ids) = children.get(id) {
             for child_id in child_ids.iter() {
+                if nodes[*child_id].style.position.is_some() {
+                    continue;
+                }
                 let child_size = measure(nodes, children, *child_id, available_space);
                 size = size.max(child_size);
             }
@@ -133,6 +280,9 @@ impl StackLayout {
         if let Some(child_ids) = children.get(id) {
             for child_id in child_ids.iter() {
                 let child = &nodes[*child_id];
+                if child.style.position.is_some() {
+                    continue;
+                }
                 let child_size = child.area.measured_size;
                 let grow_align = if child.style.grow { Align::Stretch } else { main_align };
                 let mut child_rect = grow_align.align_area(direction.horizontal(), rect, child_size);
@@ -146,62 +296,149 @@ impl StackLayout {
     }
 }
 
+struct GridCell<Id> {
+    id: Id,
+    row: usize,
+    column: usize,
+    span: usize,
+}
+
 pub struct GridLayout;
 
 impl GridLayout {
+    fn cells<Id: Key, Widget>(
+        nodes: &SlotMap<Id, Node<Id, Widget>>,
+        child_ids: Vec<Id>,
+        columns: usize,
+    ) -> Vec<GridCell<Id>> {
+        let mut cells = Vec::with_capacity(child_ids.len());
+        let (mut row, mut column) = (0, 0);
+        for id in child_ids {
+            let span = nodes[id].style.column_span.clamp(1, columns - column);
+            cells.push(GridCell { id, row, column, span });
+            column += span;
+            if column >= columns {
+                column = 0;
+                row += 1;
+            }
+        }
+        cells
+    }
+    /// Total size of `sizes` laid out in a line with `gap` between them, clamped to `i32::MAX`.
+    fn line_size(sizes: &[i32], gap: i32) -> i32 {
+        let gaps = gap.saturating_mul(i32::try_from(sizes.len()).unwrap_or(i32::MAX).saturating_sub(1));
+        sizes.iter().fold(gaps, |total, size| total.saturating_add(*size))
+    }
+    /// Size of `rows` rows of `row_size` with `gap` between them, clamped to `i32::MAX`.
+    fn rows_size(row_size: i32, rows: i32, gap: i32) -> i32 {
+        row_size
+            .saturating_mul(rows)
+            .saturating_add(gap.saturating_mul(rows - 1))
+    }
+    /// Number of rows the cells fill.
+    fn row_count<Id>(cells: &[GridCell<Id>]) -> Option<i32> {
+        cells
+            .last()
+            .map(|cell| i32::try_from(cell.row).unwrap_or(i32::MAX - 1) + 1)
+    }
+    /// Size of each column along the main axis. Cells spanning multiple columns that don't fit in them
+    /// widen the spanned columns evenly.
+    fn column_sizes<Id: Key, Widget>(
+        nodes: &SlotMap<Id, Node<Id, Widget>>,
+        cells: &[GridCell<Id>],
+        columns: usize,
+        horizontal: bool,
+        gap: i32,
+    ) -> Vec<i32> {
+        let mut sizes = vec![0; columns];
+        for cell in cells.iter().filter(|cell| cell.span == 1) {
+            let size = BoxLayout::main_size(horizontal, nodes[cell.id].area.measured_size);
+            sizes[cell.column] = sizes[cell.column].max(size);
+        }
+        for cell in cells.iter().filter(|cell| cell.span > 1) {
+            let span = cell.span as i32;
+            let spanned = &mut sizes[cell.column..(cell.column + cell.span)];
+            let current_size = Self::line_size(spanned, gap);
+            let missing_size =
+                BoxLayout::main_size(horizontal, nodes[cell.id].area.measured_size).saturating_sub(current_size);
+            if missing_size > 0 {
+                for (index, size) in spanned.iter_mut().enumerate() {
+                    *size = size.saturating_add(missing_size / span + i32::from((index as i32) < missing_size % span));
+                }
+            }
+        }
+        sizes.truncate(cells.iter().map(|cell| cell.column + cell.span).max().unwrap_or(0));
+        sizes
+    }
+    fn cross_size(horizontal: bool, size: Size) -> i32 {
+        BoxLayout::main_size(!horizontal, size)
+    }
     pub fn measure<Id: Key, Widget: LayoutWidget>(
         nodes: &mut SlotMap<Id, Node<Id, Widget>>,
         children: &SecondaryMap<Id, Vec<Id>>,
         id: Id,
-        mut available_space: Size,
+        available_space: Size,
         columns: usize,
     ) -> Size {
-        let child_ids = if let Some(child_ids) = children.get(id) {
-            child_ids
-        } else {
-            return Size::zero();
-        };
+        let child_ids = flow_children(nodes, children, id);
         let style = &nodes[id].style;
-        let direction = style.direction;
+        let horizontal = style.direction.horizontal();
         let gap = style.gap;
-        let mut size = Size::zero();
-        for column in 0..columns {
-            let mut child_size = Size::zero();
-            for i in (column..child_ids.len()).step_by(columns) {
-                child_size = child_size.max(measure(nodes, children, child_ids[i], available_space));
-            }
-            for i in (column..child_ids.len()).step_by(columns) {
-                nodes[child_ids[i]].area.measured_size = child_size;
+        let cross_gap = style.cross_gap();
+        let cells = Self::cells(nodes, child_ids, columns);
+        let Some(rows) = Self::row_count(&cells) else {
+            return Size::zero();
+        };
+        let shrink = |mut space: Size, size: i32| {
+            if horizontal && space.width != i32::MAX {
+                space.width = space.width.saturating_sub(size);
+            } else if !horizontal && space.height != i32::MAX {
+                space.height = space.height.saturating_sub(size);
             }
-            if direction.horizontal() {
-                if available_space.width != i32::MAX {
-                    available_space.width -= child_size.width + gap;
-                }
-                if size.width > 0 {
-                    size.width += gap;
-                }
-                size.width += child_size.width;
-                size.height = size.height.max(child_size.height);
-            } else {
-                if available_space.height != i32::MAX {
-                    available_space.height -= child_size.height + gap;
-                }
-                size.width = size.width.max(child_size.width);
-                if size.height > 0 {
-                    size.height += gap;
-                }
-                size.height += child_size.height;
+            space
+        };
+        let mut column_space = available_space;
+        let mut column_offsets = Vec::with_capacity(columns);
+        let mut column_offset: i32 = 0;
+        for column in 0..columns {
+            column_offsets.push(column_offset);
+            let mut column_size = 0;
+            for cell in cells.iter().filter(|cell| cell.column == column && cell.span == 1) {
+                let child_size = measure(nodes, children, cell.id, column_space);
+                column_size = column_size.max(BoxLayout::main_size(horizontal, child_size));
             }
+            column_space = shrink(column_space, column_size.saturating_add(gap));
+            column_offset = column_offset.saturating_add(column_size.saturating_add(gap));
         }
-        let rows = child_ids.len().div_ceil(columns) as i32;
-        if rows > 0 {
-            if direction.horizontal() {
-                size.height = (size.height * rows) + (gap * (rows - 1));
+        for cell in cells.iter().filter(|cell| cell.span > 1) {
+            measure(
+                nodes,
+                children,
+                cell.id,
+                shrink(available_space, column_offsets[cell.column]),
+            );
+        }
+        let column_sizes = Self::column_sizes(nodes, &cells, columns, horizontal, gap);
+        let row_size = cells
+            .iter()
+            .map(|cell| Self::cross_size(horizontal, nodes[cell.id].area.measured_size))
+            .max()
+            .unwrap_or(0);
+        for cell in cells.iter() {
+            let main_size = Self::line_size(&column_sizes[cell.column..(cell.column + cell.span)], gap);
+            nodes[cell.id].area.measured_size = if horizontal {
+                Size::new(main_size, row_size)
             } else {
-                size.width = (size.width * rows) + (gap * (rows - 1));
-            }
+                Size::new(row_size, main_size)
+            };
+        }
+        let main_size = Self::line_size(&column_sizes, gap);
+        let cross_size = Self::rows_size(row_size, rows, cross_gap);
+        if horizontal {
+            Size::new(main_size, cross_size)
+        } else {
+            Size::new(cross_size, main_size)
         }
-        size
     }
     pub fn layout<Id: Key, Widget: LayoutWidget>(
         nodes: &mut SlotMap<Id, Node<Id, Widget>>,
@@ -210,73 +447,59 @@ impl GridLayout {
         mut rect: Rect,
         columns: usize,
     ) {
-        let child_ids = if let Some(child_ids) = children.get(id) {
-            child_ids
-        } else {
+        let child_ids = flow_children(nodes, children, id);
+        let cells = Self::cells(nodes, child_ids, columns);
+        let Some(rows) = Self::row_count(&cells) else {
             return;
         };
-        let rows = child_ids.len().div_ceil(columns) as i32;
         let style = &nodes[id].style;
-        let direction = style.direction;
+        let direction = style.layout_direction();
+        let horizontal = direction.horizontal();
         let main_align = style.main_align;
+        let cross_align = style.cross_align;
         let gap = style.gap;
-        let first_child_size = child_ids
-            .first()
-            .map(|id| nodes[*id].area.measured_size)
-            .unwrap_or_default();
-        let row_size = if direction.horizontal() {
-            let row_size = first_child_size.height;
-            let unused_size = rect.size.height - ((row_size * rows) + (gap * (rows - 1)));
-            match style.cross_align {
-                Align::End => {
-                    rect.origin.y += unused_size;
-                    rect.size.height -= unused_size;
-                }
-                Align::Center => {
-                    rect.origin.y += unused_size / 2;
-                    rect.size.height -= unused_size / 2;
-                }
-                _ => {}
-            }
-            row_size
+        let cross_gap = style.cross_gap();
+        let mut column_sizes = Self::column_sizes(nodes, &cells, columns, horizontal, gap);
+        let row_size = cells
+            .iter()
+            .map(|cell| Self::cross_size(horizontal, nodes[cell.id].area.measured_size))
+            .max()
+            .unwrap_or(0);
+        let unused_size =
+            Self::cross_size(horizontal, rect.size).saturating_sub(Self::rows_size(row_size, rows, cross_gap));
+        let cross_offset = match cross_align {
+            Align::End => unused_size,
+            Align::Center => unused_size / 2,
+            _ => 0,
+        };
+        if horizontal {
+            rect.origin.y += cross_offset;
+            rect.size.height -= cross_offset;
         } else {
-            let row_size = first_child_size.width;
-            let unused_size = rect.size.width - ((row_size * rows) + (gap * (rows - 1)));
-            match style.cross_align {
-                Align::End => {
-                    rect.origin.x += unused_size;
-                    rect.size.width -= unused_size;
-                }
-                Align::Center => {
-                    rect.origin.x += unused_size / 2;
-                    rect.size.width -= unused_size / 2;
+            rect.origin.x += cross_offset;
+            rect.size.width -= cross_offset;
+        }
+        let mut grow_columns = vec![false; column_sizes.len()];
+        for cell in cells.iter().filter(|cell| cell.span == 1) {
+            grow_columns[cell.column] |= nodes[cell.id].style.grow;
+        }
+        let grow_count = grow_columns.iter().filter(|grow| **grow).count() as i32;
+        let used_size = Self::line_size(&column_sizes, gap);
+        let unused_size = BoxLayout::main_size(horizontal, rect.size)
+            .saturating_sub(used_size)
+            .max(0);
+        if grow_count > 0 {
+            for (size, grow) in column_sizes.iter_mut().zip(grow_columns) {
+                if grow {
+                    *size = size.saturating_add(unused_size / grow_count);
                 }
-                _ => {}
             }
-            row_size
-        };
-        let row_ids = &child_ids[0..columns.min(child_ids.len())];
-        let mut used_size = Size::zero();
-        let mut grow_count = 0;
-        for child_id in row_ids.iter() {
-            let child = &nodes[*child_id];
-            if direction.horizontal() {
-                used_size.width += child.area.measured_size.width + gap;
-            } else {
-                used_size.height += child.area.measured_size.height + gap;
-            }
-            if child.style.grow {
-                grow_count += 1;
-            }
-        }
-        let unused_size = if direction.horizontal() {
-            Size::new((rect.size.width - used_size.width + gap).max(0), 0)
-        } else {
-            Size::new(0, (rect.size.height - used_size.height + gap).max(0))
-        };
-        let grow_space = if grow_count > 0 {
-            unused_size / grow_count
         } else {
+            let unused_size = if horizontal {
+                Size::new(unused_size, 0)
+            } else {
+                Size::new(0, unused_size)
+            };
             match main_align {
                 Align::End => {
                     direction.layout_area(&mut rect, unused_size, 0);
@@ -286,28 +509,194 @@ impl GridLayout {
                 }
                 _ => {}
             }
-            Size::zero()
-        };
-        for (row_index, child_id) in row_ids.iter().enumerate() {
-            let child = &nodes[*child_id];
-            let mut child_size = child.area.measured_size;
-            if child.style.grow {
-                child_size += grow_space;
-            }
-            let mut child_rect = direction.layout_area(&mut rect, child_size, gap);
-            if direction.horizontal() {
+        }
+        let column_rects: Vec<Rect> = column_sizes
+            .iter()
+            .map(|size| {
+                let size = if horizontal {
+                    Size::new(*size, 0)
+                } else {
+                    Size::new(0, *size)
+                };
+                direction.layout_area(&mut rect, size, gap)
+            })
+            .collect();
+        for cell in cells.iter() {
+            let mut child_rect = column_rects[cell.column].union(&column_rects[cell.column + cell.span - 1]);
+            let row_offset = i32::try_from(cell.row)
+                .unwrap_or(i32::MAX)
+                .saturating_mul(row_size.saturating_add(cross_gap));
+            if horizontal {
+                child_rect.origin.y += row_offset;
                 child_rect.size.height = row_size;
             } else {
+                child_rect.origin.x += row_offset;
                 child_rect.size.width = row_size;
             }
-            for i in (row_index..child_ids.len()).step_by(columns) {
-                layout(nodes, children, child_ids[i], child_rect);
-                if direction.horizontal() {
-                    child_rect.origin.y += row_size + gap;
-                } else {
-                    child_rect.origin.x += row_size + gap;
-                }
-            }
+            layout(nodes, children, cell.id, child_rect);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{TestNodes, fixed, layout_children},
+        *,
+    };
+
+    fn grid(columns: usize, gap: i32, gap_cross: Option<i32>) -> Style {
+        Style {
+            layout: Layout::Grid(columns),
+            gap,
+            gap_cross,
+            main_align: Align::Start,
+            cross_align: Align::Start,
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn grid_rows_use_cross_gap() {
+        let area = Rect::new(Point::origin(), Size::new(200, 200));
+        let cells = layout_children(grid(2, 4, Some(10)), vec![fixed(Size::new(20, 10)); 4], area);
+        let origins: Vec<_> = cells.iter().map(|cell| cell.origin).collect();
+        assert_eq!(
+            origins,
+            [
+                Point::new(0, 0),
+                Point::new(24, 0),
+                Point::new(0, 20),
+                Point::new(24, 20)
+            ]
+        );
+        assert!(cells.iter().all(|cell| cell.size == Size::new(20, 10)));
+
+        // Without a cross gap, rows are spaced by the gap.
+        let cells = layout_children(grid(2, 4, None), vec![fixed(Size::new(20, 10)); 4], area);
+        assert_eq!(cells[2].origin, Point::new(0, 14));
+    }
+
+    #[test]
+    fn wrap_starts_new_line() {
+        let style = Style {
+            wrap: true,
+            gap: 4,
+            cross_align: Align::Start,
+            ..Default::default()
+        };
+        let area = Rect::new(Point::origin(), Size::new(100, 100));
+        let children = layout_children(style, vec![fixed(Size::new(40, 20)); 3], area);
+        let origins: Vec<_> = children.iter().map(|child| child.origin).collect();
+        assert_eq!(origins, [Point::new(0, 0), Point::new(44, 0), Point::new(0, 24)]);
+    }
+
+    #[test]
+    fn column_span_covers_columns() {
+        let spanning = |width, column_span| Style {
+            min_size: Size::new(width, 10),
+            column_span,
+            ..Default::default()
+        };
+        let area = Rect::new(Point::origin(), Size::new(200, 200));
+        let style = Style {
+            layout: Layout::Grid(3),
+            gap: 4,
+            ..Default::default()
+        };
+        let cells = layout_children(
+            style,
+            vec![
+                spanning(20, 2),
+                spanning(20, 1),
+                spanning(20, 1),
+                spanning(20, 1),
+                spanning(20, 1),
+                spanning(20, 5),
+            ],
+            area,
+        );
+        assert_eq!(
+            cells,
+            [
+                Rect::new(Point::new(0, 0), Size::new(44, 10)),
+                Rect::new(Point::new(48, 0), Size::new(20, 10)),
+                Rect::new(Point::new(0, 14), Size::new(20, 10)),
+                Rect::new(Point::new(24, 14), Size::new(20, 10)),
+                Rect::new(Point::new(48, 14), Size::new(20, 10)),
+                // The span is clamped to the three columns in the row.
+                Rect::new(Point::new(0, 28), Size::new(68, 10)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rtl_mirrors_row() {
+        let row = |rtl| Style {
+            rtl,
+            gap: 4,
+            cross_align: Align::Start,
+            ..Default::default()
+        };
+        let area = Rect::new(Point::origin(), Size::new(100, 100));
+        let children = vec![fixed(Size::new(20, 10)), fixed(Size::new(30, 10))];
+        let ltr = layout_children(row(false), children.clone(), area);
+        assert_eq!(ltr[0].origin, Point::new(0, 0));
+        assert_eq!(ltr[1].origin, Point::new(24, 0));
+        let rtl = layout_children(row(true), children, area);
+        assert_eq!(rtl[0].origin, Point::new(80, 0));
+        assert_eq!(rtl[1].origin, Point::new(46, 0));
+    }
+
+    #[test]
+    fn auto_margin_centers_child() {
+        let style = Style {
+            cross_align: Align::Start,
+            ..Default::default()
+        };
+        let centered = Style {
+            auto_margin: true,
+            ..fixed(Size::new(20, 10))
+        };
+        let area = Rect::new(Point::origin(), Size::new(100, 100));
+        let children = layout_children(
+            style,
+            vec![fixed(Size::new(10, 10)), centered, fixed(Size::new(10, 10))],
+            area,
+        );
+        let origins: Vec<_> = children.iter().map(|child| child.origin).collect();
+        // The 60 pixels left over become a 30 pixel margin on each side of the middle child.
+        assert_eq!(origins, [Point::new(0, 0), Point::new(40, 0), Point::new(90, 0)]);
+    }
+
+    #[test]
+    fn grid_uses_gap_per_axis() {
+        let mut nodes = TestNodes::with_key();
+        let mut children = SecondaryMap::new();
+        let parent = nodes.insert(Node::new(grid(3, 2, Some(6)), None));
+        let child_ids: Vec<_> = (0..5)
+            .map(|_| nodes.insert(Node::new(fixed(Size::new(10, 10)), None)))
+            .collect();
+        children.insert(parent, child_ids.clone());
+        measure_and_layout(
+            &mut nodes,
+            &children,
+            parent,
+            Rect::new(Point::origin(), Size::new(100, 100)),
+        );
+
+        // Columns are 2 pixels apart, and rows 6.
+        assert_eq!(nodes[parent].area.children_size, Size::new(34, 26));
+        let origins: Vec<_> = child_ids.iter().map(|id| nodes[*id].area.content_rect.origin).collect();
+        assert_eq!(
+            origins,
+            [
+                Point::new(0, 0),
+                Point::new(12, 0),
+                Point::new(24, 0),
+                Point::new(0, 16),
+                Point::new(12, 16)
+            ]
+        );
+    }
 }
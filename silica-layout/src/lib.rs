@@ -3,6 +3,7 @@ mod layout;
 use std::marker::PhantomData;
 
 use euclid::{BoolVector2D, point2, size2};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use silica_color::Rgba;
 use slotmap::{Key, SecondaryMap, SlotMap};
 
@@ -17,6 +18,29 @@ pub type Size = euclid::Size2D<i32, Pixel>;
 pub type Rect = euclid::Rect<i32, Pixel>;
 pub type SideOffsets = euclid::SideOffsets2D<i32, Pixel>;
 
+/// Adds sizes component-wise, clamping to `i32::MAX` instead of wrapping.
+fn saturating_add(a: Size, b: Size) -> Size {
+    Size::new(a.width.saturating_add(b.width), a.height.saturating_add(b.height))
+}
+/// Subtracts sizes component-wise, clamping instead of wrapping.
+fn saturating_sub(a: Size, b: Size) -> Size {
+    Size::new(a.width.saturating_sub(b.width), a.height.saturating_sub(b.height))
+}
+
+/// Pixel-space geometry types and constructors, so users don't need to depend on `euclid` directly.
+///
+/// ```
+/// use silica_layout::geometry::*;
+///
+/// let content = Rect::new(point2(4, 4), size2(32, 16));
+/// assert_eq!(content.outer_rect(SideOffsets::new_all_same(4)), Rect::from_size(Size::new(40, 24)));
+/// ```
+pub mod geometry {
+    pub use euclid::{Box2D, point2, size2, vec2};
+
+    pub use crate::{Pixel, Point, Rect, SideOffsets, Size, Vector};
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Color {
     Background,
@@ -27,7 +51,64 @@ pub enum Color {
     Custom(Rgba),
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+impl std::str::FromStr for Color {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "background" => Ok(Color::Background),
+            "border" => Ok(Color::Border),
+            "gutter" => Ok(Color::Gutter),
+            "accent" => Ok(Color::Accent),
+            "foreground" => Ok(Color::Foreground),
+            _ => s.parse().map(Color::Custom),
+        }
+    }
+}
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Color::Background => serializer.serialize_str("background"),
+            Color::Border => serializer.serialize_str("border"),
+            Color::Gutter => serializer.serialize_str("gutter"),
+            Color::Accent => serializer.serialize_str("accent"),
+            Color::Foreground => serializer.serialize_str("foreground"),
+            Color::Custom(rgba) => rgba.serialize(serializer),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Theme colors are referenced by name, anything else is a hex string or an Rgba map.
+        struct ColorVisitor;
+
+        impl<'de> de::Visitor<'de> for ColorVisitor {
+            type Value = Color;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("color name, hex string, or map")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Color, E>
+            where
+                E: de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_map<M>(self, map: M) -> Result<Color, M::Error>
+            where
+                M: de::MapAccess<'de>,
+            {
+                Rgba::deserialize(de::value::MapAccessDeserializer::new(map)).map(Color::Custom)
+            }
+        }
+
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
 pub enum Layout {
     None,
     #[default]
@@ -67,7 +148,8 @@ impl Layout {
     }
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
 pub enum Direction {
     #[default]
     Row,
@@ -80,31 +162,36 @@ impl Direction {
     fn horizontal(&self) -> bool {
         *self == Direction::Row || *self == Direction::RowReverse
     }
+    /// Takes an area of `size` from the start of `rect` in this direction, and removes it plus `gap` from
+    /// `rect`. The reverse directions take from the end, so they place children as a mirror image of the
+    /// forward directions, including with a negative gap or when `rect` is smaller than `size`.
     fn layout_area(&self, rect: &mut Rect, size: Size, gap: i32) -> Rect {
         match self {
             Direction::Row => {
                 let area = Rect::new(rect.origin, size2(size.width, size.height.max(rect.height())));
-                rect.origin.x += size.width + gap;
-                rect.size.width -= size.width + gap;
+                let advance = size.width.saturating_add(gap);
+                rect.origin.x = rect.origin.x.saturating_add(advance);
+                rect.size.width = rect.size.width.saturating_sub(advance);
                 area
             }
             Direction::Column => {
                 let area = Rect::new(rect.origin, size2(size.width.max(rect.width()), size.height));
-                rect.origin.y += size.height + gap;
-                rect.size.height -= size.height + gap;
+                let advance = size.height.saturating_add(gap);
+                rect.origin.y = rect.origin.y.saturating_add(advance);
+                rect.size.height = rect.size.height.saturating_sub(advance);
                 area
             }
             Direction::RowReverse => {
-                rect.size.width -= size.width + gap;
+                rect.size.width = rect.size.width.saturating_sub(size.width.saturating_add(gap));
                 Rect::new(
-                    point2(rect.max_x() + gap, rect.min_y()),
+                    point2(rect.max_x().saturating_add(gap), rect.min_y()),
                     size2(size.width, size.height.max(rect.height())),
                 )
             }
             Direction::ColumnReverse => {
-                rect.size.height -= size.height + gap;
+                rect.size.height = rect.size.height.saturating_sub(size.height.saturating_add(gap));
                 Rect::new(
-                    point2(rect.min_x(), rect.max_y() + gap),
+                    point2(rect.min_x(), rect.max_y().saturating_add(gap)),
                     size2(size.width.max(rect.width()), size.height),
                 )
             }
@@ -112,7 +199,8 @@ impl Direction {
     }
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
 pub enum Align {
     #[default]
     Stretch,
@@ -146,20 +234,55 @@ impl Align {
 
 #[derive(Clone)]
 pub struct Style {
+    /// Hides the node and its descendants. Hidden nodes take up no space and let pointer input through.
     pub hidden: bool,
     pub background_color: Option<Color>,
     pub border_color: Option<Color>,
+    /// Lets pointer input pass through the node's background to the nodes behind it. Backgrounds that
+    /// are fully transparent always let input through.
+    pub input_transparent: bool,
+    /// Multiplies the alpha of everything drawn by the node and its descendants. Nodes at zero opacity
+    /// aren't drawn and let pointer input through, like hidden nodes, but still take up space.
+    pub opacity: f32,
 
     pub min_size: Size,
+    /// Defaults to `i32::MAX`, meaning unbounded. Layout arithmetic saturates, so sizes that would exceed
+    /// `i32::MAX` pixels (including margins, border, and padding) are clamped to it.
     pub max_size: Size,
     pub grow: bool,
+    /// Splits the space left over in a box layout evenly between both sides of the node along the main
+    /// axis, centering it between its siblings. Has no effect if a sibling grows.
+    pub auto_margin: bool,
+    /// Sizes the node as a fraction of the parent's content rect on each axis, including the node's margins.
+    /// Applied before the parent aligns its children, and clamped by `min_size`/`max_size`.
+    pub size_fraction: Option<(Option<f32>, Option<f32>)>,
+    /// Lets the children extend past the content rect on each axis, as in a scroll area. On overflowing
+    /// axes children are measured without a limit and laid out in a space at least as large as their
+    /// natural size, so content larger than the node keeps its full size and starts at the content rect's
+    /// origin, while smaller content is still aligned and stretched within the content rect. Other axes
+    /// are limited to the content rect as usual.
     pub overflow: BoolVector2D,
+    /// If the node is given less space than its measured size, lay it out at its measured size and clip it
+    /// to the given space, instead of squeezing it (or hiding it, if its margins, border, and padding
+    /// don't fit).
+    pub clip_overflow: bool,
+    /// Places the node at this offset from the parent's content rect, outside of the parent's layout.
+    pub position: Option<Point>,
 
     pub layout: Layout,
     pub direction: Direction,
+    /// Mirrors horizontal directions, so the first child of a row is placed on the right. `main_align`
+    /// is mirrored too, with `Start` aligning to the right.
+    pub rtl: bool,
     pub main_align: Align,
     pub cross_align: Align,
     pub gap: i32,
+    /// Whether a box layout starts a new line when its children don't fit along the main axis.
+    pub wrap: bool,
+    /// Gap between the rows of a grid or the lines of a wrapped box. Uses `gap` if `None`.
+    pub gap_cross: Option<i32>,
+    /// Number of consecutive grid columns the node occupies, clamped to the columns left in its row.
+    pub column_span: usize,
     pub margin: SideOffsets,
     pub border: SideOffsets,
     pub padding: SideOffsets,
@@ -169,6 +292,17 @@ impl Style {
     pub fn new() -> Self {
         Self::default()
     }
+    /// The direction children are placed in, with `rtl` applied.
+    fn layout_direction(&self) -> Direction {
+        match (self.rtl, self.direction) {
+            (true, Direction::Row) => Direction::RowReverse,
+            (true, Direction::RowReverse) => Direction::Row,
+            (_, direction) => direction,
+        }
+    }
+    fn cross_gap(&self) -> i32 {
+        self.gap_cross.unwrap_or(self.gap)
+    }
     fn box_offsets(&self) -> SideOffsets {
         self.margin + self.border + self.padding
     }
@@ -179,6 +313,21 @@ impl Style {
     fn apply_min_max(&self, size: Size) -> Size {
         size.max(self.min_size).min(self.max_size)
     }
+    fn apply_size_fraction(&self, mut size: Size, parent_size: Size) -> Size {
+        if let Some((width, height)) = self.size_fraction {
+            let box_size = self.box_size();
+            let content_size = self.apply_min_max(Size::new(
+                width
+                    .map_or(size.width, |f| (parent_size.width as f32 * f) as i32)
+                    .saturating_sub(box_size.width),
+                height
+                    .map_or(size.height, |f| (parent_size.height as f32 * f) as i32)
+                    .saturating_sub(box_size.height),
+            ));
+            size = saturating_add(content_size, box_size);
+        }
+        size
+    }
 }
 impl Default for Style {
     fn default() -> Self {
@@ -186,15 +335,25 @@ impl Default for Style {
             hidden: false,
             background_color: None,
             border_color: Some(Color::Border),
+            input_transparent: false,
+            opacity: 1.0,
             min_size: Size::zero(),
             max_size: Size::new(i32::MAX, i32::MAX),
             grow: false,
+            auto_margin: false,
+            size_fraction: None,
             overflow: BoolVector2D { x: false, y: false },
+            clip_overflow: false,
+            position: None,
             layout: Layout::default(),
             direction: Direction::default(),
+            rtl: false,
             main_align: Align::default(),
             cross_align: Align::default(),
             gap: 0,
+            wrap: false,
+            gap_cross: None,
+            column_span: 1,
             margin: SideOffsets::zero(),
             border: SideOffsets::zero(),
             padding: SideOffsets::zero(),
@@ -204,8 +363,17 @@ impl Default for Style {
 
 #[derive(Default, Clone)]
 pub struct Area {
+    /// Size the node's layout needs for its children, set by [`measure`]. This is the children's extents
+    /// summed along the main axis (plus gaps) for a box layout, the largest child for a stack layout,
+    /// the full grid for a grid layout, and zero for [`Layout::None`]. Absolutely positioned children
+    /// aren't included. Unlike `measured_size`, it isn't limited by the node's overflow or size limits.
     pub children_size: Size,
+    /// Size of the node including its margins, border, and padding, set by [`measure`].
     pub measured_size: Size,
+    /// Whether the node was given less space than its measured size.
+    pub overflowing: bool,
+    /// The space the node is clipped to, if it's overflowing and has [`Style::clip_overflow`] set.
+    pub clip_rect: Option<Rect>,
     pub hidden: bool,
     pub content_rect: Rect,
     pub background_rect: Rect,
@@ -215,6 +383,13 @@ impl Area {
     pub fn new() -> Self {
         Self::default()
     }
+    /// Whether the children are larger than the content rect on each axis.
+    pub fn overflows(&self) -> BoolVector2D {
+        BoolVector2D {
+            x: self.children_size.width > self.content_rect.size.width,
+            y: self.children_size.height > self.content_rect.size.height,
+        }
+    }
 }
 
 pub trait LayoutWidget {
@@ -222,10 +397,21 @@ pub trait LayoutWidget {
     fn layout(&mut self, area: &Area);
 }
 
+/// Result of measuring a node with some available space. Nodes are usually measured with two different
+/// spaces, their minimum size and their actual size, so each node keeps the last two.
+#[derive(Clone, Copy)]
+struct MeasureCache {
+    available_space: Size,
+    measured_size: Size,
+    children_size: Size,
+}
+
 pub struct Node<Id, Widget> {
     pub style: Style,
     pub area: Area,
     pub widget: Option<Widget>,
+    dirty: bool,
+    measure_cache: [Option<MeasureCache>; 2],
     _id: PhantomData<Id>,
 }
 
@@ -235,18 +421,23 @@ impl<Id, Widget> Node<Id, Widget> {
             style,
             area: Area::new(),
             widget,
+            dirty: true,
+            measure_cache: [None; 2],
             _id: PhantomData,
         }
     }
+    /// Makes the next [`measure`] measure this node again, instead of reusing the size it measured with
+    /// the same available space. Ancestors of the node must be marked too.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
 }
 impl<Id, Widget> Default for Node<Id, Widget> {
     fn default() -> Self {
-        Self {
-            style: Style::default(),
-            area: Area::new(),
-            widget: None,
-            _id: PhantomData,
-        }
+        Self::new(Style::default(), None)
     }
 }
 
@@ -254,27 +445,75 @@ pub fn measure<Id: Key, Widget: LayoutWidget>(
     nodes: &mut SlotMap<Id, Node<Id, Widget>>,
     children: &SecondaryMap<Id, Vec<Id>>,
     id: Id,
-    mut available_space: Size,
+    available_space: Size,
 ) -> Size {
-    let node = &nodes[id];
-    let box_size = node.style.box_size();
-    available_space = node.style.apply_min_max(available_space - box_size);
-    let mut size = node.style.layout.measure(
-        nodes,
-        children,
-        id,
-        node.style.overflow.select_size(Size::splat(i32::MAX), available_space),
-    );
+    let node = &mut nodes[id];
+    if node.style.hidden {
+        // Hidden nodes take up no space. The dirty flag is kept so they're measured again once shown.
+        node.area.measured_size = Size::zero();
+        node.area.children_size = Size::zero();
+        return Size::zero();
+    }
+    if std::mem::take(&mut node.dirty) {
+        node.measure_cache = [None; 2];
+    } else if let Some(index) = node
+        .measure_cache
+        .iter()
+        .position(|cache| cache.is_some_and(|cache| cache.available_space == available_space))
+    {
+        let cache = node.measure_cache[index].unwrap();
+        if index == 1 {
+            // The descendants were measured with the other space since, and layout uses the sizes they
+            // were last measured with. Measure them again, which their own caches make cheap.
+            node.measure_cache.swap(0, 1);
+            measure_children(nodes, children, id, available_space);
+        }
+        // Layouts may have replaced the measured size with the size they gave the node.
+        let node = &mut nodes[id];
+        node.area.measured_size = cache.measured_size;
+        node.area.children_size = cache.children_size;
+        return cache.measured_size;
+    }
+    let (mut size, inner_space) = measure_children(nodes, children, id, available_space);
     let node = &mut nodes[id];
     node.area.children_size = size;
     size = node.style.overflow.select_size(Size::zero(), size);
     if let Some(widget) = node.widget.as_mut() {
-        size = size.max(widget.measure(available_space));
+        size = size.max(widget.measure(inner_space));
     }
-    size = node.style.apply_min_max(size) + box_size;
+    size = saturating_add(node.style.apply_min_max(size), node.style.box_size());
     node.area.measured_size = size;
+    node.measure_cache = [
+        Some(MeasureCache {
+            available_space,
+            measured_size: size,
+            children_size: node.area.children_size,
+        }),
+        node.measure_cache[0],
+    ];
     size
 }
+/// Measures the children of a node given the node's available space. Returns the size of the node's
+/// layout and the space inside the node's margins, border, and padding.
+fn measure_children<Id: Key, Widget: LayoutWidget>(
+    nodes: &mut SlotMap<Id, Node<Id, Widget>>,
+    children: &SecondaryMap<Id, Vec<Id>>,
+    id: Id,
+    available_space: Size,
+) -> (Size, Size) {
+    let style = &nodes[id].style;
+    let inner_space = style.apply_min_max(saturating_sub(available_space, style.box_size()));
+    let layout_space = style.overflow.select_size(Size::splat(i32::MAX), inner_space);
+    let size = style.layout.measure(nodes, children, id, layout_space);
+    if let Some(child_ids) = children.get(id) {
+        for child_id in child_ids.iter() {
+            if nodes[*child_id].style.position.is_some() {
+                measure(nodes, children, *child_id, inner_space);
+            }
+        }
+    }
+    (size, inner_space)
+}
 pub fn layout<Id: Key, Widget: LayoutWidget>(
     nodes: &mut SlotMap<Id, Node<Id, Widget>>,
     children: &SecondaryMap<Id, Vec<Id>>,
@@ -283,6 +522,13 @@ pub fn layout<Id: Key, Widget: LayoutWidget>(
 ) {
     let node = &mut nodes[id];
     let box_offsets = node.style.box_offsets();
+    let measured_size = node.area.measured_size;
+    node.area.overflowing = rect.width() < measured_size.width || rect.height() < measured_size.height;
+    node.area.clip_rect = None;
+    if node.style.clip_overflow && node.area.overflowing {
+        node.area.clip_rect = Some(rect);
+        rect.size = rect.size.max(measured_size);
+    }
     if rect.width() <= box_offsets.horizontal() || rect.height() <= box_offsets.vertical() {
         node.area.hidden = true;
         return;
@@ -297,7 +543,29 @@ pub fn layout<Id: Key, Widget: LayoutWidget>(
     if let Some(widget) = node.widget.as_mut() {
         widget.layout(&node.area);
     }
-    node.style.layout.layout(nodes, children, id, rect);
+    let node_layout = node.style.layout;
+    let layout_rect = Rect::new(
+        rect.origin,
+        node.style
+            .overflow
+            .select_size(rect.size.max(node.area.children_size), rect.size),
+    );
+    if let Some(child_ids) = children.get(id) {
+        for child_id in child_ids.iter() {
+            let child = &mut nodes[*child_id];
+            child.area.measured_size = child.style.apply_size_fraction(child.area.measured_size, rect.size);
+        }
+    }
+    node_layout.layout(nodes, children, id, layout_rect);
+    if let Some(child_ids) = children.get(id) {
+        for child_id in child_ids.iter() {
+            let child = &nodes[*child_id];
+            if let Some(position) = child.style.position {
+                let child_rect = Rect::new(rect.origin + position.to_vector(), child.area.measured_size);
+                layout(nodes, children, *child_id, child_rect);
+            }
+        }
+    }
 }
 pub fn measure_and_layout<Id: Key, Widget: LayoutWidget>(
     nodes: &mut SlotMap<Id, Node<Id, Widget>>,
@@ -308,3 +576,459 @@ pub fn measure_and_layout<Id: Key, Widget: LayoutWidget>(
     measure(nodes, children, id, rect.size);
     layout(nodes, children, id, rect);
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use slotmap::new_key_type;
+
+    use super::*;
+
+    new_key_type! { pub(crate) struct TestId; }
+
+    /// Takes up to 50x40 pixels of the available space.
+    pub(crate) struct TestWidget;
+
+    impl LayoutWidget for TestWidget {
+        fn measure(&mut self, available_space: Size) -> Size {
+            available_space.min(Size::new(50, 40))
+        }
+        fn layout(&mut self, _area: &Area) {}
+    }
+
+    pub(crate) type TestNodes = SlotMap<TestId, Node<TestId, TestWidget>>;
+
+    /// Lays out a parent with `style` in `rect`, holding children with `child_styles`, and returns the
+    /// content rects of the children.
+    pub(crate) fn layout_children(style: Style, child_styles: Vec<Style>, rect: Rect) -> Vec<Rect> {
+        let mut nodes = TestNodes::with_key();
+        let mut children = SecondaryMap::new();
+        let parent = nodes.insert(Node::new(style, None));
+        let child_ids: Vec<_> = child_styles
+            .into_iter()
+            .map(|style| nodes.insert(Node::new(style, None)))
+            .collect();
+        children.insert(parent, child_ids.clone());
+        measure_and_layout(&mut nodes, &children, parent, rect);
+        child_ids.iter().map(|id| nodes[*id].area.content_rect).collect()
+    }
+
+    fn column() -> Style {
+        Style {
+            direction: Direction::Column,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn measure_cache_hit_restores_descendants() {
+        let mut nodes = TestNodes::with_key();
+        let mut children = SecondaryMap::new();
+        let root = nodes.insert(Node::new(column(), None));
+        let sibling = nodes.insert(Node::new(
+            Style {
+                min_size: Size::new(0, 10),
+                ..Default::default()
+            },
+            None,
+        ));
+        let subtree = nodes.insert(Node::new(
+            Style {
+                cross_align: Align::Start,
+                ..column()
+            },
+            None,
+        ));
+        let leaf = nodes.insert(Node::new(Style::default(), Some(TestWidget)));
+        children.insert(root, vec![sibling, subtree]);
+        children.insert(subtree, vec![leaf]);
+        // Measured like the gui does, with a small space and then the space it's laid out in. The column
+        // doesn't shrink unbounded heights, so the sibling only affects the subtree's small space.
+        let small_space = Size::new(20, 100);
+        let area = Rect::new(Point::origin(), Size::new(200, i32::MAX));
+        measure(&mut nodes, &children, root, small_space);
+        measure_and_layout(&mut nodes, &children, root, area);
+        assert_eq!(nodes[leaf].area.content_rect.size, Size::new(50, 40));
+
+        nodes[sibling].style.min_size.height = 30;
+        nodes[sibling].mark_dirty();
+        nodes[root].mark_dirty();
+        measure(&mut nodes, &children, root, small_space);
+        measure_and_layout(&mut nodes, &children, root, area);
+        assert_eq!(nodes[leaf].area.content_rect.size, Size::new(50, 40));
+    }
+
+    /// Lays out areas of `sizes` in `rect` in both `forward` and `reverse`, returning the areas.
+    fn layout_areas(
+        forward: Direction,
+        reverse: Direction,
+        rect: Rect,
+        sizes: &[Size],
+        gap: i32,
+    ) -> (Vec<Rect>, Vec<Rect>) {
+        let take = |direction: Direction| {
+            let mut rect = rect;
+            sizes
+                .iter()
+                .map(|size| direction.layout_area(&mut rect, *size, gap))
+                .collect::<Vec<_>>()
+        };
+        (take(forward), take(reverse))
+    }
+
+    fn assert_row_mirrored(rect: Rect, sizes: &[Size], gap: i32) {
+        let (forward, reverse) = layout_areas(Direction::Row, Direction::RowReverse, rect, sizes, gap);
+        for (forward, reverse) in forward.iter().zip(reverse.iter()) {
+            assert_eq!(reverse.size, forward.size);
+            assert_eq!(reverse.min_x() - rect.min_x(), rect.max_x() - forward.max_x());
+            assert_eq!(reverse.min_y(), forward.min_y());
+        }
+    }
+
+    fn assert_column_mirrored(rect: Rect, sizes: &[Size], gap: i32) {
+        let (forward, reverse) = layout_areas(Direction::Column, Direction::ColumnReverse, rect, sizes, gap);
+        for (forward, reverse) in forward.iter().zip(reverse.iter()) {
+            assert_eq!(reverse.size, forward.size);
+            assert_eq!(reverse.min_y() - rect.min_y(), rect.max_y() - forward.max_y());
+            assert_eq!(reverse.min_x(), forward.min_x());
+        }
+    }
+
+    const SIZES: [Size; 3] = [Size::new(20, 10), Size::new(30, 15), Size::new(10, 5)];
+
+    #[test]
+    fn reverse_directions_mirror_forward() {
+        let rect = Rect::new(Point::new(5, 7), Size::new(100, 80));
+        assert_row_mirrored(rect, &SIZES, 4);
+        assert_column_mirrored(rect, &SIZES, 4);
+        let (_, reverse) = layout_areas(Direction::Row, Direction::RowReverse, rect, &SIZES, 4);
+        assert_eq!(reverse[0], Rect::new(Point::new(85, 7), Size::new(20, 80)));
+        assert_eq!(reverse[1], Rect::new(Point::new(51, 7), Size::new(30, 80)));
+    }
+
+    #[test]
+    fn reverse_directions_mirror_forward_with_negative_gap() {
+        let rect = Rect::new(Point::new(5, 7), Size::new(100, 80));
+        assert_row_mirrored(rect, &SIZES, -6);
+        assert_column_mirrored(rect, &SIZES, -6);
+        let (_, reverse) = layout_areas(Direction::Column, Direction::ColumnReverse, rect, &SIZES, -6);
+        assert_eq!(reverse[1].min_y(), reverse[0].min_y() - 15 + 6);
+    }
+
+    #[test]
+    fn reverse_directions_mirror_forward_in_undersized_rect() {
+        let rect = Rect::new(Point::new(5, 7), Size::new(25, 12));
+        assert_row_mirrored(rect, &SIZES, 4);
+        assert_column_mirrored(rect, &SIZES, 4);
+        assert_row_mirrored(rect, &SIZES, -6);
+        assert_column_mirrored(rect, &SIZES, -6);
+    }
+
+    /// Checks that `value` serializes to `yaml` and back.
+    fn assert_yaml_round_trip<T>(value: T, yaml: &str)
+    where
+        T: Serialize + for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug,
+    {
+        assert_eq!(serde_yml::to_string(&value).unwrap().trim(), yaml);
+        assert_eq!(serde_yml::from_str::<T>(yaml).unwrap(), value);
+    }
+
+    #[test]
+    fn layout_enums_round_trip() {
+        assert_yaml_round_trip(Layout::None, "none");
+        assert_yaml_round_trip(Layout::Stack, "stack");
+        assert_yaml_round_trip(Layout::Grid(3), "!grid 3");
+        assert_yaml_round_trip(Direction::ColumnReverse, "column-reverse");
+        assert_yaml_round_trip(Align::Center, "center");
+    }
+
+    #[test]
+    fn color_keyword_or_hex() {
+        assert_yaml_round_trip(Color::Accent, "accent");
+        assert_yaml_round_trip(Color::Foreground, "foreground");
+        assert_eq!(
+            serde_yml::from_str::<Color>("'#ff0000'").unwrap(),
+            Color::Custom(Rgba::RED)
+        );
+        assert_eq!(
+            serde_yml::from_str::<Color>("{r: 0.0, g: 0.0, b: 1.0}").unwrap(),
+            Color::Custom(Rgba::BLUE)
+        );
+        let custom = Color::Custom(Rgba::new(0.5, 0.25, 1.0, 0.5));
+        assert_eq!(
+            serde_yml::from_str::<Color>(&serde_yml::to_string(&custom).unwrap()).unwrap(),
+            custom
+        );
+        assert!(serde_yml::from_str::<Color>("sky").is_err());
+    }
+
+    pub(crate) fn fixed(size: Size) -> Style {
+        Style {
+            min_size: size,
+            max_size: size,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn absolute_child_is_outside_flow() {
+        let mut nodes = TestNodes::with_key();
+        let mut children = SecondaryMap::new();
+        let parent = nodes.insert(Node::new(
+            Style {
+                padding: SideOffsets::new_all_same(4),
+                ..Default::default()
+            },
+            None,
+        ));
+        let content = nodes.insert(Node::new(Style::default(), Some(TestWidget)));
+        // A badge in the top right corner of the content.
+        let badge = nodes.insert(Node::new(
+            Style {
+                position: Some(Point::new(50 - 16, 0)),
+                ..fixed(Size::splat(16))
+            },
+            None,
+        ));
+        children.insert(parent, vec![badge, content]);
+        let area = Rect::new(Point::new(10, 10), Size::new(200, 100));
+        measure_and_layout(&mut nodes, &children, parent, area);
+        assert_eq!(nodes[parent].area.measured_size, Size::new(58, 48));
+        let content_rect = nodes[content].area.content_rect;
+        assert_eq!(content_rect.origin, Point::new(14, 14));
+        assert_eq!(content_rect.width(), 50);
+        assert_eq!(
+            nodes[badge].area.background_rect,
+            Rect::new(
+                Point::new(content_rect.max_x() - 16, content_rect.min_y()),
+                Size::splat(16)
+            )
+        );
+    }
+
+    #[test]
+    fn size_fraction_of_parent() {
+        let mut nodes = TestNodes::with_key();
+        let mut children = SecondaryMap::new();
+        let parent = nodes.insert(Node::new(
+            Style {
+                cross_align: Align::Start,
+                ..Default::default()
+            },
+            None,
+        ));
+        let half = nodes.insert(Node::new(
+            Style {
+                size_fraction: Some((Some(0.5), None)),
+                ..Default::default()
+            },
+            Some(TestWidget),
+        ));
+        let quarter = nodes.insert(Node::new(
+            Style {
+                size_fraction: Some((Some(0.25), Some(0.25))),
+                ..Default::default()
+            },
+            None,
+        ));
+        children.insert(parent, vec![half, quarter]);
+        let area = Rect::new(Point::origin(), Size::new(400, 100));
+        measure_and_layout(&mut nodes, &children, parent, area);
+        assert_eq!(nodes[half].area.content_rect.size, Size::new(200, 40));
+        assert_eq!(
+            nodes[quarter].area.content_rect,
+            Rect::new(Point::new(200, 0), Size::new(100, 25))
+        );
+    }
+
+    #[test]
+    fn children_size_sums_box_and_overlaps_stack() {
+        let children_size = |layout| {
+            let mut nodes = TestNodes::with_key();
+            let mut children = SecondaryMap::new();
+            let style = Style {
+                layout,
+                gap: 4,
+                ..Default::default()
+            };
+            let parent = nodes.insert(Node::new(style, None));
+            let child_ids =
+                [Size::new(20, 10), Size::new(30, 15)].map(|size| nodes.insert(Node::new(fixed(size), None)));
+            children.insert(parent, child_ids.to_vec());
+            measure_and_layout(
+                &mut nodes,
+                &children,
+                parent,
+                Rect::new(Point::origin(), Size::new(200, 100)),
+            );
+            nodes[parent].area.children_size
+        };
+        assert_eq!(children_size(Layout::Box), Size::new(54, 15));
+        assert_eq!(children_size(Layout::Stack), Size::new(30, 15));
+        assert_eq!(children_size(Layout::None), Size::zero());
+    }
+
+    /// Counts how many times it was measured.
+    struct CountingWidget(Rc<Cell<u32>>);
+
+    impl LayoutWidget for CountingWidget {
+        fn measure(&mut self, available_space: Size) -> Size {
+            self.0.set(self.0.get() + 1);
+            available_space.min(Size::new(50, 40))
+        }
+        fn layout(&mut self, _area: &Area) {}
+    }
+
+    #[test]
+    fn measure_repeats_only_dirty_path() {
+        let mut nodes = SlotMap::<TestId, Node<TestId, CountingWidget>>::with_key();
+        let mut children = SecondaryMap::<TestId, Vec<TestId>>::new();
+        let root = nodes.insert(Node::new(column(), None));
+        // Three branches, each a chain of nodes ending in a counting leaf.
+        let mut paths = Vec::new();
+        let mut counts = Vec::new();
+        for _ in 0..3 {
+            let mut path = vec![root];
+            for _ in 0..4 {
+                let node = nodes.insert(Node::new(column(), None));
+                children.entry(*path.last().unwrap()).unwrap().or_default().push(node);
+                path.push(node);
+            }
+            let count = Rc::new(Cell::new(0));
+            let leaf = nodes.insert(Node::new(Style::default(), Some(CountingWidget(count.clone()))));
+            children.entry(*path.last().unwrap()).unwrap().or_default().push(leaf);
+            path.push(leaf);
+            paths.push(path);
+            counts.push(count);
+        }
+        let space = Size::new(200, 200);
+        let count_values = || counts.iter().map(|count| count.get()).collect::<Vec<_>>();
+        measure(&mut nodes, &children, root, space);
+        assert_eq!(count_values(), [1, 1, 1]);
+
+        measure(&mut nodes, &children, root, space);
+        assert_eq!(count_values(), [1, 1, 1]);
+
+        for id in paths[1].iter() {
+            nodes[*id].mark_dirty();
+        }
+        measure(&mut nodes, &children, root, space);
+        assert_eq!(count_values(), [1, 2, 1]);
+        assert!(nodes.values().all(|node| !node.is_dirty()));
+    }
+
+    #[test]
+    fn clip_overflow_keeps_measured_size() {
+        let mut nodes = TestNodes::with_key();
+        let mut children = SecondaryMap::new();
+        let parent = nodes.insert(Node::new(
+            Style {
+                layout: Layout::Stack,
+                ..Default::default()
+            },
+            None,
+        ));
+        let child_style = |clip_overflow| Style {
+            clip_overflow,
+            margin: SideOffsets::new_all_same(30),
+            ..fixed(Size::new(40, 10))
+        };
+        let clipped = nodes.insert(Node::new(child_style(true), None));
+        let squeezed = nodes.insert(Node::new(child_style(false), None));
+        children.insert(parent, vec![clipped, squeezed]);
+        measure_and_layout(
+            &mut nodes,
+            &children,
+            parent,
+            Rect::new(Point::origin(), Size::new(50, 70)),
+        );
+
+        // Both children are 100x70 with their margins, and a stack gives them the parent's 50x70.
+        let clipped = &nodes[clipped].area;
+        assert!(clipped.overflowing && !clipped.hidden);
+        assert_eq!(clipped.content_rect, Rect::new(Point::new(30, 30), Size::new(40, 10)));
+        assert_eq!(clipped.clip_rect, Some(Rect::new(Point::origin(), Size::new(50, 70))));
+        let squeezed = &nodes[squeezed].area;
+        assert!(squeezed.overflowing && squeezed.hidden);
+        assert_eq!(squeezed.clip_rect, None);
+    }
+
+    #[test]
+    fn huge_sizes_saturate() {
+        let unbounded = Rect::new(Point::origin(), Size::splat(i32::MAX));
+        // A child as wide as possible, plus margins, border, and padding.
+        let huge = Style {
+            margin: SideOffsets::new_all_same(10),
+            border: SideOffsets::new_all_same(1),
+            padding: SideOffsets::new_all_same(5),
+            ..fixed(Size::new(i32::MAX, 10))
+        };
+        let mut nodes = TestNodes::with_key();
+        let mut children = SecondaryMap::new();
+        let parent = nodes.insert(Node::new(
+            Style {
+                gap: 100,
+                cross_align: Align::Start,
+                ..Default::default()
+            },
+            None,
+        ));
+        let child = nodes.insert(Node::new(huge, None));
+        children.insert(parent, vec![child]);
+        measure_and_layout(&mut nodes, &children, parent, unbounded);
+        assert_eq!(nodes[parent].area.measured_size, Size::new(i32::MAX, 42));
+        assert_eq!(nodes[child].area.measured_size, Size::new(i32::MAX, 42));
+        assert_eq!(
+            nodes[child].area.content_rect,
+            Rect::new(Point::new(16, 16), Size::new(i32::MAX - 32, 10))
+        );
+
+        // Many tall grid rows add up to at most `i32::MAX`.
+        let mut nodes = TestNodes::with_key();
+        let mut children = SecondaryMap::new();
+        let grid = nodes.insert(Node::new(
+            Style {
+                layout: Layout::Grid(2),
+                gap: 4,
+                ..Default::default()
+            },
+            None,
+        ));
+        let cells = (0..10_000)
+            .map(|_| nodes.insert(Node::new(fixed(Size::new(10, i32::MAX / 1000)), None)))
+            .collect();
+        children.insert(grid, cells);
+        measure_and_layout(&mut nodes, &children, grid, unbounded);
+        assert_eq!(nodes[grid].area.measured_size, Size::new(24, i32::MAX));
+        assert!(nodes[grid].area.children_size.height > 0);
+    }
+
+    #[test]
+    fn overflow_keeps_natural_height() {
+        let scroll = Style {
+            overflow: BoolVector2D { x: false, y: true },
+            ..column()
+        };
+        let tall = Style {
+            min_size: Size::new(20, 200),
+            ..Default::default()
+        };
+        let rect = Rect::new(Point::new(10, 10), Size::new(100, 50));
+        // The tall child keeps its full height past the bottom, and still stretches across.
+        let rects = layout_children(scroll.clone(), vec![tall], rect);
+        assert_eq!(rects, [Rect::new(Point::new(10, 10), Size::new(100, 200))]);
+
+        // Content smaller than the viewport is still aligned within it.
+        let short = Style {
+            min_size: Size::new(20, 20),
+            ..Default::default()
+        };
+        let centered = Style {
+            main_align: Align::Center,
+            ..scroll
+        };
+        let rects = layout_children(centered, vec![short], rect);
+        assert_eq!(rects, [Rect::new(Point::new(10, 25), Size::new(100, 20))]);
+    }
+}
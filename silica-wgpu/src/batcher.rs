@@ -120,6 +120,11 @@ impl<T: Pod> ImmediateBatcher<T> {
             self.current_texture = Some(texture.clone());
         }
     }
+    /// Whether queueing an instance with `texture` would draw the instances queued so far.
+    pub fn would_draw(&self, texture: &Texture) -> bool {
+        let changes_texture = self.current_texture.as_ref() != Some(texture.bind_group());
+        (changes_texture && !self.buffer_range.is_empty()) || self.buffer_data.len() >= self.buffer.capacity()
+    }
     pub fn queue(
         &mut self,
         context: &Context,
@@ -1,5 +1,6 @@
 mod batcher;
 pub mod draw;
+mod profiler;
 mod texture;
 
 use std::marker::PhantomData;
@@ -8,7 +9,7 @@ use bytemuck::Pod;
 use euclid::point2;
 pub use wgpu;
 
-pub use crate::{batcher::*, texture::*};
+pub use crate::{batcher::*, profiler::*, texture::*};
 
 pub struct Uv;
 pub type UvRect = euclid::Box2D<f32, Uv>;
@@ -29,6 +30,13 @@ pub struct AdapterFeatures {
     pub required_limits: wgpu::Limits,
 }
 
+impl AdapterFeatures {
+    /// Requests timestamp queries if the adapter supports them, for use with [`GpuProfiler`].
+    pub fn with_profiling(mut self) -> Self {
+        self.optional_features |= wgpu::Features::TIMESTAMP_QUERY;
+        self
+    }
+}
 impl Default for AdapterFeatures {
     fn default() -> Self {
         Self {
@@ -201,6 +209,14 @@ impl Surface {
         let format = config.format.remove_srgb_suffix();
         config.format = format;
         config.view_formats.push(format);
+        // Lets apps copy a frame kept in another texture to the surface, to only redraw part of it.
+        if surface
+            .get_capabilities(&context.adapter)
+            .usages
+            .contains(wgpu::TextureUsages::COPY_DST)
+        {
+            config.usage |= wgpu::TextureUsages::COPY_DST;
+        }
 
         surface.configure(&context.device, &config);
         context.surface_format = Some(config.format);
@@ -218,16 +234,15 @@ impl Surface {
         surface.configure(&context.device, config);
     }
 
-    /// Acquire the next surface texture.
-    pub fn acquire(&mut self, context: &Context) -> wgpu::SurfaceTexture {
+    /// Acquire the next surface texture. Timeouts are retried once, and the surface is reconfigured if
+    /// it's outdated or lost. If acquiring still fails, the error is returned so the frame can be skipped.
+    pub fn acquire(&mut self, context: &Context) -> Result<wgpu::SurfaceTexture, wgpu::SurfaceError> {
         let surface = self.surface.as_ref().unwrap();
 
         match surface.get_current_texture() {
-            Ok(frame) => frame,
+            Ok(frame) => Ok(frame),
             // If we timed out, just try again
-            Err(wgpu::SurfaceError::Timeout) => surface
-                .get_current_texture()
-                .expect("Failed to acquire next surface texture!"),
+            Err(wgpu::SurfaceError::Timeout) => surface.get_current_texture(),
             Err(
                 // If the surface is outdated, or was lost, reconfigure it.
                 wgpu::SurfaceError::Outdated
@@ -237,9 +252,7 @@ impl Surface {
                 | wgpu::SurfaceError::OutOfMemory,
             ) => {
                 surface.configure(&context.device, self.config.as_ref().unwrap());
-                surface
-                    .get_current_texture()
-                    .expect("Failed to acquire next surface texture!")
+                surface.get_current_texture()
             }
         }
     }
@@ -0,0 +1,212 @@
+use std::time::Duration;
+
+use crate::Context;
+
+pub struct GpuSpan {
+    pub label: String,
+    pub duration: Duration,
+}
+
+struct Queries {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+}
+
+/// Measures how long render passes take on the GPU, using timestamp queries. If the device doesn't
+/// support `TIMESTAMP_QUERY` (see [`AdapterFeatures::with_profiling`](crate::AdapterFeatures::with_profiling)),
+/// the profiler does nothing and reports no spans.
+pub struct GpuProfiler {
+    queries: Option<Queries>,
+    max_spans: u32,
+    labels: Vec<String>,
+}
+
+impl GpuProfiler {
+    pub fn new(context: &Context, max_spans: u32) -> Self {
+        assert!(max_spans > 0, "profiler must measure at least one span");
+        let queries = context
+            .device
+            .features()
+            .contains(wgpu::Features::TIMESTAMP_QUERY)
+            .then(|| {
+                let size = (max_spans * 2) as u64 * wgpu::QUERY_SIZE as u64;
+                Queries {
+                    query_set: context.device.create_query_set(&wgpu::QuerySetDescriptor {
+                        label: Some("silica profiler queries"),
+                        ty: wgpu::QueryType::Timestamp,
+                        count: max_spans * 2,
+                    }),
+                    resolve_buffer: context.device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("silica profiler resolve"),
+                        size,
+                        usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                        mapped_at_creation: false,
+                    }),
+                    readback_buffer: context.device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("silica profiler readback"),
+                        size,
+                        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                        mapped_at_creation: false,
+                    }),
+                }
+            });
+        if queries.is_none() {
+            log::info!("GPU profiling disabled, timestamp queries aren't supported");
+        }
+        GpuProfiler {
+            queries,
+            max_spans,
+            labels: Vec::new(),
+        }
+    }
+    pub fn is_supported(&self) -> bool {
+        self.queries.is_some()
+    }
+    /// Returns the timestamp writes for a render pass, to be measured as a span with the given label.
+    /// Returns `None` if profiling isn't supported, or `max_spans` passes were already measured this
+    /// frame.
+    pub fn pass_timestamp_writes(&mut self, label: &str) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        let queries = self.queries.as_ref()?;
+        let index = self.labels.len() as u32;
+        if index >= self.max_spans {
+            return None;
+        }
+        self.labels.push(label.to_string());
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set: &queries.query_set,
+            beginning_of_pass_write_index: Some(index * 2),
+            end_of_pass_write_index: Some(index * 2 + 1),
+        })
+    }
+    /// Copies this frame's timestamps into a readable buffer. Call after all measured passes are
+    /// recorded, before the encoder is submitted.
+    pub fn resolve(&self, encoder: &mut wgpu::CommandEncoder) {
+        if let Some(queries) = self.queries.as_ref()
+            && !self.labels.is_empty()
+        {
+            let count = self.labels.len() as u32 * 2;
+            encoder.resolve_query_set(&queries.query_set, 0..count, &queries.resolve_buffer, 0);
+            encoder.copy_buffer_to_buffer(
+                &queries.resolve_buffer,
+                0,
+                &queries.readback_buffer,
+                0,
+                count as u64 * wgpu::QUERY_SIZE as u64,
+            );
+        }
+    }
+    /// Reads the spans measured this frame and starts a new frame. Call after submitting. This waits
+    /// for the GPU to finish the frame, so it should only be used while profiling.
+    pub fn finish_frame(&mut self, context: &Context) -> Vec<GpuSpan> {
+        let labels = std::mem::take(&mut self.labels);
+        let Some(queries) = self.queries.as_ref() else {
+            return Vec::new();
+        };
+        if labels.is_empty() {
+            return Vec::new();
+        }
+        let size = (labels.len() * 2) as u64 * wgpu::QUERY_SIZE as u64;
+        let slice = queries.readback_buffer.slice(..size);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        let mapped = match context.device.poll(wgpu::PollType::Wait) {
+            Ok(_) => receiver.try_recv().map_err(|_| "buffer wasn't mapped".to_string()),
+            Err(error) => Err(error.to_string()),
+        };
+        if let Err(error) = mapped.and_then(|result| result.map_err(|error| error.to_string())) {
+            log::warn!("failed to read GPU timestamps: {error}");
+            // Cancels the mapping if it's still pending, so the buffer can be used next frame.
+            queries.readback_buffer.unmap();
+            return Vec::new();
+        }
+        let period = context.queue.get_timestamp_period() as f64;
+        let spans = {
+            let data = slice.get_mapped_range();
+            let timestamps: &[u64] = bytemuck::cast_slice(&data);
+            labels
+                .into_iter()
+                .zip(timestamps.chunks_exact(2))
+                .map(|(label, pair)| GpuSpan {
+                    label,
+                    duration: Duration::from_nanos((pair[1].saturating_sub(pair[0]) as f64 * period) as u64),
+                })
+                .collect()
+        };
+        queries.readback_buffer.unmap();
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdapterFeatures;
+
+    /// Measures two frames of a pass clearing a small texture.
+    fn profile_frames(context: &Context, profiler: &mut GpuProfiler) -> Vec<Vec<GpuSpan>> {
+        let texture = context.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        (0..2)
+            .map(|_| {
+                let mut encoder = context
+                    .device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: None,
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &view,
+                        resolve_target: None,
+                        ops: wgpu::Operations::default(),
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: profiler.pass_timestamp_writes("clear"),
+                    occlusion_query_set: None,
+                });
+                profiler.resolve(&mut encoder);
+                context.queue.submit([encoder.finish()]);
+                profiler.finish_frame(context)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn profiler_reports_spans_if_supported() {
+        let context = Context::init(AdapterFeatures::default().with_profiling());
+        let mut profiler = GpuProfiler::new(&context, 1);
+        let frames = profile_frames(&context, &mut profiler);
+        if context.device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            assert!(profiler.is_supported());
+            for spans in frames {
+                let labels: Vec<_> = spans.iter().map(|span| span.label.as_str()).collect();
+                assert_eq!(labels, ["clear"]);
+            }
+        } else {
+            assert!(!profiler.is_supported());
+            assert!(frames.iter().all(Vec::is_empty));
+        }
+    }
+
+    #[test]
+    fn profiler_without_timestamps_does_nothing() {
+        let context = Context::init(AdapterFeatures::default());
+        let mut profiler = GpuProfiler::new(&context, 1);
+        assert!(!profiler.is_supported());
+        assert!(profile_frames(&context, &mut profiler).iter().all(Vec::is_empty));
+    }
+}
@@ -1,7 +1,9 @@
+use silica_color::Rgba;
 use wgpu::util::DeviceExt;
 
 use crate::Context;
 
+pub type TexturePoint = euclid::Point2D<u32, Texture>;
 pub type TextureSize = euclid::Size2D<u32, Texture>;
 pub type TextureRect = euclid::Box2D<u32, Texture>;
 
@@ -73,7 +75,7 @@ impl Texture {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         })
     }
@@ -93,7 +95,9 @@ impl Texture {
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
                 format,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST
+                    | wgpu::TextureUsages::COPY_SRC,
                 view_formats: &[],
             },
             wgpu::util::TextureDataOrder::LayerMajor,
@@ -133,6 +137,16 @@ impl Texture {
         let bind_group = Self::create_bind_group(context, config, &texture);
         Texture { texture, bind_group }
     }
+    /// Creates an sRGB texture filled with a single color.
+    pub fn solid(context: &Context, config: &TextureConfig, color: Rgba, size: TextureSize) -> Self {
+        let (r, g, b, a) = color.to_srgb_u8();
+        let data = [r, g, b, a].repeat(size.area() as usize);
+        Self::new_with_data(context, config, size, wgpu::TextureFormat::Rgba8UnormSrgb, &data)
+    }
+    /// A 1x1 white texture, for drawing quads with only a vertex color.
+    pub fn white(context: &Context, config: &TextureConfig) -> Self {
+        Self::solid(context, config, Rgba::WHITE, TextureSize::new(1, 1))
+    }
     pub fn width(&self) -> u32 {
         self.texture.width()
     }
@@ -145,7 +159,13 @@ impl Texture {
     pub fn format(&self) -> wgpu::TextureFormat {
         self.texture.format()
     }
+    /// Writes `data` into `rect` of the texture. Panics if `rect` doesn't fit inside the texture.
     pub fn write_data(&self, context: &Context, rect: TextureRect, data: &[u8], offset: u64, stride: u32) {
+        assert!(
+            TextureRect::from_size(self.size()).contains_box(&rect),
+            "write to {rect:?} is outside of {:?} texture",
+            self.size()
+        );
         let mut texture_copy_info = self.texture.as_image_copy();
         texture_copy_info.origin = wgpu::Origin3d {
             x: rect.min.x,
@@ -167,3 +187,87 @@ impl Texture {
         &self.bind_group
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdapterFeatures;
+
+    fn write_block(origin: TexturePoint) {
+        let context = Context::init(AdapterFeatures::default());
+        let config = TextureConfig::new(&context, wgpu::FilterMode::Nearest);
+        let texture = Texture::new(
+            &context,
+            &config,
+            TextureSize::new(4, 4),
+            wgpu::TextureFormat::Rgba8Unorm,
+        );
+        let rect = TextureRect::from_origin_and_size(origin, TextureSize::new(2, 2));
+        texture.write_data(&context, rect, &[255; 2 * 2 * 4], 0, 2 * 4);
+    }
+
+    #[test]
+    fn write_inside_texture() {
+        write_block(TexturePoint::new(2, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "is outside of")]
+    fn write_past_edge_panics() {
+        write_block(TexturePoint::new(3, 2));
+    }
+
+    /// Reads back the pixels of a texture with four bytes per pixel, row by row.
+    fn read_pixels(context: &Context, texture: &Texture) -> Vec<[u8; 4]> {
+        let row_size = texture.width() * 4;
+        let padded_row_size = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (padded_row_size * texture.height()) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            texture.texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: None,
+                },
+            },
+            texture.texture.size(),
+        );
+        context.queue.submit([encoder.finish()]);
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        context.device.poll(wgpu::PollType::Wait).unwrap();
+        let data = buffer.slice(..).get_mapped_range();
+        data.chunks(padded_row_size as usize)
+            .flat_map(|row| row[..row_size as usize].chunks(4))
+            .map(|pixel| pixel.try_into().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn solid_texture_has_color() {
+        let context = Context::init(AdapterFeatures::default());
+        let config = TextureConfig::new(&context, wgpu::FilterMode::Nearest);
+        let white = Texture::white(&context, &config);
+        assert_eq!(white.size(), TextureSize::new(1, 1));
+        assert_eq!(read_pixels(&context, &white), [[255; 4]]);
+
+        // Colors are stored in sRGB.
+        let color = Rgba::new(1.0, 0.5, 0.0, 1.0);
+        let solid = Texture::solid(&context, &config, color, TextureSize::new(3, 2));
+        assert_eq!(solid.format(), wgpu::TextureFormat::Rgba8UnormSrgb);
+        let (r, g, b, a) = color.to_srgb_u8();
+        assert_eq!(read_pixels(&context, &solid), vec![[r, g, b, a]; 6]);
+        assert_eq!([r, g, b, a], [255, 188, 0, 255]);
+    }
+}
@@ -0,0 +1,115 @@
+use silica_gui::{
+    render::{GuiRenderer, Quad},
+    *,
+};
+use silica_wgpu::{AdapterFeatures, Context};
+use silica_window::{Window, run_gui_app};
+
+// Custom widgets implement `Widget`, and are added to the tree like any other widget with
+// `NodeBuilder::build_widget` or `Gui::create_widget`. Events are stored as `EventFn`s and queued
+// on the `EventExecutor` during input, so they can access the `Gui` after input handling is done.
+struct Swatch {
+    colors: Vec<Rgba>,
+    index: usize,
+    on_changed: EventFn,
+}
+
+impl Swatch {
+    const SIZE: Size = Size::new(64, 64);
+}
+impl Widget for Swatch {
+    fn measure(&mut self, _available_space: Size) -> Size {
+        Self::SIZE
+    }
+    fn input(&mut self, input: &GuiInput, executor: &mut EventExecutor, area: &Area) -> InputAction {
+        if input.blocked || !area.content_rect.contains(input.pointer) {
+            return InputAction::Pass;
+        }
+        if input.clicked {
+            self.index = (self.index + 1) % self.colors.len();
+            executor.queue(self.on_changed.clone(), Some(Box::new(self.colors[self.index])));
+            executor.request_redraw();
+        }
+        InputAction::Block
+    }
+    fn draw(&mut self, renderer: &mut GuiRenderer, area: &Area) {
+        renderer.draw_theme_quad(Quad {
+            rect: area.content_rect.to_box2d(),
+            uv: GuiRenderer::UV_WHITE,
+            color: self.colors[self.index],
+        });
+    }
+}
+
+// Builders of custom widgets can implement `WidgetBuilder` like the built-in ones, so they share the same
+// methods for setting up the widget's node.
+struct SwatchBuilder {
+    node: NodeBuilder,
+    colors: Vec<Rgba>,
+}
+
+impl SwatchBuilder {
+    fn new(colors: Vec<Rgba>) -> Self {
+        SwatchBuilder {
+            node: NodeBuilder::new(),
+            colors,
+        }
+    }
+}
+impl WidgetBuilder for SwatchBuilder {
+    type Widget = Swatch;
+    type Args = EventFn;
+    fn modify_style<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Style),
+    {
+        self.node = self.node.modify_style(f);
+        self
+    }
+    fn parent(mut self, parent: impl Into<NodeId>) -> Self {
+        self.node = self.node.parent(parent);
+        self
+    }
+    fn build(self, gui: &mut Gui, on_changed: EventFn) -> WidgetId<Swatch> {
+        let swatch = Swatch {
+            colors: self.colors,
+            index: 0,
+            on_changed,
+        };
+        self.node.build_widget(gui, swatch)
+    }
+}
+
+fn build_gui(gui: &mut Gui) -> NodeId {
+    let label = LabelBuilder::new("Click the swatch").build(gui);
+    let on_changed = EventFn::new_param(move |gui: &mut Gui, color: Rgba| {
+        label.set_text(gui, &format!("Color {color}"));
+    });
+    let swatch = SwatchBuilder::new(vec![Rgba::RED, Rgba::GREEN, Rgba::BLUE]).build(gui, on_changed);
+    NodeBuilder::new()
+        .modify_style(|style| {
+            style.direction = Direction::Column;
+            style.cross_align = Align::Start;
+            style.padding = SideOffsets::new_all_same(16);
+            style.gap = 16;
+        })
+        .child(label)
+        .child(swatch)
+        .build(gui)
+}
+
+fn main() {
+    let context = Context::init(AdapterFeatures::default());
+    run_gui_app(
+        Window::default_attributes().with_title("Custom Widget Example"),
+        context,
+        "theme/light_theme",
+        |theme| {
+            let mut gui = Gui::new(theme);
+            let root = build_gui(&mut gui);
+            gui.set_root(root);
+            gui
+        },
+    )
+    .unwrap();
+}
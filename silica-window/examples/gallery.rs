@@ -65,19 +65,16 @@ fn build_gui(gui: &mut Gui) -> NodeId {
                         padding: SideOffsets::new_all_same(8),
                         ..Default::default()
                     });
-                    let slider = Slider::new(false, move |gui, value| {
-                        label.set_text(gui, &format!("Moved Slider {}", value));
-                    });
-                    let widget = gui.create_widget(
-                        Style {
-                            background_color: Some(Color::Gutter),
-                            min_size: Size::splat(32),
-                            grow: true,
-                            ..Default::default()
-                        },
-                        slider,
-                    );
-                    gui.add_child(content, widget);
+                    SliderBuilder::new()
+                        .modify_style(|style| style.grow = true)
+                        .parent(content)
+                        .range(0.0, 100.0)
+                        .step(5.0)
+                        .ticks(25.0)
+                        .show_value(true)
+                        .build(gui, move |gui, value| {
+                            label.set_text(gui, &format!("Moved Slider {}", value));
+                        });
                     content
                 })
                 .build(gui)
@@ -1,28 +1,58 @@
-use std::rc::Rc;
+use std::{rc::Rc, time::Instant};
 
 use silica_asset::DirectorySource;
 use silica_gui::{
-    Gui, Point, Rect,
-    render::GuiResources,
+    CursorIcon, EventExecutor, Gui, InputEvent as GuiInputEvent, MouseButtonEvent as _, Point, Rect, Size,
+    WindowRegion,
+    render::{self, GuiResources},
     theme::{StandardTheme, Theme},
 };
 use silica_wgpu::{Context, SurfaceSize, TextureConfig, wgpu};
 use winit::{
     error::EventLoopError,
     event_loop::ActiveEventLoop,
-    window::{Window, WindowAttributes},
+    window::{CursorIcon as WinitCursorIcon, ResizeDirection, Window, WindowAttributes},
 };
 
 use crate::{App, InputEvent, run_app};
 
 struct GuiApp {
     gui: Gui,
+    executor: EventExecutor,
+    last_frame: Option<Instant>,
     texture_config: TextureConfig,
     resources: Option<GuiResources>,
+    pointer: Point,
+    cursor: CursorIcon,
+    /// The last frame, kept so only the gui's damaged area has to be drawn again. Only used if the surface
+    /// can be copied to.
+    canvas: Option<wgpu::Texture>,
 }
 
+impl GuiApp {
+    /// How far from the edges of the window a press in a [`WindowRegion::Resize`] node resizes it.
+    const RESIZE_BORDER: i32 = 6;
+    /// Moves or resizes the window if the press is in one of the gui's window regions. Edges take
+    /// priority, so a title bar at the top of the window can still be resized from.
+    fn drag_window(&self, window: &Window) {
+        let size = self.gui.area().size;
+        let resize = resize_direction(size, self.pointer, Self::RESIZE_BORDER)
+            .filter(|_| self.gui.in_window_region(self.pointer, WindowRegion::Resize));
+        let result = if let Some(direction) = resize {
+            window.drag_resize_window(direction)
+        } else if self.gui.in_window_region(self.pointer, WindowRegion::Drag) {
+            window.drag_window()
+        } else {
+            return;
+        };
+        if let Err(error) = result {
+            log::warn!("failed to move window: {error}");
+        }
+    }
+}
 impl App for GuiApp {
     const RUN_CONTINUOUSLY: bool = false;
+    const KEY_REPEAT: bool = true;
     fn resize_window(&mut self, context: &Context, size: SurfaceSize) {
         self.gui.set_area(Rect::new(Point::origin(), size.to_i32().cast_unit()));
         let resources = self
@@ -31,12 +61,25 @@ impl App for GuiApp {
         resources.surface_resize(context, size);
     }
     fn input(&mut self, event_loop: &ActiveEventLoop, window: &Window, event: InputEvent) {
-        let (executor, _) = self.gui.handle_input(event);
-        let redraw = executor.needs_redraw();
-        executor.execute(&mut self.gui);
+        if let GuiInputEvent::MouseMotion(pointer) = &event {
+            self.pointer = *pointer;
+        }
+        let unhandled_event = self.gui.handle_input_with(event, Instant::now(), &mut self.executor);
+        if let Some(GuiInputEvent::MouseButton(event)) = unhandled_event
+            && event.is_primary_button()
+            && event.is_pressed()
+        {
+            self.drag_window(window);
+        }
+        if self.gui.cursor() != self.cursor {
+            self.cursor = self.gui.cursor();
+            window.set_cursor(cursor_icon(self.cursor));
+        }
+        let redraw = self.executor.needs_redraw();
+        self.executor.execute_and_clear(&mut self.gui);
         if self.gui.exit_requested() {
             event_loop.exit();
-        } else if redraw || self.gui.needs_layout() {
+        } else if redraw || self.gui.needs_redraw() {
             window.request_redraw();
         }
     }
@@ -44,31 +87,49 @@ impl App for GuiApp {
         &mut self,
         _event_loop: &ActiveEventLoop,
         context: &Context,
-        view: &wgpu::TextureView,
+        frame: &wgpu::Texture,
         encoder: &mut wgpu::CommandEncoder,
     ) {
+        // Only measure time between frames while animating, so animations don't jump after being idle.
+        let now = Instant::now();
+        let dt = self
+            .last_frame
+            .map(|last_frame| (now - last_frame).as_secs_f32())
+            .unwrap_or(0.0);
+        let animating = self.gui.update(dt);
+        self.last_frame = animating.then_some(now);
         let resources = self.resources.as_mut().unwrap();
-        let background_color = self.gui.background_color();
-        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: None,
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: background_color.r as f64,
-                        g: background_color.g as f64,
-                        b: background_color.b as f64,
-                        a: 1.0,
-                    }),
-                    store: wgpu::StoreOp::Store,
-                },
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
-        self.gui.render(context, &mut pass, resources);
+        if !frame.usage().contains(wgpu::TextureUsages::COPY_DST) {
+            let view = frame.create_view(&wgpu::TextureViewDescriptor::default());
+            self.gui
+                .render_to_view(context, encoder, &view, render::LoadOp::Clear, resources);
+            return;
+        }
+        if self.canvas.as_ref().is_none_or(|canvas| canvas.size() != frame.size()) {
+            self.canvas = Some(context.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("gui canvas"),
+                size: frame.size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: frame.format(),
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            }));
+            // The new canvas is empty, so all of it has to be drawn.
+            self.gui.request_redraw();
+        }
+        let canvas = self.canvas.as_ref().unwrap();
+        let view = canvas.create_view(&wgpu::TextureViewDescriptor::default());
+        self.gui
+            .render_to_view(context, encoder, &view, render::LoadOp::LoadDamaged, resources);
+        encoder.copy_texture_to_texture(canvas.as_image_copy(), frame.as_image_copy(), frame.size());
+    }
+    fn needs_redraw(&self) -> bool {
+        self.last_frame.is_some()
+    }
+    fn min_inner_size(&self) -> Option<SurfaceSize> {
+        Some(self.gui.content_size().max(Size::zero()).to_u32().cast_unit())
     }
 }
 
@@ -92,8 +153,118 @@ where
         context,
         GuiApp {
             gui,
+            executor: EventExecutor::new(),
+            last_frame: None,
             texture_config,
             resources: None,
+            pointer: Point::origin(),
+            cursor: CursorIcon::Default,
+            canvas: None,
         },
     )
 }
+
+fn cursor_icon(cursor: CursorIcon) -> WinitCursorIcon {
+    match cursor {
+        CursorIcon::Default => WinitCursorIcon::Default,
+        CursorIcon::Pointer => WinitCursorIcon::Pointer,
+        CursorIcon::Text => WinitCursorIcon::Text,
+        CursorIcon::Grab => WinitCursorIcon::Grab,
+        CursorIcon::Grabbing => WinitCursorIcon::Grabbing,
+        CursorIcon::ColResize => WinitCursorIcon::ColResize,
+        CursorIcon::RowResize => WinitCursorIcon::RowResize,
+        CursorIcon::NotAllowed => WinitCursorIcon::NotAllowed,
+    }
+}
+
+/// The edge or corner of a window of `size` that `position` is within `border` pixels of, for resizing
+/// windows without decorations.
+pub fn resize_direction(size: Size, position: Point, border: i32) -> Option<ResizeDirection> {
+    if !Rect::from_size(size).contains(position) {
+        return None;
+    }
+    let west = position.x < border;
+    let east = position.x >= size.width - border;
+    let north = position.y < border;
+    let south = position.y >= size.height - border;
+    match (north, south, west, east) {
+        (true, _, true, _) => Some(ResizeDirection::NorthWest),
+        (true, _, _, true) => Some(ResizeDirection::NorthEast),
+        (_, true, true, _) => Some(ResizeDirection::SouthWest),
+        (_, true, _, true) => Some(ResizeDirection::SouthEast),
+        (true, ..) => Some(ResizeDirection::North),
+        (_, true, ..) => Some(ResizeDirection::South),
+        (.., true, _) => Some(ResizeDirection::West),
+        (.., true) => Some(ResizeDirection::East),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use silica_gui::{NodeBuilder, SideOffsets, Style};
+    use silica_wgpu::AdapterFeatures;
+
+    use super::*;
+
+    #[test]
+    fn min_inner_size_is_content_size() {
+        let context = Context::init(AdapterFeatures::default());
+        let texture_config = TextureConfig::new(&context, wgpu::FilterMode::Linear);
+        let theme_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../theme/dark_theme");
+        let theme =
+            StandardTheme::load(&context, &texture_config, &mut DirectorySource::new(theme_dir.into())).unwrap();
+        let mut gui = Gui::new(Rc::new(theme));
+        let fixed = |gui: &mut Gui, width, height| {
+            NodeBuilder::new()
+                .modify_style(|style| style.min_size = Size::new(width, height))
+                .build(gui)
+        };
+        let left = fixed(&mut gui, 120, 80);
+        let right = fixed(&mut gui, 60, 100);
+        let root = NodeBuilder::new()
+            .style(Style {
+                gap: 10,
+                padding: SideOffsets::new_all_same(5),
+                ..Default::default()
+            })
+            .child(left)
+            .child(right)
+            .build(&mut gui);
+        gui.set_root(root);
+        gui.set_area(Rect::from_size(Size::new(640, 480)));
+        gui.layout();
+        let app = GuiApp {
+            gui,
+            executor: EventExecutor::new(),
+            last_frame: None,
+            texture_config,
+            resources: None,
+            pointer: Point::origin(),
+            cursor: CursorIcon::Default,
+            canvas: None,
+        };
+        assert_eq!(app.gui.content_size(), Size::new(200, 110));
+        assert_eq!(app.min_inner_size(), Some(SurfaceSize::new(200, 110)));
+    }
+
+    #[test]
+    fn resize_direction_at_edges() {
+        let size = Size::new(100, 80);
+        let at = |x, y| resize_direction(size, Point::new(x, y), 4);
+        assert_eq!(at(50, 40), None);
+        assert_eq!(at(50, 0), Some(ResizeDirection::North));
+        assert_eq!(at(50, 79), Some(ResizeDirection::South));
+        assert_eq!(at(3, 40), Some(ResizeDirection::West));
+        assert_eq!(at(96, 40), Some(ResizeDirection::East));
+        assert_eq!(at(0, 0), Some(ResizeDirection::NorthWest));
+        assert_eq!(at(99, 2), Some(ResizeDirection::NorthEast));
+        assert_eq!(at(1, 78), Some(ResizeDirection::SouthWest));
+        assert_eq!(at(99, 79), Some(ResizeDirection::SouthEast));
+        // Just inside the border, and outside the window.
+        assert_eq!(at(4, 4), None);
+        assert_eq!(at(95, 75), None);
+        assert_eq!(at(-1, 40), None);
+        assert_eq!(at(50, 80), None);
+    }
+}
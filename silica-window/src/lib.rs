@@ -2,12 +2,13 @@ mod gui;
 
 use std::sync::Arc;
 
-use silica_gui::{Hotkey, Point};
+use silica_gui::{Hotkey, Modifiers, NavKey, Point, trace_scope};
 use silica_wgpu::{Context, Surface, SurfaceSize, wgpu};
 use winit::{
     application::ApplicationHandler,
+    dpi::PhysicalSize,
     error::EventLoopError,
-    event::{ElementState, WindowEvent},
+    event::{ElementState, MouseScrollDelta, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     keyboard::{KeyCode, ModifiersState, PhysicalKey, SmolStr},
     window::WindowId,
@@ -16,7 +17,7 @@ pub use winit::{
     event::MouseButton,
     event_loop::ActiveEventLoop,
     keyboard,
-    window::{Icon, Window, WindowAttributes},
+    window::{Icon, ResizeDirection, Window, WindowAttributes},
 };
 
 pub use crate::gui::*;
@@ -26,19 +27,24 @@ pub struct KeyboardEvent {
     physical_key: KeyCode,
     text: Option<SmolStr>,
     modifiers: ModifiersState,
+    repeat: bool,
 }
 
 impl KeyboardEvent {
     pub fn is_pressed(&self) -> bool {
         self.state == ElementState::Pressed
     }
+    pub fn is_repeat(&self) -> bool {
+        self.repeat
+    }
     pub fn physical_key(&self) -> KeyCode {
         self.physical_key
     }
 }
 impl silica_gui::KeyboardEvent for KeyboardEvent {
     fn to_hotkey(&self) -> Option<Hotkey> {
-        if self.is_pressed() {
+        // held keys shouldn't make buttons auto-fire, repeats only reach widgets as nav keys
+        if self.is_pressed() && !self.repeat {
             self.text.as_ref().map(|text| Hotkey {
                 key: text.chars().next().unwrap(),
                 mod1: self.modifiers.control_key(),
@@ -48,9 +54,22 @@ impl silica_gui::KeyboardEvent for KeyboardEvent {
             None
         }
     }
+    fn to_nav_key(&self) -> Option<NavKey> {
+        if !self.is_pressed() {
+            return None;
+        }
+        match self.physical_key {
+            KeyCode::ArrowUp => Some(NavKey::Up),
+            KeyCode::ArrowDown => Some(NavKey::Down),
+            KeyCode::Enter | KeyCode::NumpadEnter if !self.repeat => Some(NavKey::Enter),
+            KeyCode::Escape if !self.repeat => Some(NavKey::Escape),
+            KeyCode::Backspace => Some(NavKey::Backspace),
+            _ => None,
+        }
+    }
 }
 
-pub struct MouseButtonEvent(MouseButton, ElementState);
+pub struct MouseButtonEvent(MouseButton, ElementState, ModifiersState);
 
 impl MouseButtonEvent {
     pub fn button(&self) -> MouseButton {
@@ -61,27 +80,50 @@ impl silica_gui::MouseButtonEvent for MouseButtonEvent {
     fn is_primary_button(&self) -> bool {
         self.0 == MouseButton::Left
     }
+    fn is_secondary_button(&self) -> bool {
+        self.0 == MouseButton::Right
+    }
     fn is_pressed(&self) -> bool {
         self.1.is_pressed()
     }
+    fn modifiers(&self) -> Modifiers {
+        Modifiers {
+            shift: self.2.shift_key(),
+            mod1: self.2.control_key(),
+            mod2: self.2.alt_key(),
+        }
+    }
 }
 
 pub type InputEvent = silica_gui::InputEvent<KeyboardEvent, MouseButtonEvent>;
 
 pub trait App {
     const RUN_CONTINUOUSLY: bool;
+    /// Whether key events generated by holding down a key are delivered to [`App::input`].
+    const KEY_REPEAT: bool = false;
     fn close_window(&mut self, event_loop: &ActiveEventLoop) {
         event_loop.exit();
     }
     fn resize_window(&mut self, context: &Context, size: SurfaceSize);
     fn input(&mut self, event_loop: &ActiveEventLoop, window: &Window, event: InputEvent);
+    /// Records the frame into `encoder`. `frame` is the surface texture, which can also be copied to if
+    /// its usage includes [`wgpu::TextureUsages::COPY_DST`].
     fn render(
         &mut self,
         event_loop: &ActiveEventLoop,
         context: &Context,
-        view: &wgpu::TextureView,
+        frame: &wgpu::Texture,
         encoder: &mut wgpu::CommandEncoder,
     );
+    /// Whether another frame should be rendered after this one, for apps that don't run continuously.
+    fn needs_redraw(&self) -> bool {
+        false
+    }
+    /// The smallest size the window can be resized to, checked after each frame. It's capped to the size
+    /// of the monitor the window is on.
+    fn min_inner_size(&self) -> Option<SurfaceSize> {
+        None
+    }
 }
 
 struct WindowApp<T> {
@@ -90,22 +132,61 @@ struct WindowApp<T> {
     context: Context,
     surface: Surface,
     modifiers: ModifiersState,
+    min_inner_size: Option<SurfaceSize>,
     app: T,
 }
 
 impl<T: App> WindowApp<T> {
-    fn render(&mut self, event_loop: &ActiveEventLoop) {
-        let frame = self.surface.acquire(&self.context);
-        let view: wgpu::TextureView = frame.texture.create_view(&wgpu::TextureViewDescriptor::default());
+    /// Pixels scrolled per line by wheels that report lines rather than pixels.
+    const WHEEL_LINE_HEIGHT: f32 = 48.0;
+    /// Renders and presents a frame, returning false if the frame was skipped.
+    fn render(&mut self, event_loop: &ActiveEventLoop) -> bool {
+        trace_scope!("frame");
+        let Some(frame) = frame_or_skip(self.surface.acquire(&self.context)) else {
+            return false;
+        };
         let mut encoder = self
             .context
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
-        self.app.render(event_loop, &self.context, &view, &mut encoder);
-        self.context.queue.submit([encoder.finish()]);
-        self.window.as_ref().unwrap().pre_present_notify();
-        frame.present();
+        self.app.render(event_loop, &self.context, &frame.texture, &mut encoder);
+        {
+            trace_scope!("frame submit");
+            self.context.queue.submit([encoder.finish()]);
+            self.window.as_ref().unwrap().pre_present_notify();
+            frame.present();
+        }
+        self.update_min_inner_size();
+        true
+    }
+    /// Whether to draw another frame after one that was presented, or skipped because the surface couldn't
+    /// be acquired. Apps that run continuously keep drawing, which retries the surface every frame. Others
+    /// wait for the next resize or input event after a skipped frame, as requesting another frame right away
+    /// would spin while the error lasts.
+    fn redraw_after(&self, presented: bool) -> bool {
+        T::RUN_CONTINUOUSLY || (presented && self.app.needs_redraw())
+    }
+    fn update_min_inner_size(&mut self) {
+        let min_inner_size = self.app.min_inner_size();
+        if min_inner_size == self.min_inner_size {
+            return;
+        }
+        self.min_inner_size = min_inner_size;
+        let window = self.window.as_ref().unwrap();
+        let monitor_size = window.current_monitor().map(|monitor| monitor.size());
+        window.set_min_inner_size(min_inner_size.map(|size| cap_min_inner_size(size, monitor_size)));
+    }
+}
+
+/// Limits a minimum window size to the monitor's size, so content larger than the screen doesn't force
+/// the window past it.
+fn cap_min_inner_size(size: SurfaceSize, monitor_size: Option<PhysicalSize<u32>>) -> PhysicalSize<u32> {
+    let mut size = PhysicalSize::new(size.width, size.height);
+    if let Some(monitor_size) = monitor_size {
+        size.width = size.width.min(monitor_size.width);
+        size.height = size.height.min(monitor_size.height);
     }
+    size
 }
 
 impl<T: App> ApplicationHandler for WindowApp<T> {
@@ -134,8 +215,8 @@ impl<T: App> ApplicationHandler for WindowApp<T> {
                 window.request_redraw();
             }
             WindowEvent::RedrawRequested => {
-                self.render(event_loop);
-                if T::RUN_CONTINUOUSLY && !event_loop.exiting() {
+                let presented = self.render(event_loop);
+                if self.redraw_after(presented) && !event_loop.exiting() {
                     self.window.as_ref().unwrap().request_redraw();
                 }
             }
@@ -150,16 +231,23 @@ impl<T: App> ApplicationHandler for WindowApp<T> {
                 self.app.input(
                     event_loop,
                     window,
-                    InputEvent::MouseButton(MouseButtonEvent(button, state)),
+                    InputEvent::MouseButton(MouseButtonEvent(button, state, self.modifiers)),
                 );
             }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => y * Self::WHEEL_LINE_HEIGHT,
+                    MouseScrollDelta::PixelDelta(position) => position.y as f32,
+                };
+                self.app.input(event_loop, window, InputEvent::MouseWheel(delta));
+            }
             WindowEvent::KeyboardInput {
                 event,
                 is_synthetic: false,
                 ..
             } => {
                 if let PhysicalKey::Code(key_code) = event.physical_key
-                    && !event.repeat
+                    && (T::KEY_REPEAT || !event.repeat)
                 {
                     self.app.input(
                         event_loop,
@@ -169,6 +257,7 @@ impl<T: App> ApplicationHandler for WindowApp<T> {
                             physical_key: key_code,
                             text: event.text,
                             modifiers: self.modifiers,
+                            repeat: event.repeat,
                         }),
                     );
                 }
@@ -181,6 +270,17 @@ impl<T: App> ApplicationHandler for WindowApp<T> {
     }
 }
 
+/// Returns the acquired frame, or logs the error and returns `None` if the frame has to be skipped.
+fn frame_or_skip(frame: Result<wgpu::SurfaceTexture, wgpu::SurfaceError>) -> Option<wgpu::SurfaceTexture> {
+    match frame {
+        Ok(frame) => Some(frame),
+        Err(error) => {
+            log::warn!("skipping frame, failed to acquire surface texture: {error}");
+            None
+        }
+    }
+}
+
 pub fn run_app<T: App>(window_attributes: WindowAttributes, context: Context, app: T) -> Result<(), EventLoopError> {
     let event_loop = EventLoop::new()?;
     event_loop.set_control_flow(if T::RUN_CONTINUOUSLY {
@@ -194,8 +294,109 @@ pub fn run_app<T: App>(window_attributes: WindowAttributes, context: Context, ap
         context,
         surface: Surface::new(),
         modifiers: ModifiersState::empty(),
+        min_inner_size: None,
         app,
     };
     event_loop.run_app(&mut window_app)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use silica_wgpu::AdapterFeatures;
+
+    use super::*;
+
+    /// Always wants another frame, and can't be rendered.
+    struct TestApp<const CONTINUOUS: bool>;
+
+    impl<const CONTINUOUS: bool> App for TestApp<CONTINUOUS> {
+        const RUN_CONTINUOUSLY: bool = CONTINUOUS;
+        fn resize_window(&mut self, _context: &Context, _size: SurfaceSize) {}
+        fn input(&mut self, _event_loop: &ActiveEventLoop, _window: &Window, _event: InputEvent) {}
+        fn render(
+            &mut self,
+            _event_loop: &ActiveEventLoop,
+            _context: &Context,
+            _frame: &wgpu::Texture,
+            _encoder: &mut wgpu::CommandEncoder,
+        ) {
+            unreachable!()
+        }
+        fn needs_redraw(&self) -> bool {
+            true
+        }
+    }
+
+    fn window_app<T: App>(app: T) -> WindowApp<T> {
+        WindowApp {
+            window_attributes: WindowAttributes::default(),
+            window: None,
+            context: Context::init(AdapterFeatures::default()),
+            surface: Surface::new(),
+            modifiers: ModifiersState::empty(),
+            min_inner_size: None,
+            app,
+        }
+    }
+
+    #[test]
+    fn persistent_acquire_error_skips_frames() {
+        let continuous = window_app(TestApp::<true>);
+        let waiting = window_app(TestApp::<false>);
+        for _ in 0..3 {
+            assert!(frame_or_skip(Err(wgpu::SurfaceError::Outdated)).is_none());
+            assert!(continuous.redraw_after(false));
+            assert!(!waiting.redraw_after(false));
+        }
+        assert!(waiting.redraw_after(true));
+    }
+
+    #[test]
+    fn min_inner_size_capped_to_monitor() {
+        let size = SurfaceSize::new(800, 3000);
+        assert_eq!(cap_min_inner_size(size, None), PhysicalSize::new(800, 3000));
+        let monitor = Some(PhysicalSize::new(1920, 1080));
+        assert_eq!(cap_min_inner_size(size, monitor), PhysicalSize::new(800, 1080));
+    }
+
+    /// Presses `key`, then holds it for `repeats` more events.
+    fn held_key(key: KeyCode, text: Option<&str>, repeats: usize) -> Vec<KeyboardEvent> {
+        (0..=repeats)
+            .map(|i| KeyboardEvent {
+                state: ElementState::Pressed,
+                physical_key: key,
+                text: text.map(SmolStr::new),
+                modifiers: ModifiersState::empty(),
+                repeat: i > 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn held_backspace_repeats_nav_key() {
+        use silica_gui::KeyboardEvent;
+
+        let nav_keys: Vec<_> = held_key(KeyCode::Backspace, None, 3)
+            .iter()
+            .map(|event| event.to_nav_key())
+            .collect();
+        assert_eq!(nav_keys, [Some(NavKey::Backspace); 4]);
+    }
+
+    #[test]
+    fn held_hotkey_fires_once() {
+        use silica_gui::KeyboardEvent;
+
+        let events = held_key(KeyCode::KeyA, Some("a"), 3);
+        let hotkeys: Vec<_> = events.iter().filter_map(|event| event.to_hotkey()).collect();
+        assert_eq!(hotkeys.len(), 1);
+        assert_eq!(hotkeys[0].key, 'a');
+        // Held Enter doesn't keep activating the focused widget either.
+        let enter: Vec<_> = held_key(KeyCode::Enter, None, 3)
+            .iter()
+            .map(|event| event.to_nav_key())
+            .collect();
+        assert_eq!(enter, [Some(NavKey::Enter), None, None, None]);
+    }
+}